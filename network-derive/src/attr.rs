@@ -0,0 +1,198 @@
+//! The `#[netz(...)]` attributes of a derived type, its' variants and its'
+//! fields.
+
+use proc_macro2::Span;
+use syn::{Attribute, Expr, ExprLit, Lit, LitInt, Type};
+
+/// The name of the attribute, which configures the derived code.
+const ATTRIBUTE: &str = "netz";
+
+/// The attributes of the struct or enum itself.
+#[derive(Default)]
+pub struct ContainerAttributes {
+    /// The type of the enum discriminator, `#[netz(discriminator = u16)]`.
+    pub discriminator: Option<Type>,
+}
+
+impl ContainerAttributes {
+    pub fn parse(attributes: &[Attribute]) -> syn::Result<Self> {
+        let mut result = Self::default();
+
+        for attribute in netz_attributes(attributes) {
+            attribute.parse_nested_meta(|meta| {
+                if meta.path.is_ident("discriminator") {
+                    result.discriminator = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `discriminator = <type>`"))
+                }
+            })?;
+        }
+
+        Ok(result)
+    }
+}
+
+/// The attributes of an enum variant.
+#[derive(Default)]
+pub struct VariantAttributes {
+    /// The discriminator, `#[netz(discriminator = 3)]`.
+    pub discriminator: Option<(u64, Span)>,
+}
+
+impl VariantAttributes {
+    /// Reads the attributes and the explicit discriminant of the variant,
+    /// such as `Variant = 3`. Both must not be given at once.
+    pub fn parse(attributes: &[Attribute], discriminant: Option<&Expr>) -> syn::Result<Self> {
+        let mut result = Self::default();
+
+        for attribute in netz_attributes(attributes) {
+            attribute.parse_nested_meta(|meta| {
+                if meta.path.is_ident("discriminator") {
+                    let literal: LitInt = meta.value()?.parse()?;
+                    result.discriminator = Some((literal.base10_parse()?, literal.span()));
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `discriminator = <integer>`"))
+                }
+            })?;
+        }
+
+        if let Some(expression) = discriminant {
+            let Expr::Lit(ExprLit {
+                lit: Lit::Int(literal),
+                ..
+            }) = expression
+            else {
+                return Err(syn::Error::new_spanned(
+                    expression,
+                    "the discriminant has to be an integer literal",
+                ));
+            };
+
+            if result.discriminator.is_some() {
+                return Err(syn::Error::new_spanned(
+                    expression,
+                    "the variant has a discriminant and a `#[netz(discriminator)]`",
+                ));
+            }
+
+            result.discriminator = Some((literal.base10_parse()?, literal.span()));
+        }
+
+        Ok(result)
+    }
+}
+
+/// The attributes of a struct or variant field.
+#[derive(Default)]
+pub struct FieldAttributes {
+    /// The field is not encoded, `#[netz(skip)]`. It is decoded with its'
+    /// default value.
+    pub skip: bool,
+    /// The integer is encoded in fixed width, `#[netz(fixed)]`.
+    pub fixed: bool,
+    /// The position of the field on the wire, `#[netz(order = 2)]`.
+    pub order: Option<(u32, Span)>,
+}
+
+impl FieldAttributes {
+    pub fn parse(attributes: &[Attribute]) -> syn::Result<Self> {
+        let mut result = Self::default();
+
+        for attribute in netz_attributes(attributes) {
+            attribute.parse_nested_meta(|meta| {
+                if meta.path.is_ident("skip") {
+                    result.skip = true;
+                } else if meta.path.is_ident("fixed") {
+                    result.fixed = true;
+                } else if meta.path.is_ident("varint") {
+                    // integers are varints by default
+                    result.fixed = false;
+                } else if meta.path.is_ident("order") {
+                    let literal: LitInt = meta.value()?.parse()?;
+                    result.order = Some((literal.base10_parse()?, literal.span()));
+                } else {
+                    return Err(
+                        meta.error("expected `skip`, `fixed`, `varint` or `order = <integer>`")
+                    );
+                }
+
+                Ok(())
+            })?;
+
+            if result.skip && (result.fixed || result.order.is_some()) {
+                return Err(syn::Error::new_spanned(
+                    attribute,
+                    "a skipped field is not encoded, it can not be `fixed` or have an `order`",
+                ));
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+/// The attributes named `netz`.
+fn netz_attributes(attributes: &[Attribute]) -> impl Iterator<Item = &Attribute> {
+    attributes
+        .iter()
+        .filter(|attribute| attribute.path().is_ident(ATTRIBUTE))
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::{parse_quote, Field};
+
+    use super::*;
+
+    fn field(field: Field) -> syn::Result<FieldAttributes> {
+        FieldAttributes::parse(&field.attrs)
+    }
+
+    #[test]
+    fn field_attributes() {
+        let attributes = field(parse_quote!(#[netz(fixed, order = 2)] id: u64)).unwrap();
+        assert!(attributes.fixed && !attributes.skip);
+        assert_eq!(attributes.order.map(|(order, _)| order), Some(2));
+
+        let attributes = field(parse_quote!(#[doc = ""] #[netz(skip)] cache: u64)).unwrap();
+        assert!(attributes.skip);
+    }
+
+    #[test]
+    fn invalid_field_attributes() {
+        let error = field(parse_quote!(#[netz(fast)] id: u64)).err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "expected `skip`, `fixed`, `varint` or `order = <integer>`"
+        );
+
+        let error = field(parse_quote!(#[netz(skip, fixed)] id: u64))
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.to_string(),
+            "a skipped field is not encoded, it can not be `fixed` or have an `order`"
+        );
+    }
+
+    #[test]
+    fn variant_discriminator() {
+        let variant: syn::Variant = parse_quote!(A = 7);
+        let attributes =
+            VariantAttributes::parse(&variant.attrs, variant.discriminant.as_ref().map(|d| &d.1));
+        assert_eq!(
+            attributes.unwrap().discriminator.map(|(value, _)| value),
+            Some(7)
+        );
+
+        let variant: syn::Variant = parse_quote!(
+            #[netz(discriminator = 1)]
+            A = 7
+        );
+        let attributes =
+            VariantAttributes::parse(&variant.attrs, variant.discriminant.as_ref().map(|d| &d.1));
+        assert!(attributes.is_err());
+    }
+}
@@ -0,0 +1,88 @@
+//! The expansion of `#[derive(NetzDecode)]`.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput};
+
+use crate::{
+    attr::ContainerAttributes,
+    bounded_generics,
+    fields::FieldList,
+    variants::{discriminator_type, parse_variants},
+};
+
+pub fn derive(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let attributes = ContainerAttributes::parse(&input.attrs)?;
+    let name = input.ident.to_string();
+
+    let body = match &input.data {
+        Data::Struct(data) => {
+            let fields = FieldList::parse(&data.fields)?;
+            let constructor = fields.constructor();
+            let decode = fields.wire_order().into_iter().map(|field| field.decode());
+
+            quote! {
+                #(#decode)*
+                ::core::result::Result::Ok(Self #constructor)
+            }
+        }
+        Data::Enum(data) => {
+            let ty = discriminator_type(&attributes);
+            let variants = parse_variants(data)?;
+
+            let arms = variants.iter().map(|variant| {
+                let ident = &variant.ident;
+                let constructor = variant.fields.constructor();
+                let discriminator = variant.literal();
+                let decode = variant
+                    .fields
+                    .wire_order()
+                    .into_iter()
+                    .map(|field| field.decode());
+
+                quote! {
+                    #discriminator => {
+                        #(#decode)*
+                        ::core::result::Result::Ok(Self::#ident #constructor)
+                    }
+                }
+            });
+
+            quote! {
+                let discriminator = <#ty as ::network_library::decode::Decode>::decode(reader)?;
+
+                #[allow(unreachable_patterns)]
+                match discriminator {
+                    #(#arms)*
+                    other => ::core::result::Result::Err(::network_library::decode::DecodeError::InvalidDiscriminant {
+                        name: #name,
+                        value: other as u128,
+                    }),
+                }
+            }
+        }
+        Data::Union(data) => {
+            return Err(syn::Error::new_spanned(
+                data.union_token,
+                "`NetzDecode` can not be derived for unions",
+            ))
+        }
+    };
+
+    let ident = &input.ident;
+    let generics = bounded_generics(&input.generics, quote!(::network_library::decode::Decode));
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics ::network_library::decode::Decode for #ident #type_generics
+        #where_clause
+        {
+            fn decode<R: ::network_library::decode::Reader + ?Sized>(
+                reader: &mut R,
+            ) -> ::core::result::Result<Self, ::network_library::decode::DecodeError> {
+                #body
+            }
+        }
+    })
+}
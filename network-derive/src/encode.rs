@@ -0,0 +1,88 @@
+//! The expansion of `#[derive(NetzEncode)]`.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput};
+
+use crate::{
+    attr::ContainerAttributes,
+    bounded_generics,
+    fields::FieldList,
+    variants::{discriminator_type, parse_variants},
+};
+
+pub fn derive(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let attributes = ContainerAttributes::parse(&input.attrs)?;
+
+    let body = match &input.data {
+        Data::Struct(data) => {
+            let fields = FieldList::parse(&data.fields)?;
+            let pattern = fields.pattern();
+            let encode = fields.wire_order().into_iter().map(|field| field.encode());
+
+            quote! {
+                let Self #pattern = self;
+                #(#encode)*
+                ::core::result::Result::Ok(())
+            }
+        }
+        Data::Enum(data) => {
+            let ty = discriminator_type(&attributes);
+            let variants = parse_variants(data)?;
+
+            if variants.is_empty() {
+                return Ok(implementation(input, quote!(match *self {})));
+            }
+
+            let arms = variants.iter().map(|variant| {
+                let ident = &variant.ident;
+                let pattern = variant.fields.pattern();
+                let discriminator = variant.literal();
+                let encode = variant
+                    .fields
+                    .wire_order()
+                    .into_iter()
+                    .map(|field| field.encode());
+
+                quote! {
+                    Self::#ident #pattern => {
+                        let discriminator: #ty = #discriminator;
+                        ::network_library::encode::Encode::encode(&discriminator, writer)?;
+                        #(#encode)*
+                        ::core::result::Result::Ok(())
+                    }
+                }
+            });
+
+            quote!(match self { #(#arms)* })
+        }
+        Data::Union(data) => {
+            return Err(syn::Error::new_spanned(
+                data.union_token,
+                "`NetzEncode` can not be derived for unions",
+            ))
+        }
+    };
+
+    Ok(implementation(input, body))
+}
+
+fn implementation(input: &DeriveInput, body: TokenStream) -> TokenStream {
+    let name = &input.ident;
+    let generics = bounded_generics(&input.generics, quote!(::network_library::encode::Encode));
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics ::network_library::encode::Encode for #name #type_generics
+        #where_clause
+        {
+            fn encode<W: ::network_library::encode::Writer + ?Sized>(
+                &self,
+                writer: &mut W,
+            ) -> ::std::io::Result<()> {
+                #body
+            }
+        }
+    }
+}
@@ -0,0 +1,167 @@
+//! The fields of a struct or enum variant, in the order they are encoded.
+
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::{Fields, Ident, Member, Type};
+
+use crate::attr::FieldAttributes;
+
+/// A field of a struct or enum variant.
+pub struct Field {
+    /// The name or the index of the field.
+    pub member: Member,
+    /// The name the value of the field is bound to in patterns and while
+    /// it is decoded.
+    pub binding: Ident,
+    pub ty: Type,
+    pub attributes: FieldAttributes,
+}
+
+impl Field {
+    /// The expression encoding the value of the binding, which is a
+    /// reference to the field value.
+    pub fn encode(&self) -> TokenStream {
+        let binding = &self.binding;
+
+        if self.attributes.fixed {
+            quote!(::network_library::encode::Encode::encode(
+                &::network_library::encode::Fixed(*#binding),
+                writer
+            )?;)
+        } else {
+            quote!(::network_library::encode::Encode::encode(#binding, writer)?;)
+        }
+    }
+
+    /// The statement decoding the value of the field into the binding.
+    pub fn decode(&self) -> TokenStream {
+        let binding = &self.binding;
+        let ty = &self.ty;
+
+        if self.attributes.fixed {
+            quote!(let #binding = <::network_library::encode::Fixed<#ty>
+                as ::network_library::decode::Decode>::decode(reader)?.0;)
+        } else {
+            quote!(let #binding = <#ty as ::network_library::decode::Decode>::decode(reader)?;)
+        }
+    }
+}
+
+/// The fields of a struct or enum variant.
+pub struct FieldList {
+    pub fields: Fields,
+    pub list: Vec<Field>,
+}
+
+impl FieldList {
+    pub fn parse(fields: &Fields) -> syn::Result<Self> {
+        let list = fields
+            .iter()
+            .enumerate()
+            .map(|(index, field)| {
+                Ok(Field {
+                    member: match &field.ident {
+                        Some(ident) => Member::Named(ident.clone()),
+                        None => Member::Unnamed(index.into()),
+                    },
+                    binding: format_ident!("__field{index}"),
+                    ty: field.ty.clone(),
+                    attributes: FieldAttributes::parse(&field.attrs)?,
+                })
+            })
+            .collect::<syn::Result<Vec<_>>>()?;
+
+        let result = Self {
+            fields: fields.clone(),
+            list,
+        };
+        result.check_order()?;
+        Ok(result)
+    }
+
+    /// Either none or all encoded fields declare their' distinct order.
+    fn check_order(&self) -> syn::Result<()> {
+        let orders: Vec<Option<(u32, Span)>> = self
+            .list
+            .iter()
+            .filter(|field| !field.attributes.skip)
+            .map(|field| field.attributes.order)
+            .collect();
+
+        if orders.iter().all(Option::is_none) {
+            return Ok(());
+        }
+
+        let mut seen = vec![];
+
+        for (field, order) in self.encoded().zip(&orders) {
+            let Some((order, span)) = order else {
+                return Err(syn::Error::new_spanned(
+                    &field.ty,
+                    "the field has no `#[netz(order)]`, but other fields have one",
+                ));
+            };
+
+            if seen.contains(order) {
+                return Err(syn::Error::new(
+                    *span,
+                    format!("the order {order} is used by another field"),
+                ));
+            }
+
+            seen.push(*order);
+        }
+
+        Ok(())
+    }
+
+    /// The fields, which are not skipped, in declaration order.
+    fn encoded(&self) -> impl Iterator<Item = &Field> {
+        self.list.iter().filter(|field| !field.attributes.skip)
+    }
+
+    /// The fields, which are not skipped, in the order they are encoded.
+    pub fn wire_order(&self) -> Vec<&Field> {
+        let mut fields: Vec<&Field> = self.encoded().collect();
+        fields.sort_by_key(|field| field.attributes.order.map(|(order, _)| order));
+        fields
+    }
+
+    /// The pattern binding the fields after a path, such as `{ a: __field0 }`
+    /// or `(__field0, _)`. Skipped fields are not bound.
+    pub fn pattern(&self) -> TokenStream {
+        let bindings = self.list.iter().map(|field| match field.attributes.skip {
+            true => quote!(_),
+            false => {
+                let binding = &field.binding;
+                quote!(#binding)
+            }
+        });
+
+        self.construct(bindings)
+    }
+
+    /// The expression constructing the value after a path from the decoded
+    /// bindings. Skipped fields take their' default value.
+    pub fn constructor(&self) -> TokenStream {
+        let values = self.list.iter().map(|field| match field.attributes.skip {
+            true => quote!(::core::default::Default::default()),
+            false => {
+                let binding = &field.binding;
+                quote!(#binding)
+            }
+        });
+
+        self.construct(values)
+    }
+
+    fn construct(&self, values: impl Iterator<Item = TokenStream>) -> TokenStream {
+        let members = self.list.iter().map(|field| &field.member);
+
+        match &self.fields {
+            Fields::Named(_) => quote!({ #(#members: #values),* }),
+            Fields::Unnamed(_) => quote!(( #(#values),* )),
+            Fields::Unit => quote!(),
+        }
+    }
+}
@@ -0,0 +1,83 @@
+//! Derive macros for the `Encode` and `Decode` traits of the
+//! `network-library`, so types written by hand speak the same wire format
+//! as the types declared in network files. The derived code refers to the
+//! crate `network_library`, which has to be a dependency.
+//!
+//! Structs encode their' fields in declaration order, enums encode the
+//! discriminator of the variant followed by its' fields. These attributes
+//! change the encoding:
+//!
+//! - `#[netz(discriminator = u16)]` on an enum sets the type of the
+//!   discriminator, which is `u8` by default.
+//! - `#[netz(discriminator = 3)]` or `Variant = 3` on a variant sets its'
+//!   discriminator. A variant without one continues counting from its'
+//!   predecessor, starting with 0.
+//! - `#[netz(fixed)]` on an integer field encodes it in fixed width and
+//!   little endian order instead of 7-bit encoding, like `@fixed` in a
+//!   network file. `#[netz(varint)]` is the default.
+//! - `#[netz(order = 2)]` on a field sets its' position on the wire. Either
+//!   none or all encoded fields of a struct or variant have an order.
+//! - `#[netz(skip)]` on a field does not encode it. It is decoded with its'
+//!   default value.
+//!
+//! ```
+//! use network_derive::{NetzDecode, NetzEncode};
+//! use network_library::{decode::Decode, encode::Encode};
+//!
+//! #[derive(NetzEncode, NetzDecode, Debug, PartialEq)]
+//! struct Block {
+//!     height: u64,
+//!     #[netz(fixed)]
+//!     hash: u32,
+//!     #[netz(skip)]
+//!     verified: bool,
+//! }
+//!
+//! let block = Block { height: 300, hash: 1, verified: true };
+//! let bytes = block.to_bytes();
+//!
+//! assert_eq!(bytes, vec![0xAC, 0x02, 1, 0, 0, 0]);
+//! assert_eq!(Block::from_bytes(&bytes).unwrap(), Block { verified: false, ..block });
+//! ```
+
+mod attr;
+mod decode;
+mod encode;
+mod fields;
+mod variants;
+
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, parse_quote, DeriveInput, GenericParam, Generics};
+
+/// Derives `network_library::encode::Encode` for a struct or enum.
+#[proc_macro_derive(NetzEncode, attributes(netz))]
+pub fn derive_encode(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    encode::derive(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derives `network_library::decode::Decode` for a struct or enum.
+#[proc_macro_derive(NetzDecode, attributes(netz))]
+pub fn derive_decode(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    decode::derive(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Adds the bound to every type parameter.
+fn bounded_generics(generics: &Generics, bound: proc_macro2::TokenStream) -> Generics {
+    let mut generics = generics.clone();
+
+    for param in &mut generics.params {
+        if let GenericParam::Type(param) = param {
+            param.bounds.push(parse_quote!(#bound));
+        }
+    }
+
+    generics
+}
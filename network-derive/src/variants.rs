@@ -0,0 +1,139 @@
+//! The variants of an enum with their' resolved discriminators.
+
+use proc_macro2::Literal;
+use quote::quote;
+use syn::{DataEnum, Ident, Type};
+
+use crate::{
+    attr::{ContainerAttributes, VariantAttributes},
+    fields::FieldList,
+};
+
+/// A variant of an enum.
+pub struct Variant {
+    pub ident: Ident,
+    pub fields: FieldList,
+    pub discriminator: u64,
+}
+
+impl Variant {
+    /// The discriminator as an unsuffixed integer literal, so it takes the
+    /// type of the discriminator.
+    pub fn literal(&self) -> Literal {
+        Literal::u64_unsuffixed(self.discriminator)
+    }
+}
+
+/// The type of the discriminator, `u8` unless declared otherwise.
+pub fn discriminator_type(attributes: &ContainerAttributes) -> Type {
+    match &attributes.discriminator {
+        Some(ty) => ty.clone(),
+        None => syn::parse2(quote!(u8)).expect("`u8` is a type"),
+    }
+}
+
+/// Reads the variants of the enum. Like the members of an enum in a
+/// network file, a variant without a discriminator continues counting
+/// from its' predecessor, starting with 0.
+pub fn parse_variants(data: &DataEnum) -> syn::Result<Vec<Variant>> {
+    let mut variants: Vec<Variant> = vec![];
+
+    for variant in &data.variants {
+        let discriminant = variant.discriminant.as_ref().map(|(_, expr)| expr);
+        let attributes = VariantAttributes::parse(&variant.attrs, discriminant)?;
+
+        let discriminator = match (attributes.discriminator, variants.last()) {
+            (Some((value, span)), _) => {
+                if let Some(other) = variants.iter().find(|other| other.discriminator == value) {
+                    return Err(syn::Error::new(
+                        span,
+                        format!("the discriminator {value} is used by `{}`", other.ident),
+                    ));
+                }
+
+                value
+            }
+            (None, None) => 0,
+            (None, Some(previous)) => {
+                let value = previous.discriminator.checked_add(1).ok_or_else(|| {
+                    syn::Error::new_spanned(&variant.ident, "the discriminator overflows")
+                })?;
+
+                if let Some(other) = variants.iter().find(|other| other.discriminator == value) {
+                    return Err(syn::Error::new_spanned(
+                        &variant.ident,
+                        format!("the discriminator {value} is used by `{}`", other.ident),
+                    ));
+                }
+
+                value
+            }
+        };
+
+        variants.push(Variant {
+            ident: variant.ident.clone(),
+            fields: FieldList::parse(&variant.fields)?,
+            discriminator,
+        });
+    }
+
+    Ok(variants)
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::{parse_quote, DeriveInput};
+
+    use super::*;
+
+    fn variants(input: DeriveInput) -> syn::Result<Vec<(String, u64)>> {
+        let syn::Data::Enum(data) = input.data else {
+            unreachable!()
+        };
+
+        Ok(parse_variants(&data)?
+            .into_iter()
+            .map(|variant| (variant.ident.to_string(), variant.discriminator))
+            .collect())
+    }
+
+    #[test]
+    fn continue_counting() {
+        let input = parse_quote! {
+            enum Message {
+                Ping,
+                #[netz(discriminator = 5)]
+                Text(String),
+                Close { code: u16 },
+                Reset = 2,
+                Other,
+            }
+        };
+
+        assert_eq!(
+            variants(input).unwrap(),
+            vec![
+                ("Ping".into(), 0),
+                ("Text".into(), 5),
+                ("Close".into(), 6),
+                ("Reset".into(), 2),
+                ("Other".into(), 3),
+            ]
+        );
+    }
+
+    #[test]
+    fn duplicate_discriminator() {
+        let input = parse_quote! {
+            enum Message {
+                #[netz(discriminator = 1)]
+                Ping,
+                Pong = 0,
+                Close,
+            }
+        };
+
+        let error = variants(input).err().unwrap();
+        assert_eq!(error.to_string(), "the discriminator 1 is used by `Ping`");
+    }
+}
@@ -0,0 +1,186 @@
+use std::collections::BTreeMap;
+
+use network_derive::{NetzDecode, NetzEncode};
+use network_library::{
+    decode::{Decode, DecodeError},
+    encode::Encode,
+};
+
+#[derive(NetzEncode, NetzDecode, Debug, PartialEq)]
+struct User {
+    id: u32,
+    name: String,
+    email: Option<String>,
+}
+
+#[derive(NetzEncode, NetzDecode, Debug, PartialEq)]
+struct Point(i16, i16);
+
+#[derive(NetzEncode, NetzDecode, Debug, PartialEq)]
+struct Unit;
+
+#[derive(NetzEncode, NetzDecode, Debug, PartialEq)]
+struct Ordered {
+    #[netz(order = 2)]
+    first: u8,
+    #[netz(skip)]
+    cache: Vec<u8>,
+    #[netz(order = 1)]
+    second: u8,
+}
+
+#[derive(NetzEncode, NetzDecode, Debug, PartialEq)]
+struct Fixed {
+    #[netz(fixed)]
+    hash: u64,
+    #[netz(fixed)]
+    offset: i16,
+    #[netz(varint)]
+    length: u64,
+}
+
+#[derive(NetzEncode, NetzDecode, Debug, PartialEq)]
+#[netz(discriminator = u16)]
+enum Message {
+    Ping,
+    #[netz(discriminator = 300)]
+    Text(String),
+    Move {
+        #[netz(order = 1)]
+        to: Point,
+        #[netz(order = 0)]
+        from: Point,
+    },
+    Users(BTreeMap<u32, User>),
+    #[netz(discriminator = 1000)]
+    Close,
+}
+
+#[derive(NetzEncode, NetzDecode, Debug, PartialEq, Clone, Copy)]
+enum Status {
+    Online = 1,
+    Away,
+    Offline = 10,
+}
+
+#[derive(NetzEncode, NetzDecode, Debug, PartialEq)]
+struct Wrapper<T> {
+    values: Vec<T>,
+}
+
+#[derive(NetzEncode, NetzDecode, Debug, PartialEq)]
+enum Empty {}
+
+fn round_trip<T: Encode + Decode + PartialEq + std::fmt::Debug>(value: T) -> Vec<u8> {
+    let bytes = value.to_bytes();
+    assert_eq!(T::from_bytes(&bytes).unwrap(), value, "bytes {bytes:x?}");
+    bytes
+}
+
+#[test]
+fn structs() {
+    let user = User {
+        id: 300,
+        name: "ada".into(),
+        email: None,
+    };
+
+    assert_eq!(round_trip(user), vec![0xAC, 0x02, 3, b'a', b'd', b'a', 0]);
+    assert_eq!(round_trip(Point(-1, 1)), vec![1, 2]);
+    assert_eq!(round_trip(Unit), Vec::<u8>::new());
+}
+
+#[test]
+fn field_order() {
+    let value = Ordered {
+        first: 1,
+        cache: vec![],
+        second: 2,
+    };
+
+    assert_eq!(round_trip(value), vec![2, 1]);
+}
+
+#[test]
+fn skipped_fields_are_default() {
+    let value = Ordered {
+        first: 1,
+        cache: vec![1, 2, 3],
+        second: 2,
+    };
+
+    let decoded = Ordered::from_bytes(&value.to_bytes()).unwrap();
+    assert_eq!(decoded.cache, Vec::<u8>::new());
+}
+
+#[test]
+fn fixed_integers() {
+    let value = Fixed {
+        hash: 1,
+        offset: -2,
+        length: 1,
+    };
+
+    assert_eq!(
+        round_trip(value),
+        vec![1, 0, 0, 0, 0, 0, 0, 0, 0xFE, 0xFF, 1]
+    );
+}
+
+#[test]
+fn enums() {
+    assert_eq!(round_trip(Message::Ping), vec![0]);
+    assert_eq!(round_trip(Message::Close), vec![0xE8, 0x07]);
+    assert_eq!(
+        round_trip(Message::Text("hi".into())),
+        vec![0xAC, 0x02, 2, b'h', b'i']
+    );
+
+    let from = Point(0, 0);
+    let to = Point(1, 1);
+    assert_eq!(
+        round_trip(Message::Move { to, from }),
+        vec![0xAD, 0x02, 0, 0, 2, 2]
+    );
+
+    let users = BTreeMap::from([(
+        1,
+        User {
+            id: 1,
+            name: String::new(),
+            email: Some("a@b".into()),
+        },
+    )]);
+    round_trip(Message::Users(users));
+}
+
+/// The discriminants of a unit enum are its' discriminators.
+#[test]
+fn explicit_discriminants() {
+    assert_eq!(round_trip(Status::Online), vec![1]);
+    assert_eq!(round_trip(Status::Away), vec![2]);
+    assert_eq!(round_trip(Status::Offline), vec![Status::Offline as u8]);
+}
+
+#[test]
+fn invalid_discriminator() {
+    assert!(matches!(
+        Message::from_bytes(&[5]),
+        Err(DecodeError::InvalidDiscriminant {
+            name: "Message",
+            value: 5
+        })
+    ));
+    assert!(matches!(
+        Empty::from_bytes(&[0]),
+        Err(DecodeError::InvalidDiscriminant { name: "Empty", .. })
+    ));
+}
+
+#[test]
+fn generics() {
+    round_trip(Wrapper {
+        values: vec![Point(1, 2)],
+    });
+    round_trip(Wrapper::<Unit> { values: vec![] });
+}
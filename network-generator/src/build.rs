@@ -0,0 +1,129 @@
+//! Compiles network files into Rust modules from a build script. The
+//! generated module is written to `OUT_DIR` and included with the
+//! `include!` macro.
+//!
+//! ```no_run
+//! // the main function of build.rs
+//! network_generator::build::compile("net/forum.net").unwrap();
+//! ```
+//!
+//! ```ignore
+//! // src/lib.rs
+//! pub mod forum {
+//!     include!(concat!(env!("OUT_DIR"), "/forum.rs"));
+//! }
+//! ```
+
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+};
+
+use network_parser::parser::{diagnostic::Severity, loader::NetworkLoader, prelude::Prelude};
+
+use crate::rust::RustGenerator;
+
+/// Compiles a network file with its' imports into `OUT_DIR/<name>.rs`.
+/// Shorthand for [Builder::compile] with the default builder.
+pub fn compile(entry: impl AsRef<Path>) -> io::Result<PathBuf> {
+    Builder::new().compile(entry)
+}
+
+/// The configuration of the compilation of network files in a build
+/// script.
+#[derive(Debug, Clone, Default)]
+pub struct Builder {
+    loader: NetworkLoader,
+    out_dir: Option<PathBuf>,
+}
+
+impl Builder {
+    /// A builder, whose' files use the standard library as prelude and
+    /// are written to `OUT_DIR`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a directory, which is searched for imported files.
+    pub fn with_root(mut self, root: impl Into<PathBuf>) -> Self {
+        self.loader = self.loader.with_root(root);
+        self
+    }
+
+    /// Sets the prelude of every loaded file.
+    pub fn with_prelude(mut self, prelude: Prelude) -> Self {
+        self.loader = self.loader.with_prelude(prelude);
+        self
+    }
+
+    /// Writes the generated modules to the directory instead of `OUT_DIR`.
+    pub fn with_out_dir(mut self, out_dir: impl Into<PathBuf>) -> Self {
+        self.out_dir = Some(out_dir.into());
+        self
+    }
+
+    /// Loads the entry file and all files it imports, and writes the
+    /// declarations of all files into a single module, which is named like
+    /// the entry file. Returns the path of the module.
+    ///
+    /// Cargo is told to rerun the build script when one of the files
+    /// changes. Warnings are passed on to cargo, errors are printed and
+    /// fail the compilation.
+    pub fn compile(&self, entry: impl AsRef<Path>) -> io::Result<PathBuf> {
+        let entry = entry.as_ref();
+        let schema = self.loader.load(entry)?;
+
+        for module in schema.modules() {
+            println!("cargo:rerun-if-changed={}", module.path().display());
+        }
+
+        let mut failed = false;
+
+        for diagnostic in schema.diagnostics() {
+            let module = diagnostic.file().and_then(|name| schema.module(name));
+            let (name, source) = module.map_or(("", ""), |module| (module.name(), module.source()));
+
+            match diagnostic.severity() {
+                Severity::Error => {
+                    eprintln!("{}", diagnostic.render(name, source));
+                    failed = true;
+                }
+                Severity::Warning => println!(
+                    "cargo:warning={name}:{}: {}",
+                    diagnostic.span().line(),
+                    diagnostic.message()
+                ),
+            }
+        }
+
+        if failed {
+            return Err(invalid_data(format!(
+                "could not compile `{}` due to previous errors",
+                entry.display()
+            )));
+        }
+
+        let code = RustGenerator::new(&schema.namespace())
+            .generate()
+            .map_err(|error| invalid_data(error.to_string()))?;
+
+        let out_dir = match &self.out_dir {
+            Some(out_dir) => out_dir.clone(),
+            None => env::var_os("OUT_DIR")
+                .map(PathBuf::from)
+                .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "OUT_DIR is not set"))?,
+        };
+
+        let stem = entry.file_stem().unwrap_or(entry.as_os_str());
+        let path = out_dir.join(stem).with_extension("rs");
+
+        fs::create_dir_all(&out_dir)?;
+        fs::write(&path, code)?;
+
+        Ok(path)
+    }
+}
+
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
@@ -0,0 +1,73 @@
+/// A buffer of generated source code, which indents the lines by the depth
+/// of the open blocks.
+///
+/// ```
+/// use network_generator::code::CodeWriter;
+///
+/// let mut code = CodeWriter::new("    ");
+/// code.open("fn main() {");
+/// code.line("println!(\"hi\");");
+/// code.close("}");
+///
+/// assert_eq!(code.finish(), "fn main() {\n    println!(\"hi\");\n}\n");
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct CodeWriter {
+    code: String,
+    indent: &'static str,
+    depth: usize,
+}
+
+impl CodeWriter {
+    /// Create an empty buffer, which indents every block by `indent`.
+    pub fn new(indent: &'static str) -> Self {
+        Self {
+            code: String::new(),
+            indent,
+            depth: 0,
+        }
+    }
+
+    /// Writes a line at the current depth. Every line of a text with
+    /// several lines is indented, empty lines are written without
+    /// indentation.
+    pub fn line(&mut self, text: impl AsRef<str>) {
+        for line in text.as_ref().split('\n') {
+            if !line.is_empty() {
+                self.code += &self.indent.repeat(self.depth);
+                self.code += line;
+            }
+
+            self.code.push('\n');
+        }
+    }
+
+    /// Writes an empty line.
+    pub fn blank(&mut self) {
+        self.code.push('\n');
+    }
+
+    /// Writes a line, which opens a block, so the following lines are
+    /// indented one level deeper.
+    pub fn open(&mut self, text: impl AsRef<str>) {
+        self.line(text);
+        self.depth += 1;
+    }
+
+    /// Writes a line, which closes the innermost block.
+    pub fn close(&mut self, text: impl AsRef<str>) {
+        self.outdent();
+        self.line(text);
+    }
+
+    /// Indents the following lines one level less, without closing the
+    /// block with a line.
+    pub fn outdent(&mut self) {
+        self.depth = self.depth.saturating_sub(1);
+    }
+
+    /// The generated source code.
+    pub fn finish(self) -> String {
+        self.code
+    }
+}
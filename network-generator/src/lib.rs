@@ -0,0 +1,90 @@
+//! Generators, which translate network files into the source code of other
+//! languages. The generated code encodes and decodes the declared types in
+//! the wire format of the `network-library`.
+//!
+//! - [rust::RustGenerator] writes a Rust module, [build] compiles network
+//!   files into Rust modules from a build script.
+//! - [typescript::TypeScriptGenerator] writes a TypeScript module for every
+//!   declaration.
+//!
+//! Protocols are not generated yet.
+
+use std::fmt::Display;
+
+use network_parser::parser::{evaluate::ConstError, flags::FlagsError, structure::StructError};
+
+pub mod build;
+pub mod code;
+pub mod rust;
+mod types;
+pub mod typescript;
+
+/// The reasons a network file can not be generated. The generators expect
+/// a file without errors, these are the problems the resolver does not
+/// report.
+#[derive(Debug, PartialEq, Clone)]
+pub enum GenerateError {
+    /// A constant could not be evaluated.
+    Constant(ConstError),
+    /// A flag occupies a position, which is invalid or already occupied.
+    Flags(FlagsError),
+    /// A field number is missing, unexpected, invalid or already taken.
+    Struct(StructError),
+    /// A constant of a type, which has no constant values in the target
+    /// language, such as a struct.
+    UnsupportedConstant { constant: String, type_name: String },
+    /// The discriminator of an enum member does not fit into the type of
+    /// the discriminator.
+    DiscriminatorOverflow {
+        enumeration: String,
+        member: String,
+        discriminator: u128,
+        type_name: String,
+    },
+}
+
+impl Display for GenerateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Constant(error) => write!(f, "{error}"),
+            Self::Flags(error) => write!(f, "{error}"),
+            Self::Struct(error) => write!(f, "{error}"),
+            Self::UnsupportedConstant {
+                constant,
+                type_name,
+            } => write!(
+                f,
+                "the constant `{constant}` has the type `{type_name}`, which has no constant values"
+            ),
+            Self::DiscriminatorOverflow {
+                enumeration,
+                member,
+                discriminator,
+                type_name,
+            } => write!(
+                f,
+                "the discriminator {discriminator} of `{enumeration}.{member}` does not fit into `{type_name}`"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for GenerateError {}
+
+impl From<ConstError> for GenerateError {
+    fn from(error: ConstError) -> Self {
+        Self::Constant(error)
+    }
+}
+
+impl From<FlagsError> for GenerateError {
+    fn from(error: FlagsError) -> Self {
+        Self::Flags(error)
+    }
+}
+
+impl From<StructError> for GenerateError {
+    fn from(error: StructError) -> Self {
+        Self::Struct(error)
+    }
+}
@@ -0,0 +1,131 @@
+//! The statements, which encode and decode a value of a type expression.
+
+use network_parser::parser::expression::{TypeExpression, TypeKind};
+
+use super::{types::type_name, RustGenerator};
+use crate::{code::CodeWriter, types::Named};
+
+pub const ENCODE: &str = "::network_library::encode::Encode";
+pub const WRITER: &str = "::network_library::encode::Writer";
+pub const FIXED: &str = "::network_library::encode::Fixed";
+pub const DECODE: &str = "::network_library::decode::Decode";
+pub const READER: &str = "::network_library::decode::Reader";
+pub const DECODE_ERROR: &str = "::network_library::decode::DecodeError";
+
+impl RustGenerator<'_> {
+    /// Writes the statements, which encode the value bound to `value` as a
+    /// reference. Only fixed integers and the containers around them are
+    /// written out, every other value is encoded with its' [ENCODE] trait.
+    pub(super) fn encode_value(
+        &self,
+        code: &mut CodeWriter,
+        expression: &TypeExpression,
+        value: &str,
+        fixed: bool,
+    ) {
+        if !fixed || !self.types.has_varint(expression) {
+            code.line(format!("{ENCODE}::encode({value}, writer)?;"));
+            return;
+        }
+
+        match expression.kind() {
+            TypeKind::Named(name) => match self.types.lookup(name) {
+                Named::Alias(alias) => {
+                    code.line(format!("let {value}_inner = &{value}.0;"));
+                    self.encode_value(code, alias.aliased(), &format!("{value}_inner"), true);
+                }
+                Named::Inline(alias) => self.encode_value(code, alias.aliased(), value, true),
+                _ => code.line(format!("{ENCODE}::encode(&{FIXED}(*{value}), writer)?;")),
+            },
+            TypeKind::Array(element) => {
+                code.line(format!(
+                    "{WRITER}::write_7bit(writer, {value}.len() as u64)?;"
+                ));
+                code.open(format!("for {value}_element in {value} {{"));
+                self.encode_value(code, element, &format!("{value}_element"), true);
+                code.close("}");
+            }
+            TypeKind::Option(inner) => {
+                code.open(format!("match {value} {{"));
+                code.line(format!(
+                    "{} => {WRITER}::write_byte(writer, 0)?,",
+                    self.types.std("None")
+                ));
+                code.open(format!("{}({value}_inner) => {{", self.types.std("Some")));
+                code.line(format!("{WRITER}::write_byte(writer, 1)?;"));
+                self.encode_value(code, inner, &format!("{value}_inner"), true);
+                code.close("}");
+                code.close("}");
+            }
+            _ => code.line(format!("{ENCODE}::encode(&{FIXED}(*{value}), writer)?;")),
+        }
+    }
+
+    /// Writes the statements, which decode a value and bind it to `target`.
+    /// The counterpart of [Self::encode_value].
+    pub(super) fn decode_value(
+        &self,
+        code: &mut CodeWriter,
+        expression: &TypeExpression,
+        target: &str,
+        fixed: bool,
+    ) {
+        if !fixed || !self.types.has_varint(expression) {
+            code.line(format!("let {target} = {DECODE}::decode(reader)?;"));
+            return;
+        }
+
+        match expression.kind() {
+            TypeKind::Named(name) => match self.types.lookup(name) {
+                Named::Alias(alias) => {
+                    self.decode_value(code, alias.aliased(), &format!("{target}_inner"), true);
+                    code.line(format!(
+                        "let {target} = {}({target}_inner);",
+                        type_name(name)
+                    ));
+                }
+                Named::Inline(alias) => self.decode_value(code, alias.aliased(), target, true),
+                _ => self.decode_fixed(code, expression, target),
+            },
+            TypeKind::Array(element) => {
+                code.line(format!(
+                    "let {target}_length = {READER}::read_length(reader)?;"
+                ));
+                code.line(format!(
+                    "let mut {target} = {}::new();",
+                    self.types.std("Vec")
+                ));
+                code.open(format!("for _ in 0..{target}_length {{"));
+                self.decode_value(code, element, &format!("{target}_element"), true);
+                code.line(format!("{target}.push({target}_element);"));
+                code.close("}");
+            }
+            TypeKind::Option(inner) => {
+                code.open(format!(
+                    "let {target} = match {READER}::read_byte(reader)? {{"
+                ));
+                code.line(format!("0 => {},", self.types.std("None")));
+                code.open("1 => {");
+                self.decode_value(code, inner, &format!("{target}_inner"), true);
+                code.line(format!("{}({target}_inner)", self.types.std("Some")));
+                code.close("}");
+                code.open("value => {");
+                code.line(format!(
+                    "return {}({DECODE_ERROR}::InvalidDiscriminant {{ name: \"Option\", value: value as u128 }});",
+                    self.types.std("Err")
+                ));
+                code.close("}");
+                code.close("};");
+            }
+            _ => self.decode_fixed(code, expression, target),
+        }
+    }
+
+    /// Decodes an integer in fixed width.
+    fn decode_fixed(&self, code: &mut CodeWriter, expression: &TypeExpression, target: &str) {
+        let integer = self.types.rust_type(expression, None);
+        code.line(format!(
+            "let {target} = <{FIXED}<{integer}> as {DECODE}>::decode(reader)?.0;"
+        ));
+    }
+}
@@ -0,0 +1,673 @@
+//! The Rust backend, which generates a module with a type for every
+//! declaration of a network file and implements the `Encode` and `Decode`
+//! traits of the `network-library` for them.
+//!
+//! - Structs become structs with public fields. The fields of an `option
+//!   struct` are `Option`s, which are encoded as a message.
+//! - Enums become enums, the payload of a member becomes a tuple variant.
+//! - Flags become a newtype over the smallest unsigned integer, with a
+//!   constant for every member.
+//! - Aliases become newtypes. Aliases named like primitives, such as
+//!   `string` and `bool` of the standard library, are replaced by the
+//!   Rust type they stand for.
+//! - Constants become constants, those of a string type are `&str`.
+//!
+//! The generated code refers to the crate `network_library`, which has to
+//! be a dependency.
+
+mod codec;
+mod types;
+
+use network_parser::parser::{
+    alias::NetworkType,
+    constant::NetworkConst,
+    documentation::Documentation,
+    enumeration::NetworkEnum,
+    evaluate::{ConstEvaluator, ConstValue},
+    expression::{TypeExpression, TypeKind},
+    flags::NetworkFlags,
+    network::NetworkFileReader,
+    structure::NetworkStruct,
+};
+
+use self::{
+    codec::{DECODE, DECODE_ERROR, ENCODE, FIXED, READER, WRITER},
+    types::{constant_name, field_name, type_name},
+};
+use crate::{
+    code::CodeWriter,
+    types::{
+        integer_bits, integer_type, is_fixed, is_string, is_unit_enum, validate_flags,
+        validate_struct, Named, Types,
+    },
+    GenerateError,
+};
+
+/// The comment at the beginning of every generated file.
+const HEADER: &str = "// This file is generated from a network file, do not edit it by hand.";
+
+/// Generates the Rust module of a network file. The file is expected to
+/// have no errors, so it should be [resolved](NetworkFileReader::resolve)
+/// first.
+///
+/// ```
+/// use network_generator::rust::RustGenerator;
+/// use network_parser::parser::network::NetworkFileReader;
+///
+/// let file = NetworkFileReader::from_source("struct User {\n    name: string;\n}").unwrap();
+/// let code = RustGenerator::new(&file).generate().unwrap();
+///
+/// assert!(code.contains("pub struct User {\n    pub name: String,\n}"));
+/// ```
+pub struct RustGenerator<'a> {
+    file: &'a NetworkFileReader,
+    types: Types<'a>,
+}
+
+impl<'a> RustGenerator<'a> {
+    pub fn new(file: &'a NetworkFileReader) -> Self {
+        Self {
+            file,
+            types: Types::new(file),
+        }
+    }
+
+    /// The source code of the module. Constants come first, followed by
+    /// aliases, flags, enums and structs in the order of their' declaration.
+    pub fn generate(&self) -> Result<String, GenerateError> {
+        let mut code = CodeWriter::new("    ");
+        code.line(HEADER);
+
+        let values = ConstEvaluator::new(self.file).evaluate()?;
+
+        for constant in self.file.consts() {
+            code.blank();
+            self.constant(&mut code, constant, &values[constant.name()])?;
+        }
+
+        for alias in self.file.aliases() {
+            if let Named::Alias(_) = self.types.lookup(alias.name()) {
+                code.blank();
+                self.alias(&mut code, alias);
+            }
+        }
+
+        for flags in self.file.all_flags() {
+            validate_flags(flags)?;
+            code.blank();
+            self.flags(&mut code, flags);
+        }
+
+        for enumeration in self.file.enumerations() {
+            code.blank();
+            self.enumeration(&mut code, enumeration)?;
+        }
+
+        for structure in self.file.structures() {
+            validate_struct(structure)?;
+            code.blank();
+            self.structure(&mut code, structure);
+        }
+
+        Ok(code.finish())
+    }
+
+    /// Writes the description of the documentation as doc comment.
+    fn documentation(&self, code: &mut CodeWriter, documentation: Option<&Documentation>) {
+        let Some(documentation) = documentation else {
+            return;
+        };
+
+        for line in documentation.description().lines() {
+            match line.trim_end() {
+                "" => code.line("///"),
+                line => code.line(format!("/// {line}")),
+            }
+        }
+    }
+
+    /// Writes the derive attribute with the traits, which apply.
+    fn derive(&self, code: &mut CodeWriter, copy: bool, hashable: bool, extra: &[&str]) {
+        let mut traits = vec!["Debug", "Clone"];
+
+        if copy {
+            traits.push("Copy");
+        }
+
+        traits.push("PartialEq");
+
+        if hashable {
+            traits.extend(["Eq", "Hash"]);
+        }
+
+        traits.extend(extra);
+        code.line(format!("#[derive({})]", traits.join(", ")));
+    }
+
+    /// Opens the implementation of the encode trait.
+    fn open_encode(&self, code: &mut CodeWriter, name: &str) {
+        code.open(format!("impl {ENCODE} for {name} {{"));
+        code.open(format!(
+            "fn encode<W: {WRITER} + ?Sized>(&self, writer: &mut W) -> ::std::io::Result<()> {{"
+        ));
+    }
+
+    /// Opens the implementation of the decode trait.
+    fn open_decode(&self, code: &mut CodeWriter, name: &str) {
+        code.open(format!("impl {DECODE} for {name} {{"));
+        code.open(format!(
+            "fn decode<R: {READER} + ?Sized>(reader: &mut R) -> {}<Self, {DECODE_ERROR}> {{",
+            self.types.std("Result")
+        ));
+    }
+
+    fn close_implementation(&self, code: &mut CodeWriter) {
+        code.close("}");
+        code.close("}");
+    }
+
+    fn constant(
+        &self,
+        code: &mut CodeWriter,
+        constant: &NetworkConst,
+        value: &ConstValue,
+    ) -> Result<(), GenerateError> {
+        let (const_type, literal) = self
+            .constant_value(constant.const_type(), value)
+            .ok_or_else(|| GenerateError::UnsupportedConstant {
+                constant: constant.name().to_owned(),
+                type_name: constant.const_type().to_string(),
+            })?;
+
+        self.documentation(code, constant.documentation());
+        code.line(format!(
+            "pub const {}: {const_type} = {literal};",
+            constant_name(constant.name())
+        ));
+
+        Ok(())
+    }
+
+    /// The Rust type and the literal of a constant value, `None` if the type
+    /// has no constant values.
+    fn constant_value(
+        &self,
+        expression: &TypeExpression,
+        value: &ConstValue,
+    ) -> Option<(String, String)> {
+        let primitive = match expression.kind() {
+            TypeKind::Integer { signed, bits } => integer_type(*signed, *bits),
+            TypeKind::Named(name) => match self.types.lookup(name) {
+                Named::Alias(alias) | Named::Inline(alias) if is_string(alias) => {
+                    let ConstValue::String(string) = value else {
+                        return None;
+                    };
+
+                    return Some(("&str".to_owned(), format!("{string:?}")));
+                }
+                Named::Inline(alias) if name == "bool" && alias.aliased().name() == Some("u8") => {
+                    let ConstValue::Integer(integer) = value else {
+                        return None;
+                    };
+
+                    return Some(("bool".to_owned(), (*integer != 0).to_string()));
+                }
+                Named::Inline(alias) => return self.constant_value(alias.aliased(), value),
+                Named::Alias(alias) => {
+                    let (inner, literal) = self.constant_value(alias.aliased(), value)?;
+                    let rust_name = type_name(name);
+
+                    return match inner.as_str() {
+                        "&str" => Some((inner, literal)),
+                        _ => Some((rust_name.clone(), format!("{rust_name}({literal})"))),
+                    };
+                }
+                Named::Primitive => name.clone(),
+                _ => return None,
+            },
+            _ => return None,
+        };
+
+        let literal = match (primitive.as_str(), value) {
+            ("f32", ConstValue::Float(float)) => format!("{:?}", *float as f32),
+            ("f64", ConstValue::Float(float)) => format!("{float:?}"),
+            ("f32" | "f64", ConstValue::Integer(integer)) => format!("{integer}.0"),
+            (_, ConstValue::Integer(integer)) if integer_bits(&primitive).is_some() => {
+                integer.to_string()
+            }
+            _ => return None,
+        };
+
+        Some((primitive, literal))
+    }
+
+    /// Writes an alias as newtype.
+    fn alias(&self, code: &mut CodeWriter, alias: &NetworkType) {
+        let name = type_name(alias.name());
+        let aliased = alias.aliased();
+        let inner = match is_string(alias) {
+            true => self.types.std("String").to_owned(),
+            false => self.types.rust_type(aliased, Some(alias.name())),
+        };
+
+        let copy = self.types.is_copy(aliased) && !is_string(alias);
+        let hashable = is_string(alias) || self.types.is_hashable(aliased, &mut vec![]);
+
+        self.documentation(code, alias.documentation());
+        self.derive(code, copy, hashable, &[]);
+        code.line(format!("pub struct {name}(pub {inner});"));
+
+        let fixed = is_fixed(alias.tags());
+
+        code.blank();
+        self.open_encode(code, &name);
+        code.line("let Self(field0) = self;");
+        self.encode_value(code, aliased, "field0", fixed);
+        code.line(format!("{}(())", self.types.std("Ok")));
+        self.close_implementation(code);
+
+        code.blank();
+        self.open_decode(code, &name);
+        self.decode_value(code, aliased, "field0", fixed);
+        code.line(format!("{}(Self(field0))", self.types.std("Ok")));
+        self.close_implementation(code);
+    }
+
+    /// Writes flags as newtype over the smallest unsigned integer, which
+    /// holds all of the flags. The flags are encoded in fixed width.
+    fn flags(&self, code: &mut CodeWriter, flags: &NetworkFlags) {
+        let name = type_name(flags.name());
+        let integer = flags.width().primitive();
+        let bytes = flags.width().bits() / 8;
+
+        self.documentation(code, flags.documentation());
+        self.derive(code, true, true, &["Default"]);
+        code.line(format!("pub struct {name}({integer});"));
+        code.blank();
+
+        code.open(format!("impl {name} {{"));
+
+        for member in flags.members() {
+            self.documentation(code, member.documentation());
+            code.line(format!(
+                "pub const {}: Self = Self(1 << {});",
+                constant_name(member.name()),
+                member.bit()
+            ));
+        }
+
+        code.blank();
+        code.line("/// The set without flags.");
+        code.open("pub const fn empty() -> Self {");
+        code.line("Self(0)");
+        code.close("}");
+        code.blank();
+        code.line("/// The set of the raw bits, which keeps undeclared bits.");
+        code.open(format!(
+            "pub const fn from_bits(bits: {integer}) -> Self {{"
+        ));
+        code.line("Self(bits)");
+        code.close("}");
+        code.blank();
+        code.line("/// The raw bits of the set.");
+        code.open(format!("pub const fn bits(&self) -> {integer} {{"));
+        code.line("self.0");
+        code.close("}");
+        code.blank();
+        code.line("/// Whether all flags of `other` are set.");
+        code.open("pub const fn contains(&self, other: Self) -> bool {");
+        code.line("self.0 & other.0 == other.0");
+        code.close("}");
+        code.blank();
+        code.line("/// Sets the flags of `other`.");
+        code.open("pub fn insert(&mut self, other: Self) {");
+        code.line("self.0 |= other.0;");
+        code.close("}");
+        code.blank();
+        code.line("/// Clears the flags of `other`.");
+        code.open("pub fn remove(&mut self, other: Self) {");
+        code.line("self.0 &= !other.0;");
+        code.close("}");
+        code.close("}");
+
+        code.blank();
+        code.open(format!("impl ::core::ops::BitOr for {name} {{"));
+        code.line("type Output = Self;");
+        code.blank();
+        code.open("fn bitor(self, other: Self) -> Self {");
+        code.line("Self(self.0 | other.0)");
+        code.close("}");
+        code.close("}");
+
+        code.blank();
+        self.open_encode(code, &name);
+        code.line(format!(
+            "{WRITER}::write_bytes(writer, &self.0.to_le_bytes())"
+        ));
+        self.close_implementation(code);
+
+        code.blank();
+        self.open_decode(code, &name);
+        code.line(format!("let mut bytes = [0; {bytes}];"));
+        code.line(format!("{READER}::read_bytes(reader, &mut bytes)?;"));
+        code.line(format!(
+            "{}(Self({integer}::from_le_bytes(bytes)))",
+            self.types.std("Ok")
+        ));
+        self.close_implementation(code);
+    }
+
+    fn enumeration(
+        &self,
+        code: &mut CodeWriter,
+        enumeration: &NetworkEnum,
+    ) -> Result<(), GenerateError> {
+        let name = type_name(enumeration.name());
+        let members = self.types.discriminators(enumeration)?;
+        let discriminator = self.types.discriminator(enumeration);
+        let fixed = discriminator.fixed;
+        let discriminator = integer_type(discriminator.signed, discriminator.bits);
+        let unit = is_unit_enum(enumeration);
+        let hashable = unit
+            || self
+                .types
+                .is_hashable(&TypeExpression::named(enumeration.name()), &mut vec![]);
+
+        self.documentation(code, enumeration.documentation());
+
+        match unit {
+            true => self.derive(code, true, true, &["PartialOrd", "Ord"]),
+            false => self.derive(code, false, hashable, &[]),
+        }
+
+        code.open(format!("pub enum {name} {{"));
+
+        for (member, _) in members.iter() {
+            self.documentation(code, member.documentation());
+
+            let payload = member
+                .payload()
+                .map(|payload| self.types.rust_type(payload, Some(enumeration.name())))
+                .collect::<Vec<_>>();
+
+            match payload.is_empty() {
+                true => code.line(format!("{},", type_name(member.name()))),
+                false => code.line(format!(
+                    "{}({}),",
+                    type_name(member.name()),
+                    payload.join(", ")
+                )),
+            }
+        }
+
+        code.close("}");
+
+        let encode_discriminator = |value: u64| match fixed {
+            true => format!("{ENCODE}::encode(&{FIXED}({value}{discriminator}), writer)"),
+            false => format!("{ENCODE}::encode(&{value}{discriminator}, writer)"),
+        };
+
+        code.blank();
+        self.open_encode(code, &name);
+
+        if members.is_empty() {
+            code.line("let _ = writer;");
+            code.line("match *self {}");
+        } else {
+            code.open("match self {");
+        }
+
+        for (member, value) in members.iter() {
+            let variant = type_name(member.name());
+            let bindings = (0..member.payload().count())
+                .map(|index| format!("field{index}"))
+                .collect::<Vec<_>>();
+
+            if bindings.is_empty() {
+                code.line(format!(
+                    "Self::{variant} => {},",
+                    encode_discriminator(*value)
+                ));
+                continue;
+            }
+
+            code.open(format!("Self::{variant}({}) => {{", bindings.join(", ")));
+            code.line(format!("{}?;", encode_discriminator(*value)));
+
+            for (payload, binding) in member.payload().zip(bindings.iter()) {
+                self.encode_value(code, payload, binding, false);
+            }
+
+            code.line(format!("{}(())", self.types.std("Ok")));
+            code.close("}");
+        }
+
+        if !members.is_empty() {
+            code.close("}");
+        }
+
+        self.close_implementation(code);
+
+        code.blank();
+        self.open_decode(code, &name);
+
+        match fixed {
+            true => code.open(format!(
+                "match <{FIXED}<{discriminator}> as {DECODE}>::decode(reader)?.0 {{"
+            )),
+            false => code.open(format!(
+                "match <{discriminator} as {DECODE}>::decode(reader)? {{"
+            )),
+        }
+
+        for (member, value) in members.iter() {
+            let variant = type_name(member.name());
+            let bindings = (0..member.payload().count())
+                .map(|index| format!("field{index}"))
+                .collect::<Vec<_>>();
+
+            if bindings.is_empty() {
+                code.line(format!(
+                    "{value} => {}(Self::{variant}),",
+                    self.types.std("Ok")
+                ));
+                continue;
+            }
+
+            code.open(format!("{value} => {{"));
+
+            for (payload, binding) in member.payload().zip(bindings.iter()) {
+                self.decode_value(code, payload, binding, false);
+            }
+
+            code.line(format!(
+                "{}(Self::{variant}({}))",
+                self.types.std("Ok"),
+                bindings.join(", ")
+            ));
+            code.close("}");
+        }
+
+        code.line(format!(
+            "other => {}({DECODE_ERROR}::InvalidDiscriminant {{ name: {:?}, value: other as u128 }}),",
+            self.types.std("Err"),
+            enumeration.name()
+        ));
+        code.close("}");
+        self.close_implementation(code);
+
+        Ok(())
+    }
+
+    fn structure(&self, code: &mut CodeWriter, structure: &NetworkStruct) {
+        let name = type_name(structure.name());
+        let message = structure.is_message();
+        let hashable = self
+            .types
+            .is_hashable(&TypeExpression::named(structure.name()), &mut vec![]);
+
+        self.documentation(code, structure.documentation());
+
+        match message {
+            true => self.derive(code, false, hashable, &["Default"]),
+            false => self.derive(code, false, hashable, &[]),
+        }
+
+        if structure.fields().next().is_none() {
+            code.line(format!("pub struct {name} {{}}"));
+        } else {
+            code.open(format!("pub struct {name} {{"));
+        }
+
+        for field in structure.fields() {
+            let rust_type = self
+                .types
+                .rust_type(field.field_type(), Some(structure.name()));
+
+            self.documentation(code, field.documentation());
+
+            match message {
+                true => code.line(format!(
+                    "pub {}: {}<{rust_type}>,",
+                    field_name(field.name()),
+                    self.types.std("Option")
+                )),
+                false => code.line(format!("pub {}: {rust_type},", field_name(field.name()))),
+            }
+        }
+
+        if structure.fields().next().is_some() {
+            code.close("}");
+        }
+
+        code.blank();
+        self.open_encode(code, &name);
+
+        match message {
+            true => self.encode_message(code, structure),
+            false => self.encode_fields(code, structure),
+        }
+
+        self.close_implementation(code);
+
+        code.blank();
+        self.open_decode(code, &name);
+
+        match message {
+            true => self.decode_message(code, structure),
+            false => self.decode_fields(code, structure),
+        }
+
+        self.close_implementation(code);
+    }
+
+    /// Encodes the fields of a struct in the order of their' declaration.
+    fn encode_fields(&self, code: &mut CodeWriter, structure: &NetworkStruct) {
+        let bindings = structure
+            .fields()
+            .enumerate()
+            .map(|(index, field)| format!("{}: field{index}", field_name(field.name())))
+            .collect::<Vec<_>>();
+
+        match bindings.is_empty() {
+            true => code.line("let _ = writer;"),
+            false => code.line(format!("let Self {{ {} }} = self;", bindings.join(", "))),
+        }
+
+        for (index, field) in structure.fields().enumerate() {
+            let fixed = is_fixed(field.tags());
+            self.encode_value(code, field.field_type(), &format!("field{index}"), fixed);
+        }
+
+        code.line(format!("{}(())", self.types.std("Ok")));
+    }
+
+    /// Decodes the fields of a struct in the order of their' declaration.
+    fn decode_fields(&self, code: &mut CodeWriter, structure: &NetworkStruct) {
+        if structure.fields().next().is_none() {
+            code.line("let _ = reader;");
+        }
+
+        for (index, field) in structure.fields().enumerate() {
+            let fixed = is_fixed(field.tags());
+            self.decode_value(code, field.field_type(), &format!("field{index}"), fixed);
+        }
+
+        let fields = structure
+            .fields()
+            .enumerate()
+            .map(|(index, field)| format!("{}: field{index}", field_name(field.name())))
+            .collect::<Vec<_>>();
+
+        match fields.is_empty() {
+            true => code.line(format!("{}(Self {{}})", self.types.std("Ok"))),
+            false => code.line(format!(
+                "{}(Self {{ {} }})",
+                self.types.std("Ok"),
+                fields.join(", ")
+            )),
+        }
+    }
+
+    /// Encodes the present fields of an `option struct` as a message.
+    fn encode_message(&self, code: &mut CodeWriter, structure: &NetworkStruct) {
+        code.line("let mut message = ::network_library::message::MessageWriter::new();");
+
+        for (index, field) in structure.fields().enumerate() {
+            let binding = format!("field{index}");
+
+            code.blank();
+            code.open(format!(
+                "if let {}({binding}) = &self.{} {{",
+                self.types.std("Some"),
+                field_name(field.name())
+            ));
+            code.line(format!(
+                "let mut bytes = {}::<u8>::new();",
+                self.types.std("Vec")
+            ));
+            code.line("let writer = &mut bytes;");
+            self.encode_value(code, field.field_type(), &binding, is_fixed(field.tags()));
+            code.line(format!(
+                "message.write_field({}, &bytes);",
+                field.number().unwrap_or_default()
+            ));
+            code.close("}");
+        }
+
+        code.blank();
+        code.line(format!("{WRITER}::write_bytes(writer, &message.finish())"));
+    }
+
+    /// Decodes the fields of a message and skips unknown fields, which a
+    /// newer schema added.
+    fn decode_message(&self, code: &mut CodeWriter, structure: &NetworkStruct) {
+        code.line("let mut message = Self::default();");
+        code.blank();
+        code.open(format!(
+            "while let {}((number, bytes)) = ::network_library::message::read_field(reader)? {{",
+            self.types.std("Some")
+        ));
+        code.open("match number {");
+
+        for (index, field) in structure.fields().enumerate() {
+            let binding = format!("field{index}");
+
+            code.open(format!("{} => {{", field.number().unwrap_or_default()));
+            code.line("let reader = &mut &bytes[..];");
+            self.decode_value(code, field.field_type(), &binding, is_fixed(field.tags()));
+            code.line(format!(
+                "message.{} = {}({binding});",
+                field_name(field.name()),
+                self.types.std("Some")
+            ));
+            code.close("}");
+        }
+
+        code.line("_ => (),");
+        code.close("}");
+        code.close("}");
+        code.blank();
+        code.line(format!("{}(message)", self.types.std("Ok")));
+    }
+}
@@ -0,0 +1,270 @@
+//! The Rust types of type expressions, the traits the generated types can
+//! derive and the names of the generated items.
+
+use network_parser::parser::expression::{TypeExpression, TypeKind};
+use util_cases::CaseStyles;
+
+use crate::types::{integer_type, is_string, is_unit_enum, Named, Types};
+
+/// The keywords of Rust, which are written as raw identifiers.
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "dyn", "else", "enum", "extern", "false",
+    "fn", "for", "gen", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub",
+    "ref", "return", "static", "struct", "trait", "true", "type", "unsafe", "use", "where",
+    "while", "abstract", "become", "box", "do", "final", "macro", "override", "priv", "try",
+    "typeof", "unsized", "virtual", "yield",
+];
+
+/// The keywords of Rust, which can not be raw identifiers.
+const RESERVED: &[&str] = &["crate", "self", "Self", "super", "_"];
+
+/// The items of the standard library the generated code uses by name, with
+/// their' full path. Declarations with the same name shadow them, so the
+/// full path is used instead.
+const STD_ITEMS: &[(&str, &str)] = &[
+    ("Box", "::std::boxed::Box"),
+    ("Err", "::core::result::Result::Err"),
+    ("None", "::core::option::Option::None"),
+    ("Ok", "::core::result::Result::Ok"),
+    ("Option", "::core::option::Option"),
+    ("Result", "::core::result::Result"),
+    ("Some", "::core::option::Option::Some"),
+    ("String", "::std::string::String"),
+    ("Vec", "::std::vec::Vec"),
+];
+
+/// Converts a name into a Rust identifier, which is no keyword.
+pub fn identifier(name: String) -> String {
+    if RESERVED.contains(&name.as_str()) {
+        format!("{name}_")
+    } else if KEYWORDS.contains(&name.as_str()) {
+        format!("r#{name}")
+    } else {
+        name
+    }
+}
+
+/// The name of a generated type or enum variant in `PascalCase`.
+pub fn type_name(name: &str) -> String {
+    identifier(name.to_pascal_case())
+}
+
+/// The name of a field in `snake_case`.
+pub fn field_name(name: &str) -> String {
+    identifier(name.to_snake_case())
+}
+
+/// The name of a constant in `CONSTANT_CASE`.
+pub fn constant_name(name: &str) -> String {
+    identifier(name.to_constant_case())
+}
+
+impl Types<'_> {
+    /// Whether a declaration of the file is named like the item of the
+    /// standard library.
+    fn shadows(&self, item: &str) -> bool {
+        let file = self.file;
+
+        file.structures()
+            .any(|other| type_name(other.name()) == item)
+            || file
+                .enumerations()
+                .any(|other| type_name(other.name()) == item)
+            || file
+                .all_flags()
+                .any(|other| type_name(other.name()) == item)
+            || file.aliases().any(|other| type_name(other.name()) == item)
+    }
+
+    /// The name of an item of the standard library, or its' full path if a
+    /// generated type shadows it.
+    pub fn std(&self, item: &'static str) -> &'static str {
+        let path = STD_ITEMS
+            .iter()
+            .find(|(name, _)| *name == item)
+            .map_or(item, |(_, path)| path);
+
+        match self.shadows(item) {
+            true => path,
+            false => item,
+        }
+    }
+
+    /// The Rust type of the expression. Named types, which contain the
+    /// `owner` without indirection, are boxed, so recursive types have a
+    /// finite size.
+    pub fn rust_type(&self, expression: &TypeExpression, owner: Option<&str>) -> String {
+        match expression.kind() {
+            TypeKind::Named(name) => {
+                let rust_name = match self.lookup(name) {
+                    Named::Inline(alias) if is_string(alias) => {
+                        return self.std("String").to_owned()
+                    }
+                    Named::Inline(alias) if name == "bool" => {
+                        if alias.aliased().name() == Some("u8") {
+                            return "bool".to_owned();
+                        }
+
+                        return self.rust_type(alias.aliased(), owner);
+                    }
+                    Named::Inline(alias) => return self.rust_type(alias.aliased(), owner),
+                    Named::Primitive => return name.clone(),
+                    _ => type_name(name),
+                };
+
+                match owner.is_some_and(|owner| self.contains(expression, owner, &mut vec![])) {
+                    true => format!("{}<{rust_name}>", self.std("Box")),
+                    false => rust_name,
+                }
+            }
+            TypeKind::Array(element) => {
+                format!("{}<{}>", self.std("Vec"), self.rust_type(element, None))
+            }
+            TypeKind::Option(inner) => {
+                format!("{}<{}>", self.std("Option"), self.rust_type(inner, owner))
+            }
+            TypeKind::Map(key, value) => format!(
+                "::std::collections::HashMap<{}, {}>",
+                self.rust_type(key, None),
+                self.rust_type(value, None)
+            ),
+            TypeKind::Integer { signed, bits } => integer_type(*signed, *bits),
+            TypeKind::Unit => "()".to_owned(),
+        }
+    }
+
+    /// Whether a value of the expression contains a value of the declaration
+    /// `owner` without indirection, such as an array or a map.
+    fn contains(
+        &self,
+        expression: &TypeExpression,
+        owner: &str,
+        visited: &mut Vec<String>,
+    ) -> bool {
+        let name = match expression.kind() {
+            TypeKind::Named(name) => name,
+            TypeKind::Option(inner) => return self.contains(inner, owner, visited),
+            _ => return false,
+        };
+
+        if name == owner {
+            return true;
+        }
+
+        if visited.contains(name) {
+            return false;
+        }
+
+        visited.push(name.clone());
+
+        match self.lookup(name) {
+            Named::Struct(structure) => structure
+                .fields()
+                .any(|field| self.contains(field.field_type(), owner, visited)),
+            Named::Enum(enumeration) => enumeration
+                .members()
+                .flat_map(|member| member.payload())
+                .any(|payload| self.contains(payload, owner, visited)),
+            Named::Alias(alias) | Named::Inline(alias) => {
+                self.contains(alias.aliased(), owner, visited)
+            }
+            _ => false,
+        }
+    }
+
+    /// Whether the Rust type of the expression is `Copy`.
+    pub fn is_copy(&self, expression: &TypeExpression) -> bool {
+        match expression.kind() {
+            TypeKind::Integer { .. } | TypeKind::Unit => true,
+            TypeKind::Option(inner) => self.is_copy(inner),
+            TypeKind::Named(name) => match self.lookup(name) {
+                Named::Primitive | Named::Flags => true,
+                Named::Enum(enumeration) => is_unit_enum(enumeration),
+                Named::Alias(alias) | Named::Inline(alias) => {
+                    !is_string(alias) && self.is_copy(alias.aliased())
+                }
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    /// Whether the Rust type of the expression is `Eq` and `Hash`, which
+    /// are the types without floats and maps.
+    pub fn is_hashable(&self, expression: &TypeExpression, visited: &mut Vec<String>) -> bool {
+        let name = match expression.kind() {
+            TypeKind::Integer { .. } | TypeKind::Unit => return true,
+            TypeKind::Array(inner) | TypeKind::Option(inner) => {
+                return self.is_hashable(inner, visited)
+            }
+            TypeKind::Map(..) => return false,
+            TypeKind::Named(name) => name,
+        };
+
+        // a recursive type is hashable, if the rest of it is
+        if visited.contains(name) {
+            return true;
+        }
+
+        visited.push(name.clone());
+
+        match self.lookup(name) {
+            Named::Primitive => !matches!(name.as_str(), "f32" | "f64"),
+            Named::Struct(structure) => structure
+                .fields()
+                .all(|field| self.is_hashable(field.field_type(), visited)),
+            Named::Enum(enumeration) => enumeration
+                .members()
+                .flat_map(|member| member.payload())
+                .all(|payload| self.is_hashable(payload, visited)),
+            Named::Flags => true,
+            Named::Alias(alias) | Named::Inline(alias) => {
+                is_string(alias) || self.is_hashable(alias.aliased(), visited)
+            }
+            Named::Unknown => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use network_parser::parser::network::NetworkFileReader;
+
+    use super::*;
+
+    #[test]
+    fn names() {
+        assert_eq!(type_name("post_tag"), "PostTag");
+        assert_eq!(field_name("authorId"), "author_id");
+        assert_eq!(field_name("type"), "r#type");
+        assert_eq!(type_name("Self"), "Self_");
+        assert_eq!(constant_name("maxLength"), "MAX_LENGTH");
+    }
+
+    #[test]
+    fn rust_types() {
+        let source = "struct Tree {\n    children: Tree[];\n    parent: option(Tree);\n}\n\n\
+            struct String {\n    value: map(string -> option(u(12)));\n}";
+        let file = NetworkFileReader::from_source(source).unwrap();
+        let types = Types::new(&file);
+
+        let rust_types = file
+            .structures()
+            .flat_map(|structure| {
+                structure
+                    .fields()
+                    .map(|field| types.rust_type(field.field_type(), Some(structure.name())))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            rust_types,
+            vec![
+                "Vec<Tree>",
+                "Option<Box<Tree>>",
+                "::std::collections::HashMap<::std::string::String, Option<u16>>",
+            ]
+        );
+    }
+}
@@ -0,0 +1,262 @@
+//! The lookup of the declarations a type expression refers to, which is
+//! shared by the generators of all languages.
+
+use network_parser::parser::{
+    alias::NetworkType,
+    enumeration::{EnumMember, NetworkEnum},
+    expression::{TypeExpression, TypeKind},
+    flags::NetworkFlags,
+    network::NetworkFileReader,
+    resolver::{FIXED_TAG, STRING_CONVERT_TAG},
+    structure::NetworkStruct,
+    tag::Tag,
+    VARINT_TYPES,
+};
+use util_cases::CaseStyles;
+
+use crate::GenerateError;
+
+/// The width of the smallest integer primitive, which holds an integer of
+/// this amount of bits.
+pub fn integer_width(bits: u32) -> u32 {
+    match bits {
+        0..=8 => 8,
+        9..=16 => 16,
+        17..=32 => 32,
+        33..=64 => 64,
+        _ => 128,
+    }
+}
+
+/// The smallest integer primitive, which holds an integer of this width.
+pub fn integer_type(signed: bool, bits: u32) -> String {
+    format!("{}{}", if signed { "i" } else { "u" }, integer_width(bits))
+}
+
+/// The sign and the amount of bits of an integer primitive.
+pub fn integer_bits(primitive: &str) -> Option<(bool, u32)> {
+    let (signed, bits) = primitive.split_at_checked(1)?;
+    let signed = match signed {
+        "u" => false,
+        "i" => true,
+        _ => return None,
+    };
+
+    Some((signed, bits.parse().ok()?))
+}
+
+/// What a type name refers to.
+#[derive(Clone, Copy)]
+pub enum Named<'a> {
+    Struct(&'a NetworkStruct),
+    Enum(&'a NetworkEnum),
+    Flags,
+    /// An alias, which is generated as a type of its' own.
+    Alias(&'a NetworkType),
+    /// An alias, which is replaced by the type it aliases, such as the
+    /// aliases of the prelude and the aliases named like primitives.
+    Inline(&'a NetworkType),
+    Primitive,
+    Unknown,
+}
+
+/// Whether the alias is converted to a string.
+pub fn is_string(alias: &NetworkType) -> bool {
+    alias.tags().any(|tag| tag.name() == STRING_CONVERT_TAG)
+}
+
+/// Whether the integers of the tagged definition are encoded in fixed
+/// width.
+pub fn is_fixed<'t>(mut tags: impl Iterator<Item = &'t Tag>) -> bool {
+    tags.any(|tag| tag.name() == FIXED_TAG)
+}
+
+/// The declarations of a network file, which the generators look up by
+/// name.
+pub struct Types<'a> {
+    pub(crate) file: &'a NetworkFileReader,
+}
+
+impl<'a> Types<'a> {
+    pub fn new(file: &'a NetworkFileReader) -> Self {
+        Self { file }
+    }
+
+    /// The declaration of the name. Declarations of the file shadow the
+    /// prelude, which shadows the primitives.
+    pub fn lookup(&self, name: &str) -> Named<'a> {
+        let file = self.file;
+
+        if let Some(structure) = file.structures().find(|other| other.name() == name) {
+            return Named::Struct(structure);
+        }
+
+        if let Some(enumeration) = file.enumerations().find(|other| other.name() == name) {
+            return Named::Enum(enumeration);
+        }
+
+        if file.all_flags().any(|other| other.name() == name) {
+            return Named::Flags;
+        }
+
+        // aliases named like primitives, as `type string = u8[];`, are
+        // primitives themselves
+        if let Some(alias) = file.aliases().find(|other| other.name() == name) {
+            return match name.is_strict_flat_case() {
+                true => Named::Inline(alias),
+                false => Named::Alias(alias),
+            };
+        }
+
+        let prelude = file
+            .prelude()
+            .and_then(|prelude| prelude.aliases().find(|other| other.name() == name));
+
+        if let Some(alias) = prelude {
+            return Named::Inline(alias);
+        }
+
+        match file.primitives().iter().any(|primitive| primitive == name) {
+            true => Named::Primitive,
+            false => Named::Unknown,
+        }
+    }
+
+    /// Whether the integers of the expression are varints, which the
+    /// `@fixed` tag encodes in fixed width.
+    pub fn has_varint(&self, expression: &TypeExpression) -> bool {
+        match expression.kind() {
+            TypeKind::Integer { bits, .. } => *bits > 8,
+            TypeKind::Array(inner) | TypeKind::Option(inner) => self.has_varint(inner),
+            TypeKind::Named(name) => match self.lookup(name) {
+                Named::Primitive => VARINT_TYPES.contains(&name.as_str()),
+                Named::Alias(alias) | Named::Inline(alias) if !is_string(alias) => {
+                    self.has_varint(alias.aliased())
+                }
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    /// The integer, which encodes the discriminators of the enum. Aliases
+    /// are followed, the default is `u8`.
+    pub fn discriminator(&self, enumeration: &NetworkEnum) -> Discriminator {
+        let mut fixed = false;
+        let mut expression = enumeration.discriminator_type().cloned();
+        let mut visited = vec![];
+
+        let (signed, bits) = loop {
+            let Some(current) = expression else {
+                break (false, 8);
+            };
+
+            match current.kind() {
+                TypeKind::Integer { signed, bits } => break (*signed, *bits),
+                TypeKind::Named(name) if !visited.contains(name) => {
+                    visited.push(name.clone());
+
+                    match self.lookup(name) {
+                        Named::Alias(alias) | Named::Inline(alias) => {
+                            fixed |= is_fixed(alias.tags());
+                            expression = Some(alias.aliased().clone());
+                        }
+                        _ => break integer_bits(name).unwrap_or((false, 8)),
+                    }
+                }
+                _ => break (false, 8),
+            }
+        };
+
+        Discriminator {
+            signed,
+            bits,
+            fixed: fixed && bits > 8,
+        }
+    }
+
+    /// The members of the enum with their' discriminator. Fails, if a
+    /// discriminator does not fit into the integer of the discriminators.
+    pub fn discriminators<'e>(
+        &self,
+        enumeration: &'e NetworkEnum,
+    ) -> Result<Vec<(&'e EnumMember, u64)>, GenerateError> {
+        let discriminator = self.discriminator(enumeration);
+        let max = match discriminator.signed {
+            true => (1u128 << (discriminator.bits - 1)) - 1,
+            false => u128::MAX >> (128 - discriminator.bits),
+        };
+
+        let discriminators = enumeration.discriminators();
+        let overflow =
+            |member: &EnumMember, discriminator, type_name| GenerateError::DiscriminatorOverflow {
+                enumeration: enumeration.name().to_owned(),
+                member: member.name().to_owned(),
+                discriminator,
+                type_name,
+            };
+
+        enumeration
+            .members()
+            .enumerate()
+            .map(|(index, member)| match discriminators.get(index) {
+                Some((_, value)) if *value as u128 <= max => Ok((member, *value)),
+                Some((_, value)) => Err(overflow(
+                    member,
+                    *value as u128,
+                    integer_type(discriminator.signed, discriminator.bits),
+                )),
+                // the discriminators end before a member, whose' implicit
+                // discriminator overflows a `u64`
+                None => Err(overflow(member, u64::MAX as u128 + 1, "u64".to_owned())),
+            })
+            .collect()
+    }
+}
+
+/// The integer, which encodes the discriminators of an enum.
+#[derive(Debug, Clone, Copy)]
+pub struct Discriminator {
+    pub signed: bool,
+    pub bits: u32,
+    /// Whether the discriminator is encoded in fixed width, because the
+    /// alias of the integer has the `@fixed` tag.
+    pub fixed: bool,
+}
+
+/// Fails with the first member of the flags, which occupies an invalid
+/// or an already occupied position.
+pub fn validate_flags(flags: &NetworkFlags) -> Result<(), GenerateError> {
+    flags
+        .validate()
+        .map_err(|mut errors| errors.remove(0).1.into())
+}
+
+/// Fails with the first field of the structure, whose' number is
+/// missing, unexpected, invalid or already taken.
+pub fn validate_struct(structure: &NetworkStruct) -> Result<(), GenerateError> {
+    structure
+        .validate()
+        .map_err(|mut errors| errors.remove(0).1.into())
+}
+
+/// Whether no member of the enum carries a payload.
+pub fn is_unit_enum(enumeration: &NetworkEnum) -> bool {
+    enumeration
+        .members()
+        .all(|member| member.payload().next().is_none())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integers() {
+        assert_eq!(integer_type(false, 1), "u8");
+        assert_eq!(integer_type(true, 12), "i16");
+        assert_eq!(integer_type(false, 65), "u128");
+        assert_eq!(integer_bits("i64"), Some((true, 64)));
+        assert_eq!(integer_bits("f32"), None);
+    }
+}
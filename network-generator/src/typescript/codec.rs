@@ -0,0 +1,202 @@
+//! The TypeScript types of type expressions and the expressions, which
+//! encode and decode their' values.
+
+use std::collections::BTreeSet;
+
+use network_parser::parser::expression::{TypeExpression, TypeKind};
+
+use super::{namespace_name, type_name};
+use crate::types::{integer_bits, integer_width, is_string, Named, Types};
+
+/// The generation of a single TypeScript module, which collects the
+/// declarations the module has to import.
+pub struct Module<'t, 'a> {
+    types: &'t Types<'a>,
+    /// The declaration of the module, which is not imported.
+    owner: &'t str,
+    imports: BTreeSet<String>,
+}
+
+impl<'t, 'a> Module<'t, 'a> {
+    pub fn new(types: &'t Types<'a>, owner: &'t str) -> Self {
+        Self {
+            types,
+            owner,
+            imports: BTreeSet::new(),
+        }
+    }
+
+    /// The declarations the module refers to.
+    pub fn imports(&self) -> impl Iterator<Item = &String> {
+        self.imports.iter()
+    }
+
+    /// The namespace of the functions of a declaration, which is empty for
+    /// the declaration of the module itself.
+    fn namespace(&mut self, name: &str) -> String {
+        if name == self.owner {
+            return String::new();
+        }
+
+        self.imports.insert(name.to_owned());
+        format!("{}.", namespace_name(name))
+    }
+
+    /// The TypeScript type of the expression. Integers of more than 32
+    /// bits are `bigint`, the other numbers are `number`.
+    pub fn ts_type(&mut self, expression: &TypeExpression) -> String {
+        match expression.kind() {
+            TypeKind::Named(name) => match self.types.lookup(name) {
+                Named::Inline(alias) if is_string(alias) => "string".to_owned(),
+                Named::Inline(alias) if is_bool(name, alias.aliased()) => "boolean".to_owned(),
+                Named::Inline(alias) => self.ts_type(alias.aliased()),
+                Named::Primitive => match integer_bits(name) {
+                    Some((_, bits)) => integer_type(bits).to_owned(),
+                    None => "number".to_owned(),
+                },
+                Named::Unknown => "unknown".to_owned(),
+                _ => {
+                    if name != self.owner {
+                        self.imports.insert(name.clone());
+                    }
+
+                    type_name(name)
+                }
+            },
+            TypeKind::Array(element) => match self.ts_type(element) {
+                element if element.contains(' ') => format!("({element})[]"),
+                element => format!("{element}[]"),
+            },
+            TypeKind::Option(inner) => format!("{} | null", self.ts_type(inner)),
+            TypeKind::Map(key, value) => {
+                format!("Map<{}, {}>", self.ts_type(key), self.ts_type(value))
+            }
+            TypeKind::Integer { bits, .. } => integer_type(*bits).to_owned(),
+            TypeKind::Unit => "null".to_owned(),
+        }
+    }
+
+    /// The expression, which writes the `value` of the expression to the
+    /// `writer`. Like the Rust backend, the `@fixed` tag only applies to
+    /// the integers of the expression and the containers around them.
+    pub fn encode(&mut self, expression: &TypeExpression, value: &str, fixed: bool) -> String {
+        let fixed = fixed && self.types.has_varint(expression);
+
+        match expression.kind() {
+            TypeKind::Named(name) => match self.types.lookup(name) {
+                Named::Inline(alias) if is_string(alias) => format!("writer.writeString({value})"),
+                Named::Inline(alias) if is_bool(name, alias.aliased()) => {
+                    format!("writer.writeBool({value})")
+                }
+                Named::Inline(alias) => self.encode(alias.aliased(), value, fixed),
+                Named::Alias(alias) if fixed => self.encode(alias.aliased(), value, true),
+                Named::Primitive => match integer_bits(name) {
+                    Some((signed, bits)) => encode_integer(signed, bits, value, fixed),
+                    None => format!("writer.write{}({value})", name.to_uppercase()),
+                },
+                _ => format!("{}encode({value}, writer)", self.namespace(name)),
+            },
+            TypeKind::Array(element) => format!(
+                "writer.writeArray({value}, (item) => {})",
+                self.encode(element, "item", fixed)
+            ),
+            TypeKind::Option(inner) => format!(
+                "writer.writeOption({value}, (item) => {})",
+                self.encode(inner, "item", fixed)
+            ),
+            TypeKind::Map(key, item) => format!(
+                "writer.writeMap({value}, (key) => {}, (item) => {})",
+                self.encode(key, "key", false),
+                self.encode(item, "item", false)
+            ),
+            TypeKind::Integer { signed, bits } => encode_integer(*signed, *bits, value, fixed),
+            TypeKind::Unit => format!("void {value}"),
+        }
+    }
+
+    /// The expression, which reads a value of the expression from the
+    /// `reader`. The counterpart of [Self::encode].
+    pub fn decode(&mut self, expression: &TypeExpression, fixed: bool) -> String {
+        let fixed = fixed && self.types.has_varint(expression);
+
+        match expression.kind() {
+            TypeKind::Named(name) => match self.types.lookup(name) {
+                Named::Inline(alias) if is_string(alias) => "reader.readString()".to_owned(),
+                Named::Inline(alias) if is_bool(name, alias.aliased()) => {
+                    "reader.readBool()".to_owned()
+                }
+                Named::Inline(alias) => self.decode(alias.aliased(), fixed),
+                Named::Alias(alias) if fixed => self.decode(alias.aliased(), true),
+                Named::Primitive => match integer_bits(name) {
+                    Some((signed, bits)) => decode_integer(signed, bits, fixed),
+                    None => format!("reader.read{}()", name.to_uppercase()),
+                },
+                _ => format!("{}decode(reader)", self.namespace(name)),
+            },
+            TypeKind::Array(element) => {
+                format!("reader.readArray(() => {})", self.decode(element, fixed))
+            }
+            TypeKind::Option(inner) => {
+                format!("reader.readOption(() => {})", self.decode(inner, fixed))
+            }
+            TypeKind::Map(key, item) => format!(
+                "reader.readMap(() => {}, () => {})",
+                self.decode(key, false),
+                self.decode(item, false)
+            ),
+            TypeKind::Integer { signed, bits } => decode_integer(*signed, *bits, fixed),
+            TypeKind::Unit => "null".to_owned(),
+        }
+    }
+}
+
+/// Whether the alias is the boolean of the standard library.
+pub fn is_bool(name: &str, aliased: &TypeExpression) -> bool {
+    name == "bool" && aliased.name() == Some("u8")
+}
+
+/// The TypeScript type of an integer.
+pub fn integer_type(bits: u32) -> &'static str {
+    match integer_width(bits) > 32 {
+        true => "bigint",
+        false => "number",
+    }
+}
+
+/// A literal of an integer, with the `n` suffix of a `bigint`.
+pub fn integer_literal(bits: u32, value: impl std::fmt::Display) -> String {
+    match integer_width(bits) > 32 {
+        true => format!("{value}n"),
+        false => value.to_string(),
+    }
+}
+
+/// The expression, which writes an integer. Integers of 8 bits are one
+/// byte, wider integers are varints unless they are `fixed`.
+pub fn encode_integer(signed: bool, bits: u32, value: &str, fixed: bool) -> String {
+    let width = integer_width(bits);
+    let big = if width > 32 { "Big" } else { "" };
+
+    match (width, fixed, signed) {
+        (8, _, false) => format!("writer.writeByte({value})"),
+        (8, _, true) => format!("writer.writeFixed({value}, 8)"),
+        (_, true, _) => format!("writer.write{big}Fixed({value}, {width})"),
+        (_, false, false) => format!("writer.write{big}Varint({value})"),
+        (_, false, true) => format!("writer.writeSigned{big}Varint({value})"),
+    }
+}
+
+/// The expression, which reads an integer. The counterpart of
+/// [encode_integer].
+pub fn decode_integer(signed: bool, bits: u32, fixed: bool) -> String {
+    let width = integer_width(bits);
+    let big = if width > 32 { "Big" } else { "" };
+
+    match (width, fixed, signed) {
+        (8, _, false) => "reader.readByte()".to_owned(),
+        (8, _, true) => "reader.readFixed(8, true)".to_owned(),
+        (_, true, _) => format!("reader.read{big}Fixed({width}, {signed})"),
+        (_, false, false) => format!("reader.read{big}Varint({width})"),
+        (_, false, true) => format!("reader.readSigned{big}Varint({width})"),
+    }
+}
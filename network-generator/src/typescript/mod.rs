@@ -0,0 +1,643 @@
+//! The TypeScript backend, which generates a module for every declaration
+//! of a network file with its' type and the functions `encode(value,
+//! writer)` and `decode(reader)`.
+//!
+//! - Structs become interfaces, the fields of an `option struct` are
+//!   optional and encoded as a message.
+//! - Enums become discriminated unions over the `kind` of the member, the
+//!   payload is the `value`. Enums without payloads are unions of the
+//!   member names.
+//! - Flags become integers, with a constant for every member.
+//! - Aliases become type aliases.
+//! - Constants are collected in the module `consts`.
+//!
+//! Integers of more than 32 bits are `bigint`. The readers and writers
+//! of the runtime module `netz` are copied from the templates, the module
+//! `index` exports all modules.
+
+mod codec;
+
+use network_parser::parser::{
+    alias::NetworkType,
+    documentation::Documentation,
+    enumeration::NetworkEnum,
+    evaluate::{ConstEvaluator, ConstValue},
+    expression::{TypeExpression, TypeKind},
+    flags::NetworkFlags,
+    network::NetworkFileReader,
+    structure::NetworkStruct,
+};
+use util_cases::CaseStyles;
+
+use self::codec::{decode_integer, encode_integer, integer_literal, integer_type, is_bool, Module};
+use crate::{
+    code::CodeWriter,
+    types::{
+        integer_bits, is_fixed, is_string, is_unit_enum, validate_flags, validate_struct, Named,
+        Types,
+    },
+    GenerateError,
+};
+
+/// The comment at the beginning of every generated file.
+const HEADER: &str = "// This file is generated from a network file, do not edit it by hand.";
+
+/// The readers and writers, which the generated modules use.
+const RUNTIME: &str = include_str!("../../../templates/typescript/both.static.ts");
+
+/// The name of the module of the runtime.
+const RUNTIME_MODULE: &str = "netz";
+
+/// The name of the module of the constants.
+const CONSTS_MODULE: &str = "consts";
+
+/// The name of the generated type of a declaration, which is prefixed
+/// with `I` like the interfaces of the templates.
+pub fn type_name(name: &str) -> String {
+    format!("I{}", name.to_pascal_case())
+}
+
+/// The name of the namespace, which the functions of a declaration are
+/// imported as.
+pub fn namespace_name(name: &str) -> String {
+    name.to_pascal_case()
+}
+
+/// The name of the module of a declaration in `dash-case`.
+pub fn module_name(name: &str) -> String {
+    name.to_kebab_case()
+}
+
+/// Generates the TypeScript modules of a network file. The file is
+/// expected to have no errors, so it should be
+/// [resolved](NetworkFileReader::resolve) first.
+///
+/// ```
+/// use network_generator::typescript::TypeScriptGenerator;
+/// use network_parser::parser::network::NetworkFileReader;
+///
+/// let file = NetworkFileReader::from_source("struct User {\n    name: string;\n}").unwrap();
+/// let modules = TypeScriptGenerator::new(&file).generate().unwrap();
+/// let names = modules.iter().map(|(name, _)| name.as_str()).collect::<Vec<_>>();
+///
+/// assert_eq!(names, ["netz.ts", "user.ts", "index.ts"]);
+/// assert!(modules[1].1.contains("export default interface IUser {\n    name: string;\n}"));
+/// ```
+pub struct TypeScriptGenerator<'a> {
+    file: &'a NetworkFileReader,
+    types: Types<'a>,
+}
+
+impl<'a> TypeScriptGenerator<'a> {
+    pub fn new(file: &'a NetworkFileReader) -> Self {
+        Self {
+            file,
+            types: Types::new(file),
+        }
+    }
+
+    /// The file names and the source code of the modules. The runtime comes
+    /// first and the index last, the constants are followed by aliases,
+    /// flags, enums and structs in the order of their' declaration.
+    pub fn generate(&self) -> Result<Vec<(String, String)>, GenerateError> {
+        let mut modules = vec![(
+            format!("{RUNTIME_MODULE}.ts"),
+            format!("{HEADER}\n\n{RUNTIME}"),
+        )];
+        let mut index = CodeWriter::new("    ");
+
+        index.line(HEADER);
+        index.blank();
+        index.line(format!(
+            "export {{ NetworkFile }} from \"./{RUNTIME_MODULE}\";"
+        ));
+
+        if self.file.consts().next().is_some() {
+            modules.push((format!("{CONSTS_MODULE}.ts"), self.consts()?));
+            index.line(format!("export * from \"./{CONSTS_MODULE}\";"));
+        }
+
+        let mut declarations = vec![];
+
+        for alias in self.file.aliases() {
+            if let Named::Alias(_) = self.types.lookup(alias.name()) {
+                declarations.push((alias.name(), self.alias(alias)));
+            }
+        }
+
+        for flags in self.file.all_flags() {
+            validate_flags(flags)?;
+            declarations.push((flags.name(), self.flags(flags)));
+        }
+
+        for enumeration in self.file.enumerations() {
+            declarations.push((enumeration.name(), self.enumeration(enumeration)?));
+        }
+
+        for structure in self.file.structures() {
+            validate_struct(structure)?;
+            declarations.push((structure.name(), self.structure(structure)));
+        }
+
+        for (name, code) in declarations {
+            let module = module_name(name);
+
+            index.blank();
+            index.line(format!(
+                "export type {{ default as {} }} from \"./{module}\";",
+                type_name(name)
+            ));
+            index.line(format!(
+                "export * as {} from \"./{module}\";",
+                namespace_name(name)
+            ));
+            modules.push((format!("{module}.ts"), code));
+        }
+
+        modules.push(("index.ts".to_owned(), index.finish()));
+        Ok(modules)
+    }
+
+    /// Writes the description of the documentation as doc comment.
+    fn documentation(&self, code: &mut CodeWriter, documentation: Option<&Documentation>) {
+        let Some(documentation) = documentation else {
+            return;
+        };
+
+        code.line("/**");
+
+        for line in documentation.description().lines() {
+            match line.trim_end() {
+                "" => code.line(" *"),
+                line => code.line(format!(" * {line}")),
+            }
+        }
+
+        code.line(" */");
+    }
+
+    /// The source code of a module, which starts with the imports of the
+    /// runtime and the declarations the module refers to.
+    fn module(&self, module: Module, code: CodeWriter) -> String {
+        let mut header = CodeWriter::new("    ");
+
+        header.line(HEADER);
+        header.blank();
+        header.line(format!(
+            "import {{ NetworkFile }} from \"./{RUNTIME_MODULE}\";"
+        ));
+
+        for name in module.imports() {
+            let path = module_name(name);
+
+            header.line(format!(
+                "import type {} from \"./{path}\";",
+                type_name(name)
+            ));
+            header.line(format!(
+                "import * as {} from \"./{path}\";",
+                namespace_name(name)
+            ));
+        }
+
+        header.blank();
+        header.finish() + &code.finish()
+    }
+
+    /// The signature of the function, which encodes a value of the type.
+    fn encode_signature(&self, name: &str) -> String {
+        format!("export function encode(value: {name}, writer: NetworkFile.Writer): void")
+    }
+
+    /// Opens the function, which encodes a value of the type.
+    fn open_encode(&self, code: &mut CodeWriter, name: &str) {
+        code.open(format!("{} {{", self.encode_signature(name)));
+    }
+
+    /// Opens the function, which decodes a value of the type.
+    fn open_decode(&self, code: &mut CodeWriter, name: &str) {
+        code.open(format!(
+            "export function decode(reader: NetworkFile.Reader): {name} {{"
+        ));
+    }
+
+    fn consts(&self) -> Result<String, GenerateError> {
+        let mut code = CodeWriter::new("    ");
+        let values = ConstEvaluator::new(self.file).evaluate()?;
+
+        code.line(HEADER);
+
+        for constant in self.file.consts() {
+            let (const_type, literal) = self
+                .constant_value(constant.const_type(), &values[constant.name()])
+                .ok_or_else(|| GenerateError::UnsupportedConstant {
+                    constant: constant.name().to_owned(),
+                    type_name: constant.const_type().to_string(),
+                })?;
+
+            code.blank();
+            self.documentation(&mut code, constant.documentation());
+            code.line(format!(
+                "export const {}: {const_type} = {literal};",
+                constant.name().to_constant_case()
+            ));
+        }
+
+        Ok(code.finish())
+    }
+
+    /// The TypeScript type and the literal of a constant value, `None` if
+    /// the type has no constant values. Aliases are replaced by the type
+    /// they alias.
+    fn constant_value(
+        &self,
+        expression: &TypeExpression,
+        value: &ConstValue,
+    ) -> Option<(String, String)> {
+        let bits = match expression.kind() {
+            TypeKind::Integer { bits, .. } => *bits,
+            TypeKind::Named(name) => match self.types.lookup(name) {
+                Named::Alias(alias) | Named::Inline(alias) if is_string(alias) => {
+                    let ConstValue::String(string) = value else {
+                        return None;
+                    };
+
+                    return Some(("string".to_owned(), format!("{string:?}")));
+                }
+                Named::Inline(alias) if is_bool(name, alias.aliased()) => {
+                    let ConstValue::Integer(integer) = value else {
+                        return None;
+                    };
+
+                    return Some(("boolean".to_owned(), (*integer != 0).to_string()));
+                }
+                Named::Alias(alias) | Named::Inline(alias) => {
+                    return self.constant_value(alias.aliased(), value)
+                }
+                Named::Primitive => match (integer_bits(name), value) {
+                    (Some((_, bits)), _) => bits,
+                    (None, ConstValue::Float(float)) => {
+                        return Some(("number".to_owned(), format!("{float:?}")))
+                    }
+                    (None, ConstValue::Integer(integer)) => {
+                        return Some(("number".to_owned(), integer.to_string()))
+                    }
+                    _ => return None,
+                },
+                _ => return None,
+            },
+            _ => return None,
+        };
+
+        let ConstValue::Integer(integer) = value else {
+            return None;
+        };
+
+        Some((
+            integer_type(bits).to_owned(),
+            integer_literal(bits, integer),
+        ))
+    }
+
+    /// Writes an alias as type alias.
+    fn alias(&self, alias: &NetworkType) -> String {
+        let name = type_name(alias.name());
+        let mut module = Module::new(&self.types, alias.name());
+        let mut code = CodeWriter::new("    ");
+        let fixed = is_fixed(alias.tags());
+
+        let (ts_type, encode, decode) = match is_string(alias) {
+            true => (
+                "string".to_owned(),
+                "writer.writeString(value)".to_owned(),
+                "reader.readString()".to_owned(),
+            ),
+            false => (
+                module.ts_type(alias.aliased()),
+                module.encode(alias.aliased(), "value", fixed),
+                module.decode(alias.aliased(), fixed),
+            ),
+        };
+
+        self.documentation(&mut code, alias.documentation());
+        code.line(format!("type {name} = {ts_type};"));
+        code.blank();
+        code.line(format!("export default {name};"));
+        code.blank();
+        self.open_encode(&mut code, &name);
+        code.line(format!("{encode};"));
+        code.close("}");
+        code.blank();
+        self.open_decode(&mut code, &name);
+        code.line(format!("return {decode};"));
+        code.close("}");
+
+        self.module(module, code)
+    }
+
+    /// Writes flags as the unsigned integer, which holds all of the flags,
+    /// and a constant for every flag. The flags are encoded in fixed width.
+    fn flags(&self, flags: &NetworkFlags) -> String {
+        let name = type_name(flags.name());
+        let module = Module::new(&self.types, flags.name());
+        let mut code = CodeWriter::new("    ");
+        let bits = flags.width().bits() as u32;
+
+        self.documentation(&mut code, flags.documentation());
+        code.line(format!("type {name} = {};", integer_type(bits)));
+        code.blank();
+        code.line(format!("export default {name};"));
+
+        for member in flags.members() {
+            code.blank();
+            self.documentation(&mut code, member.documentation());
+            code.line(format!(
+                "export const {}: {name} = {};",
+                member.name().to_constant_case(),
+                integer_literal(bits, format!("0x{:x}", 1u64 << member.bit()))
+            ));
+        }
+
+        code.blank();
+        self.open_encode(&mut code, &name);
+        code.line(format!("{};", encode_integer(false, bits, "value", true)));
+        code.close("}");
+        code.blank();
+        self.open_decode(&mut code, &name);
+        code.line(format!("return {};", decode_integer(false, bits, true)));
+        code.close("}");
+
+        self.module(module, code)
+    }
+
+    fn enumeration(&self, enumeration: &NetworkEnum) -> Result<String, GenerateError> {
+        let name = type_name(enumeration.name());
+        let members = self.types.discriminators(enumeration)?;
+        let discriminator = self.types.discriminator(enumeration);
+        let (signed, bits, fixed) = (
+            discriminator.signed,
+            discriminator.bits,
+            discriminator.fixed,
+        );
+        let unit = is_unit_enum(enumeration);
+
+        let mut module = Module::new(&self.types, enumeration.name());
+        let mut code = CodeWriter::new("    ");
+
+        self.documentation(&mut code, enumeration.documentation());
+
+        if members.is_empty() {
+            code.line(format!("type {name} = never;"));
+        } else {
+            code.open(format!("type {name} ="));
+        }
+
+        for (index, (member, _)) in members.iter().enumerate() {
+            let end = if index + 1 == members.len() { ";" } else { "" };
+            let kind = format!("{:?}", member.name());
+            let payload = member
+                .payload()
+                .map(|payload| module.ts_type(payload))
+                .collect::<Vec<_>>();
+
+            self.documentation(&mut code, member.documentation());
+
+            match payload.as_slice() {
+                _ if unit => code.line(format!("| {kind}{end}")),
+                [] => code.line(format!("| {{ kind: {kind} }}{end}")),
+                [value] => code.line(format!("| {{ kind: {kind}; value: {value} }}{end}")),
+                values => code.line(format!(
+                    "| {{ kind: {kind}; value: [{}] }}{end}",
+                    values.join(", ")
+                )),
+            }
+        }
+
+        if !members.is_empty() {
+            code.outdent();
+        }
+
+        code.blank();
+        code.line(format!("export default {name};"));
+        code.blank();
+
+        self.open_encode(&mut code, &name);
+        code.open(match unit {
+            true => "switch (value) {",
+            false => "switch (value.kind) {",
+        });
+
+        for (member, discriminator) in members.iter() {
+            let literal = integer_literal(bits, discriminator);
+            let payload = member.payload().collect::<Vec<_>>();
+
+            code.open(format!("case {:?}:", member.name()));
+            code.line(format!(
+                "{};",
+                encode_integer(signed, bits, &literal, fixed)
+            ));
+
+            for (index, expression) in payload.iter().enumerate() {
+                let value = match payload.len() {
+                    1 => "value.value".to_owned(),
+                    _ => format!("value.value[{index}]"),
+                };
+
+                code.line(format!("{};", module.encode(expression, &value, false)));
+            }
+
+            code.line("break;");
+            code.outdent();
+        }
+
+        code.close("}");
+        code.close("}");
+        code.blank();
+
+        self.open_decode(&mut code, &name);
+        code.line(format!(
+            "const discriminator = {};",
+            decode_integer(signed, bits, fixed)
+        ));
+        code.blank();
+        code.open("switch (discriminator) {");
+
+        for (member, discriminator) in members.iter() {
+            let kind = format!("{:?}", member.name());
+            let payload = member
+                .payload()
+                .map(|payload| module.decode(payload, false))
+                .collect::<Vec<_>>();
+
+            code.open(format!("case {}:", integer_literal(bits, discriminator)));
+
+            match payload.as_slice() {
+                _ if unit => code.line(format!("return {kind};")),
+                [] => code.line(format!("return {{ kind: {kind} }};")),
+                [value] => code.line(format!("return {{ kind: {kind}, value: {value} }};")),
+                values => code.line(format!(
+                    "return {{ kind: {kind}, value: [{}] }};",
+                    values.join(", ")
+                )),
+            }
+
+            code.outdent();
+        }
+
+        code.open("default:");
+        code.line(format!(
+            "throw new RangeError(`invalid discriminant ${{discriminator}} of {}`);",
+            enumeration.name()
+        ));
+        code.outdent();
+        code.close("}");
+        code.close("}");
+
+        Ok(self.module(module, code))
+    }
+
+    fn structure(&self, structure: &NetworkStruct) -> String {
+        let name = type_name(structure.name());
+        let message = structure.is_message();
+        let mut module = Module::new(&self.types, structure.name());
+        let mut code = CodeWriter::new("    ");
+
+        self.documentation(&mut code, structure.documentation());
+
+        if structure.fields().next().is_none() {
+            code.line(format!("export default interface {name} {{}}"));
+        } else {
+            code.open(format!("export default interface {name} {{"));
+
+            for field in structure.fields() {
+                let optional = if message { "?" } else { "" };
+
+                self.documentation(&mut code, field.documentation());
+                code.line(format!(
+                    "{}{optional}: {};",
+                    field.name().to_camel_case(),
+                    module.ts_type(field.field_type())
+                ));
+            }
+
+            code.close("}");
+        }
+
+        code.blank();
+
+        match message {
+            true => self.message_codec(&mut module, &mut code, structure),
+            false => self.structure_codec(&mut module, &mut code, structure),
+        }
+
+        self.module(module, code)
+    }
+
+    /// Encodes and decodes the fields of a struct in the order of their'
+    /// declaration.
+    fn structure_codec(
+        &self,
+        module: &mut Module,
+        code: &mut CodeWriter,
+        structure: &NetworkStruct,
+    ) {
+        let name = type_name(structure.name());
+
+        if structure.fields().next().is_none() {
+            code.line(format!("{} {{}}", self.encode_signature(&name)));
+        } else {
+            self.open_encode(code, &name);
+        }
+
+        for field in structure.fields() {
+            let value = format!("value.{}", field.name().to_camel_case());
+            let fixed = is_fixed(field.tags());
+
+            code.line(format!(
+                "{};",
+                module.encode(field.field_type(), &value, fixed)
+            ));
+        }
+
+        if structure.fields().next().is_some() {
+            code.close("}");
+        }
+
+        code.blank();
+        self.open_decode(code, &name);
+
+        if structure.fields().next().is_none() {
+            code.line("return {};");
+        } else {
+            code.open("return {");
+
+            for field in structure.fields() {
+                let fixed = is_fixed(field.tags());
+
+                code.line(format!(
+                    "{}: {},",
+                    field.name().to_camel_case(),
+                    module.decode(field.field_type(), fixed)
+                ));
+            }
+
+            code.close("};");
+        }
+
+        code.close("}");
+    }
+
+    /// Encodes the present fields of an `option struct` as a message and
+    /// decodes them, while unknown fields are skipped.
+    fn message_codec(&self, module: &mut Module, code: &mut CodeWriter, structure: &NetworkStruct) {
+        let name = type_name(structure.name());
+
+        self.open_encode(code, &name);
+
+        for (index, field) in structure.fields().enumerate() {
+            code.line(format!(
+                "const field{index} = value.{};",
+                field.name().to_camel_case()
+            ));
+        }
+
+        for (index, field) in structure.fields().enumerate() {
+            let fixed = is_fixed(field.tags());
+            let encode = module.encode(field.field_type(), &format!("field{index}"), fixed);
+
+            code.line(format!(
+                "if (field{index} !== undefined) writer.writeField({}, (writer) => {encode});",
+                field.number().unwrap_or_default()
+            ));
+        }
+
+        code.line("writer.writeByte(0);");
+        code.close("}");
+        code.blank();
+
+        self.open_decode(code, &name);
+        code.line(format!("const value: {name} = {{}};"));
+        code.blank();
+        code.open("reader.readFields((number, reader) => {");
+        code.open("switch (number) {");
+
+        for field in structure.fields() {
+            let fixed = is_fixed(field.tags());
+
+            code.open(format!("case {}:", field.number().unwrap_or_default()));
+            code.line(format!(
+                "value.{} = {};",
+                field.name().to_camel_case(),
+                module.decode(field.field_type(), fixed)
+            ));
+            code.line("break;");
+            code.outdent();
+        }
+
+        code.close("}");
+        code.close("});");
+        code.blank();
+        code.line("return value;");
+        code.close("}");
+    }
+}
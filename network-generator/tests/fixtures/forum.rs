@@ -0,0 +1,391 @@
+// This file is generated from a network file, do not edit it by hand.
+
+/// The largest amount of posts in a thread.
+pub const MAX_POSTS: u16 = 500;
+
+pub const GREETING: &str = "Welcome!";
+
+pub const PI: f32 = 3.25;
+
+pub const ENABLED: bool = true;
+
+pub const START: Timestamp = Timestamp(1700000000);
+
+/// Seconds since the unix epoch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Timestamp(pub u64);
+
+impl ::network_library::encode::Encode for Timestamp {
+    fn encode<W: ::network_library::encode::Writer + ?Sized>(&self, writer: &mut W) -> ::std::io::Result<()> {
+        let Self(field0) = self;
+        ::network_library::encode::Encode::encode(field0, writer)?;
+        Ok(())
+    }
+}
+
+impl ::network_library::decode::Decode for Timestamp {
+    fn decode<R: ::network_library::decode::Reader + ?Sized>(reader: &mut R) -> Result<Self, ::network_library::decode::DecodeError> {
+        let field0 = ::network_library::decode::Decode::decode(reader)?;
+        Ok(Self(field0))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Hash(pub u64);
+
+impl ::network_library::encode::Encode for Hash {
+    fn encode<W: ::network_library::encode::Writer + ?Sized>(&self, writer: &mut W) -> ::std::io::Result<()> {
+        let Self(field0) = self;
+        ::network_library::encode::Encode::encode(&::network_library::encode::Fixed(*field0), writer)?;
+        Ok(())
+    }
+}
+
+impl ::network_library::decode::Decode for Hash {
+    fn decode<R: ::network_library::decode::Reader + ?Sized>(reader: &mut R) -> Result<Self, ::network_library::decode::DecodeError> {
+        let field0 = <::network_library::encode::Fixed<u64> as ::network_library::decode::Decode>::decode(reader)?.0;
+        Ok(Self(field0))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Name(pub String);
+
+impl ::network_library::encode::Encode for Name {
+    fn encode<W: ::network_library::encode::Writer + ?Sized>(&self, writer: &mut W) -> ::std::io::Result<()> {
+        let Self(field0) = self;
+        ::network_library::encode::Encode::encode(field0, writer)?;
+        Ok(())
+    }
+}
+
+impl ::network_library::decode::Decode for Name {
+    fn decode<R: ::network_library::decode::Reader + ?Sized>(reader: &mut R) -> Result<Self, ::network_library::decode::DecodeError> {
+        let field0 = ::network_library::decode::Decode::decode(reader)?;
+        Ok(Self(field0))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Permission(u16);
+
+impl Permission {
+    pub const READ: Self = Self(1 << 0);
+    pub const WRITE: Self = Self(1 << 1);
+    pub const MODERATION: Self = Self(1 << 2);
+    pub const ADMIN: Self = Self(1 << 8);
+
+    /// The set without flags.
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    /// The set of the raw bits, which keeps undeclared bits.
+    pub const fn from_bits(bits: u16) -> Self {
+        Self(bits)
+    }
+
+    /// The raw bits of the set.
+    pub const fn bits(&self) -> u16 {
+        self.0
+    }
+
+    /// Whether all flags of `other` are set.
+    pub const fn contains(&self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Sets the flags of `other`.
+    pub fn insert(&mut self, other: Self) {
+        self.0 |= other.0;
+    }
+
+    /// Clears the flags of `other`.
+    pub fn remove(&mut self, other: Self) {
+        self.0 &= !other.0;
+    }
+}
+
+impl ::core::ops::BitOr for Permission {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+}
+
+impl ::network_library::encode::Encode for Permission {
+    fn encode<W: ::network_library::encode::Writer + ?Sized>(&self, writer: &mut W) -> ::std::io::Result<()> {
+        ::network_library::encode::Writer::write_bytes(writer, &self.0.to_le_bytes())
+    }
+}
+
+impl ::network_library::decode::Decode for Permission {
+    fn decode<R: ::network_library::decode::Reader + ?Sized>(reader: &mut R) -> Result<Self, ::network_library::decode::DecodeError> {
+        let mut bytes = [0; 2];
+        ::network_library::decode::Reader::read_bytes(reader, &mut bytes)?;
+        Ok(Self(u16::from_le_bytes(bytes)))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Status {
+    Online,
+    Away,
+    Offline,
+}
+
+impl ::network_library::encode::Encode for Status {
+    fn encode<W: ::network_library::encode::Writer + ?Sized>(&self, writer: &mut W) -> ::std::io::Result<()> {
+        match self {
+            Self::Online => ::network_library::encode::Encode::encode(&0u8, writer),
+            Self::Away => ::network_library::encode::Encode::encode(&5u8, writer),
+            Self::Offline => ::network_library::encode::Encode::encode(&6u8, writer),
+        }
+    }
+}
+
+impl ::network_library::decode::Decode for Status {
+    fn decode<R: ::network_library::decode::Reader + ?Sized>(reader: &mut R) -> Result<Self, ::network_library::decode::DecodeError> {
+        match <u8 as ::network_library::decode::Decode>::decode(reader)? {
+            0 => Ok(Self::Online),
+            5 => Ok(Self::Away),
+            6 => Ok(Self::Offline),
+            other => Err(::network_library::decode::DecodeError::InvalidDiscriminant { name: "Status", value: other as u128 }),
+        }
+    }
+}
+
+/// The content of a post.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Content {
+    Empty,
+    Text(String),
+    Image(Hash, Vec<u32>),
+    Reply(Box<Post>),
+}
+
+impl ::network_library::encode::Encode for Content {
+    fn encode<W: ::network_library::encode::Writer + ?Sized>(&self, writer: &mut W) -> ::std::io::Result<()> {
+        match self {
+            Self::Empty => ::network_library::encode::Encode::encode(&0u16, writer),
+            Self::Text(field0) => {
+                ::network_library::encode::Encode::encode(&300u16, writer)?;
+                ::network_library::encode::Encode::encode(field0, writer)?;
+                Ok(())
+            }
+            Self::Image(field0, field1) => {
+                ::network_library::encode::Encode::encode(&301u16, writer)?;
+                ::network_library::encode::Encode::encode(field0, writer)?;
+                ::network_library::encode::Encode::encode(field1, writer)?;
+                Ok(())
+            }
+            Self::Reply(field0) => {
+                ::network_library::encode::Encode::encode(&302u16, writer)?;
+                ::network_library::encode::Encode::encode(field0, writer)?;
+                Ok(())
+            }
+        }
+    }
+}
+
+impl ::network_library::decode::Decode for Content {
+    fn decode<R: ::network_library::decode::Reader + ?Sized>(reader: &mut R) -> Result<Self, ::network_library::decode::DecodeError> {
+        match <u16 as ::network_library::decode::Decode>::decode(reader)? {
+            0 => Ok(Self::Empty),
+            300 => {
+                let field0 = ::network_library::decode::Decode::decode(reader)?;
+                Ok(Self::Text(field0))
+            }
+            301 => {
+                let field0 = ::network_library::decode::Decode::decode(reader)?;
+                let field1 = ::network_library::decode::Decode::decode(reader)?;
+                Ok(Self::Image(field0, field1))
+            }
+            302 => {
+                let field0 = ::network_library::decode::Decode::decode(reader)?;
+                Ok(Self::Reply(field0))
+            }
+            other => Err(::network_library::decode::DecodeError::InvalidDiscriminant { name: "Content", value: other as u128 }),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct User {
+    pub name: Name,
+    pub permission: Permission,
+    pub status: Status,
+    pub id: u32,
+    pub nickname: Option<String>,
+}
+
+impl ::network_library::encode::Encode for User {
+    fn encode<W: ::network_library::encode::Writer + ?Sized>(&self, writer: &mut W) -> ::std::io::Result<()> {
+        let Self { name: field0, permission: field1, status: field2, id: field3, nickname: field4 } = self;
+        ::network_library::encode::Encode::encode(field0, writer)?;
+        ::network_library::encode::Encode::encode(field1, writer)?;
+        ::network_library::encode::Encode::encode(field2, writer)?;
+        ::network_library::encode::Encode::encode(&::network_library::encode::Fixed(*field3), writer)?;
+        ::network_library::encode::Encode::encode(field4, writer)?;
+        Ok(())
+    }
+}
+
+impl ::network_library::decode::Decode for User {
+    fn decode<R: ::network_library::decode::Reader + ?Sized>(reader: &mut R) -> Result<Self, ::network_library::decode::DecodeError> {
+        let field0 = ::network_library::decode::Decode::decode(reader)?;
+        let field1 = ::network_library::decode::Decode::decode(reader)?;
+        let field2 = ::network_library::decode::Decode::decode(reader)?;
+        let field3 = <::network_library::encode::Fixed<u32> as ::network_library::decode::Decode>::decode(reader)?.0;
+        let field4 = ::network_library::decode::Decode::decode(reader)?;
+        Ok(Self { name: field0, permission: field1, status: field2, id: field3, nickname: field4 })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Post {
+    pub author: User,
+    pub created: Timestamp,
+    pub content: Box<Content>,
+    pub reactions: ::std::collections::HashMap<String, i32>,
+    pub deltas: Vec<Option<i64>>,
+    pub score: i32,
+    pub rating: f64,
+    pub pinned: bool,
+}
+
+impl ::network_library::encode::Encode for Post {
+    fn encode<W: ::network_library::encode::Writer + ?Sized>(&self, writer: &mut W) -> ::std::io::Result<()> {
+        let Self { author: field0, created: field1, content: field2, reactions: field3, deltas: field4, score: field5, rating: field6, pinned: field7 } = self;
+        ::network_library::encode::Encode::encode(field0, writer)?;
+        ::network_library::encode::Encode::encode(field1, writer)?;
+        ::network_library::encode::Encode::encode(field2, writer)?;
+        ::network_library::encode::Encode::encode(field3, writer)?;
+        ::network_library::encode::Writer::write_7bit(writer, field4.len() as u64)?;
+        for field4_element in field4 {
+            match field4_element {
+                None => ::network_library::encode::Writer::write_byte(writer, 0)?,
+                Some(field4_element_inner) => {
+                    ::network_library::encode::Writer::write_byte(writer, 1)?;
+                    ::network_library::encode::Encode::encode(&::network_library::encode::Fixed(*field4_element_inner), writer)?;
+                }
+            }
+        }
+        ::network_library::encode::Encode::encode(field5, writer)?;
+        ::network_library::encode::Encode::encode(field6, writer)?;
+        ::network_library::encode::Encode::encode(field7, writer)?;
+        Ok(())
+    }
+}
+
+impl ::network_library::decode::Decode for Post {
+    fn decode<R: ::network_library::decode::Reader + ?Sized>(reader: &mut R) -> Result<Self, ::network_library::decode::DecodeError> {
+        let field0 = ::network_library::decode::Decode::decode(reader)?;
+        let field1 = ::network_library::decode::Decode::decode(reader)?;
+        let field2 = ::network_library::decode::Decode::decode(reader)?;
+        let field3 = ::network_library::decode::Decode::decode(reader)?;
+        let field4_length = ::network_library::decode::Reader::read_length(reader)?;
+        let mut field4 = Vec::new();
+        for _ in 0..field4_length {
+            let field4_element = match ::network_library::decode::Reader::read_byte(reader)? {
+                0 => None,
+                1 => {
+                    let field4_element_inner = <::network_library::encode::Fixed<i64> as ::network_library::decode::Decode>::decode(reader)?.0;
+                    Some(field4_element_inner)
+                }
+                value => {
+                    return Err(::network_library::decode::DecodeError::InvalidDiscriminant { name: "Option", value: value as u128 });
+                }
+            };
+            field4.push(field4_element);
+        }
+        let field5 = ::network_library::decode::Decode::decode(reader)?;
+        let field6 = ::network_library::decode::Decode::decode(reader)?;
+        let field7 = ::network_library::decode::Decode::decode(reader)?;
+        Ok(Self { author: field0, created: field1, content: field2, reactions: field3, deltas: field4, score: field5, rating: field6, pinned: field7 })
+    }
+}
+
+/// Settings, which new clients can extend.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Settings {
+    pub theme: Option<String>,
+    pub volume: Option<u16>,
+    pub permission: Option<Permission>,
+}
+
+impl ::network_library::encode::Encode for Settings {
+    fn encode<W: ::network_library::encode::Writer + ?Sized>(&self, writer: &mut W) -> ::std::io::Result<()> {
+        let mut message = ::network_library::message::MessageWriter::new();
+
+        if let Some(field0) = &self.theme {
+            let mut bytes = Vec::<u8>::new();
+            let writer = &mut bytes;
+            ::network_library::encode::Encode::encode(field0, writer)?;
+            message.write_field(1, &bytes);
+        }
+
+        if let Some(field1) = &self.volume {
+            let mut bytes = Vec::<u8>::new();
+            let writer = &mut bytes;
+            ::network_library::encode::Encode::encode(&::network_library::encode::Fixed(*field1), writer)?;
+            message.write_field(2, &bytes);
+        }
+
+        if let Some(field2) = &self.permission {
+            let mut bytes = Vec::<u8>::new();
+            let writer = &mut bytes;
+            ::network_library::encode::Encode::encode(field2, writer)?;
+            message.write_field(4, &bytes);
+        }
+
+        ::network_library::encode::Writer::write_bytes(writer, &message.finish())
+    }
+}
+
+impl ::network_library::decode::Decode for Settings {
+    fn decode<R: ::network_library::decode::Reader + ?Sized>(reader: &mut R) -> Result<Self, ::network_library::decode::DecodeError> {
+        let mut message = Self::default();
+
+        while let Some((number, bytes)) = ::network_library::message::read_field(reader)? {
+            match number {
+                1 => {
+                    let reader = &mut &bytes[..];
+                    let field0 = ::network_library::decode::Decode::decode(reader)?;
+                    message.theme = Some(field0);
+                }
+                2 => {
+                    let reader = &mut &bytes[..];
+                    let field1 = <::network_library::encode::Fixed<u16> as ::network_library::decode::Decode>::decode(reader)?.0;
+                    message.volume = Some(field1);
+                }
+                4 => {
+                    let reader = &mut &bytes[..];
+                    let field2 = ::network_library::decode::Decode::decode(reader)?;
+                    message.permission = Some(field2);
+                }
+                _ => (),
+            }
+        }
+
+        Ok(message)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Empty {}
+
+impl ::network_library::encode::Encode for Empty {
+    fn encode<W: ::network_library::encode::Writer + ?Sized>(&self, writer: &mut W) -> ::std::io::Result<()> {
+        let _ = writer;
+        Ok(())
+    }
+}
+
+impl ::network_library::decode::Decode for Empty {
+    fn decode<R: ::network_library::decode::Reader + ?Sized>(reader: &mut R) -> Result<Self, ::network_library::decode::DecodeError> {
+        let _ = reader;
+        Ok(Self {})
+    }
+}
@@ -0,0 +1,14 @@
+// This file is generated from a network file, do not edit it by hand.
+
+/**
+ * The largest amount of posts in a thread.
+ */
+export const MAX_POSTS: number = 500;
+
+export const GREETING: string = "Welcome!";
+
+export const PI: number = 3.25;
+
+export const ENABLED: boolean = true;
+
+export const START: bigint = 1700000000n;
@@ -0,0 +1,56 @@
+// This file is generated from a network file, do not edit it by hand.
+
+import { NetworkFile } from "./netz";
+import type IHash from "./hash";
+import * as Hash from "./hash";
+import type IPost from "./post";
+import * as Post from "./post";
+
+/**
+ * The content of a post.
+ */
+type IContent =
+    | { kind: "Empty" }
+    | { kind: "Text"; value: string }
+    | { kind: "Image"; value: [IHash, number[]] }
+    | { kind: "Reply"; value: IPost };
+
+export default IContent;
+
+export function encode(value: IContent, writer: NetworkFile.Writer): void {
+    switch (value.kind) {
+        case "Empty":
+            writer.writeVarint(0);
+            break;
+        case "Text":
+            writer.writeVarint(300);
+            writer.writeString(value.value);
+            break;
+        case "Image":
+            writer.writeVarint(301);
+            Hash.encode(value.value[0], writer);
+            writer.writeArray(value.value[1], (item) => writer.writeVarint(item));
+            break;
+        case "Reply":
+            writer.writeVarint(302);
+            Post.encode(value.value, writer);
+            break;
+    }
+}
+
+export function decode(reader: NetworkFile.Reader): IContent {
+    const discriminator = reader.readVarint(16);
+
+    switch (discriminator) {
+        case 0:
+            return { kind: "Empty" };
+        case 300:
+            return { kind: "Text", value: reader.readString() };
+        case 301:
+            return { kind: "Image", value: [Hash.decode(reader), reader.readArray(() => reader.readVarint(32))] };
+        case 302:
+            return { kind: "Reply", value: Post.decode(reader) };
+        default:
+            throw new RangeError(`invalid discriminant ${discriminator} of Content`);
+    }
+}
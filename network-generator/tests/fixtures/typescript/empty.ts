@@ -0,0 +1,11 @@
+// This file is generated from a network file, do not edit it by hand.
+
+import { NetworkFile } from "./netz";
+
+export default interface IEmpty {}
+
+export function encode(value: IEmpty, writer: NetworkFile.Writer): void {}
+
+export function decode(reader: NetworkFile.Reader): IEmpty {
+    return {};
+}
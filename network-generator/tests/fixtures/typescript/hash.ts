@@ -0,0 +1,15 @@
+// This file is generated from a network file, do not edit it by hand.
+
+import { NetworkFile } from "./netz";
+
+type IHash = bigint;
+
+export default IHash;
+
+export function encode(value: IHash, writer: NetworkFile.Writer): void {
+    writer.writeBigFixed(value, 64);
+}
+
+export function decode(reader: NetworkFile.Reader): IHash {
+    return reader.readBigFixed(64, false);
+}
@@ -0,0 +1,34 @@
+// This file is generated from a network file, do not edit it by hand.
+
+export { NetworkFile } from "./netz";
+export * from "./consts";
+
+export type { default as ITimestamp } from "./timestamp";
+export * as Timestamp from "./timestamp";
+
+export type { default as IHash } from "./hash";
+export * as Hash from "./hash";
+
+export type { default as IName } from "./name";
+export * as Name from "./name";
+
+export type { default as IPermission } from "./permission";
+export * as Permission from "./permission";
+
+export type { default as IStatus } from "./status";
+export * as Status from "./status";
+
+export type { default as IContent } from "./content";
+export * as Content from "./content";
+
+export type { default as IUser } from "./user";
+export * as User from "./user";
+
+export type { default as IPost } from "./post";
+export * as Post from "./post";
+
+export type { default as ISettings } from "./settings";
+export * as Settings from "./settings";
+
+export type { default as IEmpty } from "./empty";
+export * as Empty from "./empty";
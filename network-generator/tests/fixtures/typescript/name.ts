@@ -0,0 +1,15 @@
+// This file is generated from a network file, do not edit it by hand.
+
+import { NetworkFile } from "./netz";
+
+type IName = string;
+
+export default IName;
+
+export function encode(value: IName, writer: NetworkFile.Writer): void {
+    writer.writeString(value);
+}
+
+export function decode(reader: NetworkFile.Reader): IName {
+    return reader.readString();
+}
@@ -0,0 +1,545 @@
+// This file is generated from a network file, do not edit it by hand.
+
+
+export namespace NetworkFile {
+    /**
+     * This magic number is `0111 1111` in binary and `0x7F` in hexadecimal.
+     */
+    const LOWER7_MASK = 127;
+
+    /**
+     * This magic number is `1000 000` in binary and `0x80` in hexadecimal.
+     */
+    const HIGH8_MASK = 128;
+
+    /**
+     * Class to perform 7-bit integer decoding and encoding. Under '7-bit
+     * -encoding' is meant a representation of an integer as a pure byte
+     * buffer. The bytes are separated by their first bit, the 'flag' and
+     * the rest, the 'mantisse'. If a 7-bit integer is read, bytes will be
+     * read while the flag is set, and terminate with the last byte, which
+     * sets this flag to zero. The mantisse is accumulated to create the
+     * integer.
+     */
+    export class Bit7Integer {
+        /**
+         * Reads the size of an integer in its' 7-bit encoded form.
+         *
+         * ```
+         * 1(111 1110) 0(000 0001)
+         * ```
+         */
+        public static length7BitInt(value: number): number {
+            let size = 1;
+            while (value >= HIGH8_MASK) (value = Math.floor(value / HIGH8_MASK)), size++;
+            return size;
+        }
+
+        /**
+         * Reads in bytes of an integer in 7-bit encoding and decodes
+         * the number from the generating bytes. The callback function
+         * for providing the bytes will be called while the highest bit
+         * is set. The first byte holds the lowest seven bits. Throws a
+         * `RangeError` if the integer is not a safe integer.
+         *
+         * @example
+         *
+         * ```
+         * 1111 0000 1010 1010 1000 0000 0000 0001 Reading In
+         * ^--- ---- ^--- ---- ^--- ---- ^--- ----
+         *  111 0000  010 1010  000 0000  000 0001 Writing Out
+         * ```
+         */
+        public static read7BitInt(generate: () => number): number {
+            let value = 0,
+                shift = 0,
+                byte = 0;
+
+            do {
+                // a safe integer has at most 53 bits, which are 8 bytes
+                if (shift > 49) throw new RangeError("the 7-bit integer does not fit into 53 bits");
+
+                byte = generate();
+                value += (byte & LOWER7_MASK) * 2 ** shift;
+                shift += 7;
+            } while (byte & HIGH8_MASK);
+
+            if (!Number.isSafeInteger(value)) throw new RangeError("the 7-bit integer does not fit into 53 bits");
+
+            return value;
+        }
+
+        /**
+         * Yield bytes encoding an integer into a 7-bit integer. The lowest
+         * seven bits are written first.
+         */
+        public static *write7BitInt(value: number) {
+            while (value >= HIGH8_MASK) {
+                yield (value % HIGH8_MASK) | HIGH8_MASK;
+                value = Math.floor(value / HIGH8_MASK);
+            }
+
+            yield value;
+        }
+
+        /**
+         * Reads in bytes of a signed integer in zigzag and 7-bit encoding,
+         * so `0, 1, 2, 3, 4, ...` become `0, -1, 1, -2, 2, ...`.
+         */
+        public static readSigned7BitInt(generate: () => number): number {
+            const value = Bit7Integer.read7BitInt(generate);
+            return value % 2 ? -(value + 1) / 2 : value / 2;
+        }
+
+        /**
+         * Yield bytes encoding a signed integer in zigzag and 7-bit encoding.
+         */
+        public static *writeSigned7BitInt(value: number) {
+            yield* Bit7Integer.write7BitInt(value < 0 ? -2 * value - 1 : 2 * value);
+        }
+
+        /**
+         * Reads in bytes of an integer in 7-bit encoding, which may not fit
+         * into a safe integer, such as the 64 and 128 bit integers. Throws a
+         * `RangeError` if the integer has more than 128 bits.
+         */
+        public static read7BitBigInt(generate: () => number): bigint {
+            let value = 0n,
+                shift = 0n,
+                byte = 0;
+
+            do {
+                if (shift > 126n) throw new RangeError("the 7-bit integer does not fit into 128 bits");
+
+                byte = generate();
+                value |= BigInt(byte & LOWER7_MASK) << shift;
+                shift += 7n;
+            } while (byte & HIGH8_MASK);
+
+            return value;
+        }
+
+        /**
+         * Yield bytes encoding a big integer into a 7-bit integer. The lowest
+         * seven bits are written first.
+         */
+        public static *write7BitBigInt(value: bigint) {
+            while (value >= BigInt(HIGH8_MASK)) {
+                yield Number(value & BigInt(LOWER7_MASK)) | HIGH8_MASK;
+                value >>= 7n;
+            }
+
+            yield Number(value);
+        }
+
+        /**
+         * Reads in bytes of a signed big integer in zigzag and 7-bit encoding.
+         */
+        public static readSigned7BitBigInt(generate: () => number): bigint {
+            const value = Bit7Integer.read7BitBigInt(generate);
+            return value & 1n ? -(value + 1n) / 2n : value / 2n;
+        }
+
+        /**
+         * Yield bytes encoding a signed big integer in zigzag and 7-bit
+         * encoding.
+         */
+        public static *writeSigned7BitBigInt(value: bigint) {
+            yield* Bit7Integer.write7BitBigInt(value < 0n ? -2n * value - 1n : 2n * value);
+        }
+    }
+
+    /**
+     * Class to perform floating point decoding and encoding. The primitives
+     * `f32` and `f64` are encoded as IEEE 754 numbers in little endian
+     * order, so they take four and eight bytes. Negative zero keeps its'
+     * sign. NaN is decoded as NaN, but JavaScript engines may not keep its'
+     * payload bits.
+     */
+    export class Float {
+        /**
+         * Reads in the four bytes of an `f32` in little endian order.
+         */
+        public static readF32(generate: () => number): number {
+            const view = new DataView(new ArrayBuffer(4));
+            for (let i = 0; i < 4; i++) view.setUint8(i, generate());
+            return view.getFloat32(0, true);
+        }
+
+        /**
+         * Reads in the eight bytes of an `f64` in little endian order.
+         */
+        public static readF64(generate: () => number): number {
+            const view = new DataView(new ArrayBuffer(8));
+            for (let i = 0; i < 8; i++) view.setUint8(i, generate());
+            return view.getFloat64(0, true);
+        }
+
+        /**
+         * Yield the four bytes of an `f32` in little endian order. The
+         * number is rounded to the nearest `f32`.
+         */
+        public static *writeF32(value: number) {
+            const view = new DataView(new ArrayBuffer(4));
+            view.setFloat32(0, value, true);
+            for (let i = 0; i < 4; i++) yield view.getUint8(i);
+        }
+
+        /**
+         * Yield the eight bytes of an `f64` in little endian order.
+         */
+        public static *writeF64(value: number) {
+            const view = new DataView(new ArrayBuffer(8));
+            view.setFloat64(0, value, true);
+            for (let i = 0; i < 8; i++) yield view.getUint8(i);
+        }
+    }
+
+    const ENCODER = new TextEncoder();
+    const DECODER = new TextDecoder("utf-8", { fatal: true });
+
+    /**
+     * Class to encode values into a byte buffer, which grows as values
+     * are written. The bytes are written through a `DataView`, integers
+     * of a fixed width and floats in little endian order.
+     *
+     * @example
+     *
+     * ```
+     * const writer = new NetworkFile.Writer();
+     * writer.writeString("netz");
+     * writer.writeVarint(300);
+     * writer.finish(); // [4, 110, 101, 116, 122, 172, 2]
+     * ```
+     */
+    export class Writer {
+        private view = new DataView(new ArrayBuffer(64));
+        private length = 0;
+
+        /**
+         * Reserves space for `size` more bytes and returns the offset to
+         * write them at. The view may be replaced, so it must be accessed
+         * after the call.
+         */
+        private reserve(size: number): number {
+            const offset = this.length;
+
+            if (offset + size > this.view.byteLength) {
+                const bytes = new Uint8Array(Math.max(2 * this.view.byteLength, offset + size));
+                bytes.set(new Uint8Array(this.view.buffer, 0, offset));
+                this.view = new DataView(bytes.buffer);
+            }
+
+            this.length += size;
+            return offset;
+        }
+
+        public writeByte(value: number) {
+            const offset = this.reserve(1);
+            this.view.setUint8(offset, value);
+        }
+
+        public writeBytes(bytes: Uint8Array) {
+            const offset = this.reserve(bytes.length);
+            new Uint8Array(this.view.buffer).set(bytes, offset);
+        }
+
+        /**
+         * Writes a boolean as the byte `1` or `0`.
+         */
+        public writeBool(value: boolean) {
+            this.writeByte(value ? 1 : 0);
+        }
+
+        public writeVarint(value: number) {
+            for (const byte of Bit7Integer.write7BitInt(value)) this.writeByte(byte);
+        }
+
+        public writeSignedVarint(value: number) {
+            for (const byte of Bit7Integer.writeSigned7BitInt(value)) this.writeByte(byte);
+        }
+
+        public writeBigVarint(value: bigint) {
+            for (const byte of Bit7Integer.write7BitBigInt(value)) this.writeByte(byte);
+        }
+
+        public writeSignedBigVarint(value: bigint) {
+            for (const byte of Bit7Integer.writeSigned7BitBigInt(value)) this.writeByte(byte);
+        }
+
+        /**
+         * Writes an integer of 8, 16 or 32 bits in fixed width. Negative
+         * integers are written in two's complement.
+         */
+        public writeFixed(value: number, bits: number) {
+            const offset = this.reserve(bits / 8);
+
+            if (bits === 8) this.view.setUint8(offset, value);
+            else if (bits === 16) this.view.setUint16(offset, value, true);
+            else this.view.setUint32(offset, value, true);
+        }
+
+        /**
+         * Writes an integer of 64 or 128 bits in fixed width. Negative
+         * integers are written in two's complement.
+         */
+        public writeBigFixed(value: bigint, bits: number) {
+            const offset = this.reserve(bits / 8);
+            const unsigned = BigInt.asUintN(bits, value);
+
+            this.view.setBigUint64(offset, BigInt.asUintN(64, unsigned), true);
+            if (bits === 128) this.view.setBigUint64(offset + 8, unsigned >> 64n, true);
+        }
+
+        public writeF32(value: number) {
+            const offset = this.reserve(4);
+            this.view.setFloat32(offset, value, true);
+        }
+
+        public writeF64(value: number) {
+            const offset = this.reserve(8);
+            this.view.setFloat64(offset, value, true);
+        }
+
+        /**
+         * Writes the length of the string in bytes and the string in UTF-8.
+         */
+        public writeString(value: string) {
+            const bytes = ENCODER.encode(value);
+            this.writeVarint(bytes.length);
+            this.writeBytes(bytes);
+        }
+
+        /**
+         * Writes the byte `0` for `null`, or the byte `1` and the value.
+         */
+        public writeOption<T>(value: T | null, write: (value: T) => void) {
+            if (value === null) return this.writeByte(0);
+
+            this.writeByte(1);
+            write(value);
+        }
+
+        /**
+         * Writes the amount of values and every value.
+         */
+        public writeArray<T>(values: T[], write: (value: T) => void) {
+            this.writeVarint(values.length);
+            for (const value of values) write(value);
+        }
+
+        /**
+         * Writes the amount of entries and the key and value of every entry.
+         */
+        public writeMap<K, V>(map: Map<K, V>, writeKey: (key: K) => void, writeValue: (value: V) => void) {
+            this.writeVarint(map.size);
+
+            for (const [key, value] of map) {
+                writeKey(key);
+                writeValue(value);
+            }
+        }
+
+        /**
+         * Writes a field of a message, which is the number of the field,
+         * the length of the value in four bytes and the value. The value is
+         * written to a writer of its' own. A message ends with the byte `0`.
+         */
+        public writeField(number: number, write: (writer: Writer) => void) {
+            const field = new Writer();
+            write(field);
+
+            const bytes = field.finish();
+            this.writeByte(number);
+            this.writeFixed(bytes.length, 32);
+            this.writeBytes(bytes);
+        }
+
+        /**
+         * A copy of the written bytes.
+         */
+        public finish(): Uint8Array {
+            return new Uint8Array(this.view.buffer, 0, this.length).slice();
+        }
+    }
+
+    /**
+     * Class to decode values from a byte buffer, the counterpart of the
+     * `Writer`. Reading past the end of the buffer throws a `RangeError`,
+     * as does an integer, which does not fit into its' type.
+     */
+    export class Reader {
+        private view: DataView;
+        private offset = 0;
+
+        constructor(bytes: Uint8Array) {
+            this.view = new DataView(bytes.buffer, bytes.byteOffset, bytes.byteLength);
+        }
+
+        /**
+         * The amount of bytes, which were not read yet.
+         */
+        public get remaining(): number {
+            return this.view.byteLength - this.offset;
+        }
+
+        /**
+         * Skips `size` bytes and returns the offset of the first of them.
+         */
+        private advance(size: number): number {
+            if (size > this.remaining) throw new RangeError("unexpected end of input");
+
+            const offset = this.offset;
+            this.offset += size;
+            return offset;
+        }
+
+        public readByte(): number {
+            return this.view.getUint8(this.advance(1));
+        }
+
+        public readBytes(length: number): Uint8Array {
+            const offset = this.view.byteOffset + this.advance(length);
+            return new Uint8Array(this.view.buffer, offset, length).slice();
+        }
+
+        /**
+         * Reads a boolean, every byte but `0` is `true`.
+         */
+        public readBool(): boolean {
+            return this.readByte() !== 0;
+        }
+
+        public readVarint(bits: number): number {
+            const value = Bit7Integer.read7BitInt(() => this.readByte());
+            if (value >= 2 ** bits) throw new RangeError(`the 7-bit integer does not fit into ${bits} bits`);
+            return value;
+        }
+
+        public readSignedVarint(bits: number): number {
+            const value = Bit7Integer.readSigned7BitInt(() => this.readByte());
+            if (value < -(2 ** (bits - 1)) || value >= 2 ** (bits - 1))
+                throw new RangeError(`the 7-bit integer does not fit into ${bits} bits`);
+            return value;
+        }
+
+        public readBigVarint(bits: number): bigint {
+            const value = Bit7Integer.read7BitBigInt(() => this.readByte());
+            if (value >> BigInt(bits)) throw new RangeError(`the 7-bit integer does not fit into ${bits} bits`);
+            return value;
+        }
+
+        public readSignedBigVarint(bits: number): bigint {
+            const value = Bit7Integer.readSigned7BitBigInt(() => this.readByte());
+            if (BigInt.asIntN(bits, value) !== value)
+                throw new RangeError(`the 7-bit integer does not fit into ${bits} bits`);
+            return value;
+        }
+
+        /**
+         * Reads an integer of 8, 16 or 32 bits in fixed width.
+         */
+        public readFixed(bits: number, signed: boolean): number {
+            const offset = this.advance(bits / 8);
+
+            if (bits === 8) return signed ? this.view.getInt8(offset) : this.view.getUint8(offset);
+            if (bits === 16) return signed ? this.view.getInt16(offset, true) : this.view.getUint16(offset, true);
+            return signed ? this.view.getInt32(offset, true) : this.view.getUint32(offset, true);
+        }
+
+        /**
+         * Reads an integer of 64 or 128 bits in fixed width.
+         */
+        public readBigFixed(bits: number, signed: boolean): bigint {
+            const offset = this.advance(bits / 8);
+            let value = this.view.getBigUint64(offset, true);
+
+            if (bits === 128) value |= this.view.getBigUint64(offset + 8, true) << 64n;
+            return signed ? BigInt.asIntN(bits, value) : value;
+        }
+
+        public readF32(): number {
+            return this.view.getFloat32(this.advance(4), true);
+        }
+
+        public readF64(): number {
+            return this.view.getFloat64(this.advance(8), true);
+        }
+
+        /**
+         * Reads the length of a string, an array or a map.
+         */
+        public readLength(): number {
+            return Bit7Integer.read7BitInt(() => this.readByte());
+        }
+
+        /**
+         * Reads a string in UTF-8. Throws a `TypeError` if the bytes are
+         * no valid UTF-8.
+         */
+        public readString(): string {
+            return DECODER.decode(this.readBytes(this.readLength()));
+        }
+
+        public readOption<T>(read: () => T): T | null {
+            const discriminant = this.readByte();
+
+            if (discriminant === 0) return null;
+            if (discriminant === 1) return read();
+            throw new RangeError(`invalid discriminant ${discriminant} of Option`);
+        }
+
+        public readArray<T>(read: () => T): T[] {
+            const length = this.readLength();
+            const values: T[] = [];
+
+            for (let i = 0; i < length; i++) values.push(read());
+            return values;
+        }
+
+        public readMap<K, V>(readKey: () => K, readValue: () => V): Map<K, V> {
+            const length = this.readLength();
+            const map = new Map<K, V>();
+
+            for (let i = 0; i < length; i++) {
+                const key = readKey();
+                map.set(key, readValue());
+            }
+
+            return map;
+        }
+
+        /**
+         * Reads the fields of a message until the byte `0`. Every field is
+         * passed to the callback with a reader over its' value, so unknown
+         * fields can be skipped.
+         */
+        public readFields(read: (number: number, reader: Reader) => void) {
+            for (let number = this.readByte(); number !== 0; number = this.readByte()) {
+                const length = this.readFixed(32, false);
+                read(number, new Reader(this.readBytes(length)));
+            }
+        }
+    }
+
+    /**
+     * Encodes a value into bytes.
+     */
+    export function toBytes<T>(encode: (value: T, writer: Writer) => void, value: T): Uint8Array {
+        const writer = new Writer();
+        encode(value, writer);
+        return writer.finish();
+    }
+
+    /**
+     * Decodes a value from bytes. Throws a `RangeError` if not all bytes
+     * were read.
+     */
+    export function fromBytes<T>(decode: (reader: Reader) => T, bytes: Uint8Array): T {
+        const reader = new Reader(bytes);
+        const value = decode(reader);
+
+        if (reader.remaining) throw new RangeError(`${reader.remaining} trailing bytes`);
+        return value;
+    }
+}
@@ -0,0 +1,23 @@
+// This file is generated from a network file, do not edit it by hand.
+
+import { NetworkFile } from "./netz";
+
+type IPermission = number;
+
+export default IPermission;
+
+export const READ: IPermission = 0x1;
+
+export const WRITE: IPermission = 0x2;
+
+export const MODERATION: IPermission = 0x4;
+
+export const ADMIN: IPermission = 0x100;
+
+export function encode(value: IPermission, writer: NetworkFile.Writer): void {
+    writer.writeFixed(value, 16);
+}
+
+export function decode(reader: NetworkFile.Reader): IPermission {
+    return reader.readFixed(16, false);
+}
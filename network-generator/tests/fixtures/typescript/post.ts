@@ -0,0 +1,44 @@
+// This file is generated from a network file, do not edit it by hand.
+
+import { NetworkFile } from "./netz";
+import type IContent from "./content";
+import * as Content from "./content";
+import type ITimestamp from "./timestamp";
+import * as Timestamp from "./timestamp";
+import type IUser from "./user";
+import * as User from "./user";
+
+export default interface IPost {
+    author: IUser;
+    created: ITimestamp;
+    content: IContent;
+    reactions: Map<string, number>;
+    deltas: (bigint | null)[];
+    score: number;
+    rating: number;
+    pinned: boolean;
+}
+
+export function encode(value: IPost, writer: NetworkFile.Writer): void {
+    User.encode(value.author, writer);
+    Timestamp.encode(value.created, writer);
+    Content.encode(value.content, writer);
+    writer.writeMap(value.reactions, (key) => writer.writeString(key), (item) => writer.writeSignedVarint(item));
+    writer.writeArray(value.deltas, (item) => writer.writeOption(item, (item) => writer.writeBigFixed(item, 64)));
+    writer.writeSignedVarint(value.score);
+    writer.writeF64(value.rating);
+    writer.writeBool(value.pinned);
+}
+
+export function decode(reader: NetworkFile.Reader): IPost {
+    return {
+        author: User.decode(reader),
+        created: Timestamp.decode(reader),
+        content: Content.decode(reader),
+        reactions: reader.readMap(() => reader.readString(), () => reader.readSignedVarint(32)),
+        deltas: reader.readArray(() => reader.readOption(() => reader.readBigFixed(64, true))),
+        score: reader.readSignedVarint(32),
+        rating: reader.readF64(),
+        pinned: reader.readBool(),
+    };
+}
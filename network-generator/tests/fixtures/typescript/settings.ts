@@ -0,0 +1,44 @@
+// This file is generated from a network file, do not edit it by hand.
+
+import { NetworkFile } from "./netz";
+import type IPermission from "./permission";
+import * as Permission from "./permission";
+
+/**
+ * Settings, which new clients can extend.
+ */
+export default interface ISettings {
+    theme?: string;
+    volume?: number;
+    permission?: IPermission;
+}
+
+export function encode(value: ISettings, writer: NetworkFile.Writer): void {
+    const field0 = value.theme;
+    const field1 = value.volume;
+    const field2 = value.permission;
+    if (field0 !== undefined) writer.writeField(1, (writer) => writer.writeString(field0));
+    if (field1 !== undefined) writer.writeField(2, (writer) => writer.writeFixed(field1, 16));
+    if (field2 !== undefined) writer.writeField(4, (writer) => Permission.encode(field2, writer));
+    writer.writeByte(0);
+}
+
+export function decode(reader: NetworkFile.Reader): ISettings {
+    const value: ISettings = {};
+
+    reader.readFields((number, reader) => {
+        switch (number) {
+            case 1:
+                value.theme = reader.readString();
+                break;
+            case 2:
+                value.volume = reader.readFixed(16, false);
+                break;
+            case 4:
+                value.permission = Permission.decode(reader);
+                break;
+        }
+    });
+
+    return value;
+}
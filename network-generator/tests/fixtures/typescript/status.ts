@@ -0,0 +1,39 @@
+// This file is generated from a network file, do not edit it by hand.
+
+import { NetworkFile } from "./netz";
+
+type IStatus =
+    | "Online"
+    | "Away"
+    | "Offline";
+
+export default IStatus;
+
+export function encode(value: IStatus, writer: NetworkFile.Writer): void {
+    switch (value) {
+        case "Online":
+            writer.writeByte(0);
+            break;
+        case "Away":
+            writer.writeByte(5);
+            break;
+        case "Offline":
+            writer.writeByte(6);
+            break;
+    }
+}
+
+export function decode(reader: NetworkFile.Reader): IStatus {
+    const discriminator = reader.readByte();
+
+    switch (discriminator) {
+        case 0:
+            return "Online";
+        case 5:
+            return "Away";
+        case 6:
+            return "Offline";
+        default:
+            throw new RangeError(`invalid discriminant ${discriminator} of Status`);
+    }
+}
@@ -0,0 +1,18 @@
+// This file is generated from a network file, do not edit it by hand.
+
+import { NetworkFile } from "./netz";
+
+/**
+ * Seconds since the unix epoch.
+ */
+type ITimestamp = bigint;
+
+export default ITimestamp;
+
+export function encode(value: ITimestamp, writer: NetworkFile.Writer): void {
+    writer.writeBigVarint(value);
+}
+
+export function decode(reader: NetworkFile.Reader): ITimestamp {
+    return reader.readBigVarint(64);
+}
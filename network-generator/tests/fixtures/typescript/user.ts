@@ -0,0 +1,35 @@
+// This file is generated from a network file, do not edit it by hand.
+
+import { NetworkFile } from "./netz";
+import type IName from "./name";
+import * as Name from "./name";
+import type IPermission from "./permission";
+import * as Permission from "./permission";
+import type IStatus from "./status";
+import * as Status from "./status";
+
+export default interface IUser {
+    name: IName;
+    permission: IPermission;
+    status: IStatus;
+    id: number;
+    nickname: string | null;
+}
+
+export function encode(value: IUser, writer: NetworkFile.Writer): void {
+    Name.encode(value.name, writer);
+    Permission.encode(value.permission, writer);
+    Status.encode(value.status, writer);
+    writer.writeFixed(value.id, 32);
+    writer.writeOption(value.nickname, (item) => writer.writeString(item));
+}
+
+export function decode(reader: NetworkFile.Reader): IUser {
+    return {
+        name: Name.decode(reader),
+        permission: Permission.decode(reader),
+        status: Status.decode(reader),
+        id: reader.readFixed(32, false),
+        nickname: reader.readOption(() => reader.readString()),
+    };
+}
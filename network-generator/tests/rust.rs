@@ -0,0 +1,249 @@
+use std::{collections::HashMap, env, fs};
+
+use network_generator::{build::Builder, rust::RustGenerator, GenerateError};
+use network_library::{
+    decode::{Decode, DecodeError},
+    encode::Encode,
+};
+use network_parser::parser::{loader::NetworkLoader, network::NetworkFileReader};
+
+/// The module generated from `fixtures/forum.net`, which is compared to
+/// the generator output in [golden]. Run the tests with `NETZ_BLESS=1` to
+/// update it.
+#[allow(dead_code)]
+mod forum {
+    include!("fixtures/forum.rs");
+}
+
+use forum::*;
+
+fn generate(path: &str) -> String {
+    let schema = NetworkLoader::new().load(path).unwrap();
+    assert!(schema.diagnostics().is_empty());
+
+    RustGenerator::new(&schema.namespace()).generate().unwrap()
+}
+
+fn round_trip<T: Encode + Decode + PartialEq + std::fmt::Debug>(value: T) -> Vec<u8> {
+    let bytes = value.to_bytes();
+    assert_eq!(T::from_bytes(&bytes).unwrap(), value);
+    bytes
+}
+
+fn user() -> User {
+    User {
+        name: Name("anatoly".to_owned()),
+        permission: Permission::READ | Permission::ADMIN,
+        status: Status::Away,
+        id: 7,
+        nickname: None,
+    }
+}
+
+#[test]
+fn golden() {
+    let code = generate("tests/fixtures/forum.net");
+
+    if env::var_os("NETZ_BLESS").is_some() {
+        fs::write("tests/fixtures/forum.rs", &code).unwrap();
+    }
+
+    assert_eq!(code, fs::read_to_string("tests/fixtures/forum.rs").unwrap());
+}
+
+#[test]
+fn constants() {
+    assert_eq!(MAX_POSTS, 500);
+    assert_eq!(GREETING, "Welcome!");
+    assert_eq!(PI, 3.25);
+    const { assert!(ENABLED) };
+    assert_eq!(START, Timestamp(1700000000));
+}
+
+#[test]
+fn aliases() {
+    assert_eq!(round_trip(Timestamp(300)), [0xac, 0x02]);
+    assert_eq!(round_trip(Hash(1)), [1, 0, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(round_trip(Name("hi".to_owned())), [2, b'h', b'i']);
+}
+
+#[test]
+fn flags() {
+    let mut permission = Permission::READ | Permission::ADMIN;
+    assert!(permission.contains(Permission::ADMIN));
+    assert!(!permission.contains(Permission::WRITE));
+
+    permission.insert(Permission::WRITE);
+    permission.remove(Permission::READ);
+    assert_eq!(permission.bits(), 0b1_0000_0010);
+    assert_eq!(round_trip(permission), [0b10, 0b1]);
+
+    // undeclared bits are kept
+    assert_eq!(
+        Permission::from_bytes(&[0xff, 0xff]).unwrap().bits(),
+        u16::MAX
+    );
+}
+
+#[test]
+fn enumerations() {
+    assert_eq!(round_trip(Status::Online), [0]);
+    assert_eq!(round_trip(Status::Offline), [6]);
+    assert!(matches!(
+        Status::from_bytes(&[1]),
+        Err(DecodeError::InvalidDiscriminant {
+            name: "Status",
+            value: 1
+        })
+    ));
+
+    assert_eq!(round_trip(Content::Empty), [0]);
+    assert_eq!(
+        round_trip(Content::Image(Hash(2), vec![1, 128])),
+        [0xad, 0x02, 2, 0, 0, 0, 0, 0, 0, 0, 2, 1, 0x80, 0x01]
+    );
+}
+
+#[test]
+fn structures() {
+    assert_eq!(
+        round_trip(user()),
+        [7, b'a', b'n', b'a', b't', b'o', b'l', b'y', 1, 1, 5, 7, 0, 0, 0, 0]
+    );
+
+    let post = Post {
+        author: user(),
+        created: Timestamp(1),
+        content: Box::new(Content::Reply(Box::new(Post {
+            author: user(),
+            created: Timestamp(0),
+            content: Box::new(Content::Text("first".to_owned())),
+            reactions: HashMap::new(),
+            deltas: vec![],
+            score: 0,
+            rating: 0.0,
+            pinned: false,
+        }))),
+        reactions: HashMap::from([("+1".to_owned(), -3)]),
+        deltas: vec![Some(-1), None],
+        score: -8_000_000,
+        rating: 4.5,
+        pinned: true,
+    };
+
+    round_trip(post);
+    assert_eq!(round_trip(Empty {}), []);
+}
+
+#[test]
+fn messages() {
+    let settings = Settings {
+        theme: None,
+        volume: Some(2),
+        permission: Some(Permission::MODERATION),
+    };
+
+    assert_eq!(
+        round_trip(settings.clone()),
+        [2, 2, 0, 0, 0, 2, 0, 4, 2, 0, 0, 0, 4, 0, 0]
+    );
+
+    // fields of a newer schema are skipped
+    let bytes = [
+        3, 1, 0, 0, 0, 9, 2, 2, 0, 0, 0, 2, 0, 4, 2, 0, 0, 0, 4, 0, 0,
+    ];
+    assert_eq!(Settings::from_bytes(&bytes).unwrap(), settings);
+    assert_eq!(round_trip(Settings::default()), [0]);
+}
+
+#[test]
+fn shadowed_std_items() {
+    let file = NetworkFileReader::from_source(
+        "struct Vec {\n    items: u8[];\n}\nstruct Ok {\n    maybe: option(Vec);\n}",
+    )
+    .unwrap();
+    let code = RustGenerator::new(&file).generate().unwrap();
+
+    assert!(code.contains("pub items: ::std::vec::Vec<u8>,"));
+    assert!(code.contains("pub maybe: Option<Vec>,"));
+    assert!(code.contains("::core::result::Result::Ok(Self { maybe: field0 })"));
+}
+
+#[test]
+fn errors() {
+    let file = NetworkFileReader::from_source("enum A {\n    B = 256;\n}").unwrap();
+    assert_eq!(
+        RustGenerator::new(&file).generate(),
+        Err(GenerateError::DiscriminatorOverflow {
+            enumeration: "A".to_owned(),
+            member: "B".to_owned(),
+            discriminator: 256,
+            type_name: "u8".to_owned(),
+        })
+    );
+
+    let file =
+        NetworkFileReader::from_source("enum A: u64 {\n    B = 18446744073709551615;\n    C;\n}")
+            .unwrap();
+    assert_eq!(
+        RustGenerator::new(&file).generate(),
+        Err(GenerateError::DiscriminatorOverflow {
+            enumeration: "A".to_owned(),
+            member: "C".to_owned(),
+            discriminator: 18446744073709551616,
+            type_name: "u64".to_owned(),
+        })
+    );
+
+    let file = NetworkFileReader::from_source("flags A {\n    B = 1;\n    C = 65;\n}").unwrap();
+    assert_eq!(
+        RustGenerator::new(&file)
+            .generate()
+            .unwrap_err()
+            .to_string(),
+        "the flag `C` occupies position 65, expected 1 till 64"
+    );
+
+    let file =
+        NetworkFileReader::from_source("option struct A {\n    b: u8 = 1;\n    c: u8 = 1;\n}")
+            .unwrap();
+    assert_eq!(
+        RustGenerator::new(&file)
+            .generate()
+            .unwrap_err()
+            .to_string(),
+        "the fields `b` and `c` both have the number 1"
+    );
+
+    let file = NetworkFileReader::from_source("struct A {}\nconst B: A = 1;").unwrap();
+    assert_eq!(
+        RustGenerator::new(&file)
+            .generate()
+            .unwrap_err()
+            .to_string(),
+        "the constant `B` of type `A` can not hold the value `1`"
+    );
+}
+
+#[test]
+fn builder() {
+    let out_dir = env::temp_dir().join(format!("netz-generator-{}", std::process::id()));
+    let path = Builder::new()
+        .with_out_dir(&out_dir)
+        .compile("tests/fixtures/forum.net")
+        .unwrap();
+
+    assert_eq!(path, out_dir.join("forum.rs"));
+    assert_eq!(
+        fs::read_to_string(&path).unwrap(),
+        generate("tests/fixtures/forum.net")
+    );
+
+    let error = Builder::new()
+        .with_out_dir(&out_dir)
+        .compile("../playground/net/imports/broken.net")
+        .unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+
+    fs::remove_dir_all(out_dir).unwrap();
+}
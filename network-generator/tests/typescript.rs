@@ -0,0 +1,211 @@
+use std::{collections::HashMap, env, fs, path::Path, process::Command};
+
+use network_generator::typescript::TypeScriptGenerator;
+use network_library::{decode::Decode, encode::Encode};
+use network_parser::parser::loader::NetworkLoader;
+
+/// The Rust module of `fixtures/forum.net`, which encodes the golden byte
+/// fixtures the TypeScript modules are tested against.
+#[allow(dead_code)]
+mod forum {
+    include!("fixtures/forum.rs");
+}
+
+use forum::*;
+
+const MODULES: &str = "tests/fixtures/typescript";
+const BYTES: &str = "tests/fixtures/forum.bytes.json";
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// Reads the byte fixtures, which map the name of a type to the encoded
+/// values of it in hex.
+fn byte_fixtures() -> HashMap<String, Vec<String>> {
+    serde_json::from_str(&fs::read_to_string(BYTES).unwrap()).unwrap()
+}
+
+/// Whether the program is installed, so the tests requiring it can run.
+fn installed(program: &str) -> bool {
+    Command::new(program)
+        .arg("--version")
+        .output()
+        .is_ok_and(|output| output.status.success())
+}
+
+fn user() -> User {
+    User {
+        name: Name("anatoly".to_owned()),
+        permission: Permission::READ | Permission::ADMIN,
+        status: Status::Away,
+        id: 7,
+        nickname: Some("ana".to_owned()),
+    }
+}
+
+fn post(content: Content) -> Post {
+    Post {
+        author: user(),
+        created: Timestamp(1700000000),
+        content: Box::new(content),
+        reactions: HashMap::from([("+1".to_owned(), -3)]),
+        deltas: vec![Some(-1), None, Some(i64::MAX)],
+        score: -8_000_000,
+        rating: 4.5,
+        pinned: true,
+    }
+}
+
+/// The encoded values of every type, which the byte fixtures hold.
+fn encoded_values() -> Vec<(&'static str, Vec<Vec<u8>>)> {
+    fn encode<T: Encode + Decode + PartialEq + std::fmt::Debug>(values: Vec<T>) -> Vec<Vec<u8>> {
+        values
+            .into_iter()
+            .map(|value| {
+                let bytes = value.to_bytes();
+                assert_eq!(T::from_bytes(&bytes).unwrap(), value);
+                bytes
+            })
+            .collect()
+    }
+
+    vec![
+        ("Timestamp", encode(vec![Timestamp(0), Timestamp(u64::MAX)])),
+        ("Hash", encode(vec![Hash(1), Hash(u64::MAX)])),
+        (
+            "Name",
+            encode(vec![Name("netz".to_owned()), Name("ñ".to_owned())]),
+        ),
+        (
+            "Permission",
+            encode(vec![
+                Permission::empty(),
+                Permission::READ | Permission::ADMIN,
+            ]),
+        ),
+        ("Status", encode(vec![Status::Online, Status::Offline])),
+        (
+            "Content",
+            encode(vec![
+                Content::Empty,
+                Content::Text("hi".to_owned()),
+                Content::Image(Hash(2), vec![1, 128, u32::MAX]),
+                Content::Reply(Box::new(post(Content::Empty))),
+            ]),
+        ),
+        ("User", encode(vec![user()])),
+        (
+            "Post",
+            encode(vec![post(Content::Text("first".to_owned()))]),
+        ),
+        (
+            "Settings",
+            encode(vec![
+                Settings::default(),
+                Settings {
+                    theme: Some("dark".to_owned()),
+                    volume: Some(300),
+                    permission: Some(Permission::MODERATION),
+                },
+            ]),
+        ),
+        ("Empty", encode(vec![Empty {}])),
+    ]
+}
+
+#[test]
+fn golden() {
+    let schema = NetworkLoader::new()
+        .load("tests/fixtures/forum.net")
+        .unwrap();
+    let modules = TypeScriptGenerator::new(&schema.namespace())
+        .generate()
+        .unwrap();
+
+    if env::var_os("NETZ_BLESS").is_some() {
+        fs::create_dir_all(MODULES).unwrap();
+
+        for (name, code) in modules.iter() {
+            fs::write(Path::new(MODULES).join(name), code).unwrap();
+        }
+    }
+
+    let mut files = fs::read_dir(MODULES)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect::<Vec<_>>();
+    let mut names = modules
+        .iter()
+        .map(|(name, _)| name.clone())
+        .collect::<Vec<_>>();
+
+    files.sort();
+    names.sort();
+    assert_eq!(files, names);
+
+    for (name, code) in modules {
+        let golden = fs::read_to_string(Path::new(MODULES).join(&name)).unwrap();
+        assert_eq!(code, golden, "the module {name} differs");
+    }
+}
+
+#[test]
+fn bytes() {
+    let encoded = encoded_values();
+
+    if env::var_os("NETZ_BLESS").is_some() {
+        let lines = encoded
+            .iter()
+            .map(|(name, values)| {
+                let values = values
+                    .iter()
+                    .map(|bytes| format!("\"{}\"", hex(bytes)))
+                    .collect::<Vec<_>>();
+                format!("    \"{name}\": [{}]", values.join(", "))
+            })
+            .collect::<Vec<_>>();
+
+        fs::write(BYTES, format!("{{\n{}\n}}\n", lines.join(",\n"))).unwrap();
+        return;
+    }
+
+    let fixtures = byte_fixtures();
+    assert_eq!(fixtures.len(), encoded.len());
+
+    for (name, values) in encoded {
+        let values = values.iter().map(|bytes| hex(bytes)).collect::<Vec<_>>();
+        assert_eq!(fixtures[name], values, "the fixtures of {name} differ");
+    }
+}
+
+/// Compiles the golden modules with `tsc` and decodes and encodes every
+/// byte fixture with them in `node`. Run with `--ignored`, it fails if
+/// either is not installed.
+#[test]
+#[ignore = "requires tsc and node"]
+fn round_trip() {
+    assert!(
+        installed("tsc") && installed("node"),
+        "the TypeScript round trip requires `tsc` and `node`"
+    );
+
+    let out_dir = env::temp_dir().join(format!("netz-typescript-{}", std::process::id()));
+    let tsc = Command::new("tsc")
+        .args(["--strict", "--target", "es2020", "--module", "commonjs"])
+        .args(["--rootDir", "tests", "--outDir"])
+        .arg(&out_dir)
+        .arg("tests/typescript/round_trip.ts")
+        .status()
+        .unwrap();
+    assert!(tsc.success());
+
+    let node = Command::new("node")
+        .arg(out_dir.join("typescript/round_trip.js"))
+        .arg(BYTES)
+        .status()
+        .unwrap();
+    assert!(node.success());
+
+    fs::remove_dir_all(out_dir).unwrap();
+}
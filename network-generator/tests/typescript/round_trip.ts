@@ -0,0 +1,39 @@
+// Decodes every byte fixture with the generated modules, encodes the
+// value again and compares the bytes. Run by the `round_trip` test of
+// `tests/typescript.rs` after compiling it with `tsc`.
+
+import { readFileSync } from "fs";
+import { NetworkFile } from "../fixtures/typescript/index";
+import * as forum from "../fixtures/typescript/index";
+
+type Codec = {
+    encode(value: unknown, writer: NetworkFile.Writer): void;
+    decode(reader: NetworkFile.Reader): unknown;
+};
+
+function hex(bytes: Uint8Array): string {
+    return Array.from(bytes, (byte) => byte.toString(16).padStart(2, "0")).join("");
+}
+
+function bytes(hex: string): Uint8Array {
+    return Uint8Array.from(hex.match(/../g) ?? [], (byte) => parseInt(byte, 16));
+}
+
+const fixtures: Record<string, string[]> = JSON.parse(readFileSync(process.argv[2], "utf8"));
+let failed = 0;
+
+for (const [name, values] of Object.entries(fixtures)) {
+    const codec = (forum as unknown as Record<string, Codec>)[name];
+
+    for (const value of values) {
+        const decoded = NetworkFile.fromBytes(codec.decode, bytes(value));
+        const encoded = hex(NetworkFile.toBytes(codec.encode, decoded));
+
+        if (encoded !== value) {
+            console.error(`${name}: expected ${value}, found ${encoded}`);
+            failed += 1;
+        }
+    }
+}
+
+process.exit(failed === 0 ? 0 : 1);
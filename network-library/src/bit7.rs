@@ -5,6 +5,28 @@
 //! read while the flag is set, and terminate with the last byte, which
 //! sets this flag to zero. The mantisse is accumulated to create the
 //! integer.
+//!
+//! The lowest seven bits are written first, so the encoding is the
+//! unsigned LEB128 format. The TypeScript class `Bit7Integer` in
+//! `templates/typescript/both.static.ts` writes the same bytes.
+//!
+//! ```text
+//! 624485 = 10 0110  000 1110  110 0101
+//!
+//! 1110 0101 1000 1110 0010 0110
+//! ^--- ---- ^--- ---- ^--- ----
+//!  110 0101  000 1110  010 0110
+//! ```
+//!
+//! Signed integers are mapped to unsigned integers with the 'zigzag'
+//! encoding first, which interleaves the negative and positive numbers,
+//! so integers close to zero take few bytes regardless of their' sign.
+//!
+//! ```text
+//!  0 -> 0   -1 -> 1   1 -> 2   -2 -> 3   2 -> 4   ...   i32::MIN -> u32::MAX
+//! ```
+
+use std::fmt::Display;
 
 /// This magic number is `0111 1111` in binary and `0x7F` in hexadecimal.
 /// Using the mathematical and operator 'and' on this number will give you
@@ -16,92 +38,473 @@ const LOWER7_MASK: u8 = 127;
 /// the 7-bit follow flag of a byte.
 const HIGH8_MASK: u8 = 128;
 
+/// An unsigned integer, which can be encoded in 7-bit encoding. It is
+/// implemented for `u8`, `u16`, `u32`, `u64`, `u128` and `usize`.
+pub trait Bit7Integer: Copy {
+    /// The amount of bits of the integer.
+    const BITS: u32;
+
+    /// The most bytes the 7-bit encoding of the integer takes.
+    const MAX_LENGTH: usize = Self::BITS.div_ceil(7) as usize;
+
+    /// Widens the integer without changing its' value.
+    fn to_u128(self) -> u128;
+
+    /// Narrows an integer, which is known to fit into `BITS` bits.
+    fn from_u128(value: u128) -> Self;
+}
+
+macro_rules! bit7_integer {
+    ($($integer:ty),*) => {
+        $(
+            impl Bit7Integer for $integer {
+                const BITS: u32 = <$integer>::BITS;
+
+                fn to_u128(self) -> u128 {
+                    self as u128
+                }
+
+                fn from_u128(value: u128) -> Self {
+                    value as $integer
+                }
+            }
+        )*
+    };
+}
+
+bit7_integer!(u8, u16, u32, u64, u128, usize);
+
+/// A signed integer, which can be encoded in 7-bit encoding after the
+/// zigzag encoding. It is implemented for `i8`, `i16`, `i32`, `i64`, `i128`
+/// and `isize`.
+pub trait Bit7Signed: Copy {
+    /// The unsigned integer of the same width.
+    type Unsigned: Bit7Integer;
+
+    /// Maps the integer to an unsigned integer, so `0, -1, 1, -2, 2, ...`
+    /// become `0, 1, 2, 3, 4, ...`.
+    fn zigzag(self) -> Self::Unsigned;
+
+    /// Reverses the zigzag encoding.
+    fn unzigzag(value: Self::Unsigned) -> Self;
+}
+
+macro_rules! bit7_signed {
+    ($($integer:ty => $unsigned:ty),*) => {
+        $(
+            impl Bit7Signed for $integer {
+                type Unsigned = $unsigned;
+
+                fn zigzag(self) -> $unsigned {
+                    ((self << 1) ^ (self >> (<$integer>::BITS - 1))) as $unsigned
+                }
+
+                fn unzigzag(value: $unsigned) -> Self {
+                    ((value >> 1) as $integer) ^ -((value & 1) as $integer)
+                }
+            }
+        )*
+    };
+}
+
+bit7_signed!(i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128, isize => usize);
+
+/// The reasons a 7-bit integer can not be decoded.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Bit7Error {
+    /// The encoded integer has more bits than the integer it is read into,
+    /// or it is encoded in more bytes than this integer can take.
+    Overflow { bits: u32 },
+}
+
+impl Display for Bit7Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Overflow { bits } => {
+                write!(f, "the 7-bit integer does not fit into {bits} bits")
+            }
+        }
+    }
+}
+
+impl std::error::Error for Bit7Error {}
+
 /// Reads the size of an integer in its' 7-bit encoded form. For example,
-/// the `u16`` integer stored in binary as `00 (00 0000 1) (111 1110)` would
-/// return 2, because it uses two bytes in 7-bit encoding: `1(000 0001) 0(111 1110)`
-/// 
+/// the `u16` integer stored in binary as `00 (00 0000 1) (111 1110)` would
+/// return 2, because it uses two bytes in 7-bit encoding: `1(111 1110) 0(000 0001)`
+///
 /// ```
 /// use network_library::bit7::length_7bit;
-/// 
+///
 /// // Value 0-127 expected to be stored in one byte
-/// assert_eq!(length_7bit(0), 1);
-/// assert_eq!(length_7bit(1), 1);
-/// assert_eq!(length_7bit(126), 1);
-/// assert_eq!(length_7bit(127), 1);
+/// assert_eq!(length_7bit(0u8), 1);
+/// assert_eq!(length_7bit(1u8), 1);
+/// assert_eq!(length_7bit(126u8), 1);
+/// assert_eq!(length_7bit(127u8), 1);
 /// // Values 2^7 till 2^14 - 1 expected to be stored in two bytes
-/// assert_eq!(length_7bit(128), 2);
-/// assert_eq!(length_7bit(16383), 2);
+/// assert_eq!(length_7bit(128u16), 2);
+/// assert_eq!(length_7bit(16383u16), 2);
 /// // Values 2^14 till 2^21 - 1 expected to be stored in three bytes
-/// assert_eq!(length_7bit(16384), 3);
-/// assert_eq!(length_7bit(2097151), 3);
+/// assert_eq!(length_7bit(16384u32), 3);
+/// assert_eq!(length_7bit(2097151u32), 3);
 /// // Values 2^21 till 2^28 - 1 expected to be stored in four bytes
-/// assert_eq!(length_7bit(2097152), 4);
-/// assert_eq!(length_7bit(268435455), 4);
+/// assert_eq!(length_7bit(2097152u32), 4);
+/// assert_eq!(length_7bit(268435455u32), 4);
 /// // Values above expected to be stored in five bytes
-/// assert_eq!(length_7bit(268435456), 5);
+/// assert_eq!(length_7bit(268435456u32), 5);
+/// assert_eq!(length_7bit(u128::MAX), 19);
 /// ```
-pub fn length_7bit<T : std::cmp::PartialEq<usize> + std::ops::ShrAssign<usize>>(mut value: T) -> usize {
-    let mut size = 0;
-
-    loop {
-        value >>= 7usize;
-        size += 1;
-
-        if value == 0 {
-            return size;
-        }
-    }
+pub fn length_7bit<T: Bit7Integer>(value: T) -> usize {
+    let bits = 128 - value.to_u128().leading_zeros() as usize;
+    bits.div_ceil(7).max(1)
 }
 
-/// TODO This function is broken, the order of shifting seems incorrect, this function needs more edge case tests.
-/// 
 /// Reads in bytes of an integer in 7-bit encoding and decodes
 /// the number from the generating bytes. The callback function
 /// for providing the bytes will be called while the highest bit
-/// is set.
+/// is set. The first byte holds the lowest seven bits.
 ///
 /// ```text
 /// 1111 0000 1010 1010 1000 0000 0000 0001 Reading In
 /// ^--- ---- ^--- ---- ^--- ---- ^--- ----
 ///  111 0000  010 1010  000 0000  000 0001 Writing Out
 /// ```
-/// 
+///
+/// Returns an error if the integer does not fit into `T`. No more bytes
+/// are read after the byte, which overflows.
+///
 /// ```
-/// use network_library::bit7::read_7bit;
-/// 
+/// use network_library::bit7::{read_7bit, Bit7Error};
+///
 /// // When the 7-bit follow flag is not set, returns the number.
-/// assert_eq!(read_7bit(&|| 0), 0);
-/// assert_eq!(read_7bit(&|| 1), 1);
-/// assert_eq!(read_7bit(&|| 127), 127);
+/// assert_eq!(read_7bit::<u8>(&|| 0), Ok(0));
+/// assert_eq!(read_7bit::<u8>(&|| 1), Ok(1));
+/// assert_eq!(read_7bit::<u8>(&|| 127), Ok(127));
+///
+/// // A follow flag, which is always set, overflows.
+/// assert_eq!(read_7bit::<u32>(&|| 0x80), Err(Bit7Error::Overflow { bits: 32 }));
 /// ```
-pub fn read_7bit(callback: &dyn Fn() -> u8) -> usize {
-    let mut value: usize = 0;
-    let mut shift: usize = 7;
-    const limit_bits: usize = std::mem::size_of::<usize>() * 7;
+pub fn read_7bit<T: Bit7Integer>(callback: &dyn Fn() -> u8) -> Result<T, Bit7Error> {
+    read_7bit_with(|| Ok(callback()))
+}
+
+/// Reads in bytes of an integer in 7-bit encoding from a source, which can
+/// fail. The errors of the source are passed on.
+pub(crate) fn read_7bit_with<T, E>(mut next: impl FnMut() -> Result<u8, E>) -> Result<T, E>
+where
+    T: Bit7Integer,
+    E: From<Bit7Error>,
+{
+    let mut value: u128 = 0;
+    let mut shift: u32 = 0;
 
     loop {
-        let byte = callback();
-        value |= (byte & LOWER7_MASK) as usize;
-        shift += 7;
+        let byte = next()?;
+        let mantisse = (byte & LOWER7_MASK) as u128;
 
-        if byte & HIGH8_MASK == 0 || shift > limit_bits {
-            return value;
+        // the mantisse must not have bits above the width of the integer
+        let overflow =
+            shift >= T::BITS || mantisse.checked_shr(T::BITS - shift).unwrap_or_default() != 0;
+
+        if overflow {
+            return Err(Bit7Error::Overflow { bits: T::BITS }.into());
         }
 
-        value <<= 7;
+        value |= mantisse << shift;
+
+        if byte & HIGH8_MASK == 0 {
+            return Ok(T::from_u128(value));
+        }
+
+        shift += 7;
     }
 }
 
-/// TODO add tests
-/// 
 /// Yield bytes encoding an integer into a 7-bit integer elsewhere.
 /// This function will stream data to an function that accepts one
-/// byte (`u8`) as parameter.
-pub fn write_7bit(mut value: usize, callback: &dyn Fn(u8) -> ()) -> () {
-    while value as u8 >= HIGH8_MASK {
-        callback((((value as u8) & LOWER7_MASK) | HIGH8_MASK) as u8);
+/// byte (`u8`) as parameter. The lowest seven bits are written first.
+///
+/// ```
+/// use network_library::bit7::write_7bit;
+///
+/// let bytes = std::cell::RefCell::new(vec![]);
+/// write_7bit(624485u32, &|byte| bytes.borrow_mut().push(byte));
+///
+/// assert_eq!(bytes.into_inner(), vec![0xE5, 0x8E, 0x26]);
+/// ```
+pub fn write_7bit<T: Bit7Integer>(value: T, callback: &dyn Fn(u8)) {
+    write_7bit_with(value, callback);
+}
+
+/// Yield bytes encoding an integer into a 7-bit integer to a function,
+/// which can hold state.
+pub(crate) fn write_7bit_with<T: Bit7Integer>(value: T, mut callback: impl FnMut(u8)) {
+    let mut value = value.to_u128();
+
+    while value >= HIGH8_MASK as u128 {
+        callback((value as u8 & LOWER7_MASK) | HIGH8_MASK);
         value >>= 7;
     }
+
     callback(value as u8);
 }
 
+/// Reads in bytes of a signed integer in zigzag and 7-bit encoding. The
+/// bytes are read like [read_7bit] and the errors are the same, so an
+/// `i16` overflows where an `u16` overflows.
+///
+/// ```
+/// use network_library::bit7::read_7bit_signed;
+///
+/// assert_eq!(read_7bit_signed::<i8>(&|| 0), Ok(0));
+/// assert_eq!(read_7bit_signed::<i8>(&|| 1), Ok(-1));
+/// assert_eq!(read_7bit_signed::<i8>(&|| 2), Ok(1));
+/// ```
+pub fn read_7bit_signed<T: Bit7Signed>(callback: &dyn Fn() -> u8) -> Result<T, Bit7Error> {
+    read_7bit(callback).map(T::unzigzag)
+}
+
+/// Yield bytes encoding a signed integer in zigzag and 7-bit encoding.
+///
+/// ```
+/// use network_library::bit7::write_7bit_signed;
+///
+/// let bytes = std::cell::RefCell::new(vec![]);
+/// write_7bit_signed(-65i32, &|byte| bytes.borrow_mut().push(byte));
+///
+/// assert_eq!(bytes.into_inner(), vec![0x81, 0x01]);
+/// ```
+pub fn write_7bit_signed<T: Bit7Signed>(value: T, callback: &dyn Fn(u8)) {
+    write_7bit(value.zigzag(), callback);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::{Cell, RefCell};
+
+    use super::*;
+
+    fn encode<T: Bit7Integer>(value: T) -> Vec<u8> {
+        let bytes = RefCell::new(vec![]);
+        write_7bit(value, &|byte| bytes.borrow_mut().push(byte));
+        bytes.into_inner()
+    }
+
+    /// Decodes the bytes and returns the amount of bytes, which were read.
+    fn decode<T: Bit7Integer>(bytes: &[u8]) -> (Result<T, Bit7Error>, usize) {
+        let index = Cell::new(0);
+        let value = read_7bit(&|| {
+            index.set(index.get() + 1);
+            bytes[index.get() - 1]
+        });
+
+        (value, index.get())
+    }
+
+    /// Encodes and decodes the value and checks the encoded length.
+    fn round_trip<T: Bit7Integer + PartialEq + std::fmt::Debug>(value: T) {
+        let bytes = encode(value);
+        assert_eq!(bytes.len(), length_7bit(value), "length of {value:?}");
+        assert!(bytes.len() <= T::MAX_LENGTH);
+        assert_eq!(
+            decode::<T>(&bytes),
+            (Ok(value), bytes.len()),
+            "bytes {bytes:x?}"
+        );
+    }
+
+    /// A xorshift generator, so the random values are the same in every
+    /// test run.
+    fn random_values(count: usize) -> impl Iterator<Item = u128> {
+        let mut state: u64 = 0x2545_F491_4F6C_DD1D;
+
+        (0..count).map(move |_| {
+            let mut next = || {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                state
+            };
+
+            // spread the values over all widths
+            let value = (next() as u128) << 64 | next() as u128;
+            value >> (next() % 128)
+        })
+    }
+
+    /// Known encodings, which are the same in the TypeScript class
+    /// `Bit7Integer`.
+    #[test]
+    fn known_encodings() {
+        assert_eq!(encode(0u8), vec![0x00]);
+        assert_eq!(encode(127u8), vec![0x7F]);
+        assert_eq!(encode(128u8), vec![0x80, 0x01]);
+        assert_eq!(encode(255u8), vec![0xFF, 0x01]);
+        assert_eq!(encode(300u16), vec![0xAC, 0x02]);
+        assert_eq!(encode(16384u32), vec![0x80, 0x80, 0x01]);
+        assert_eq!(encode(624485u32), vec![0xE5, 0x8E, 0x26]);
+        assert_eq!(encode(u32::MAX), vec![0xFF, 0xFF, 0xFF, 0xFF, 0x0F]);
+        // the largest safe integer in TypeScript
+        assert_eq!(
+            encode(2u64.pow(53) - 1),
+            vec![0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x0F]
+        );
+        assert_eq!(
+            encode(u64::MAX),
+            vec![0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x01]
+        );
+        assert_eq!(encode(2u128.pow(127)).last(), Some(&0x02));
+    }
+
+    #[test]
+    fn round_trip_all_small_integers() {
+        (0..=u8::MAX).for_each(round_trip);
+        (0..=u16::MAX).for_each(round_trip);
+    }
+
+    /// Every power of two and its' predecessor, which are the values where
+    /// the encoded length changes.
+    #[test]
+    fn round_trip_boundaries() {
+        for bits in 0..128 {
+            let value = 1u128 << bits;
+
+            for value in [value - 1, value] {
+                round_trip(value);
+                if let Ok(value) = u64::try_from(value) {
+                    round_trip(value);
+                    round_trip(value as usize);
+                }
+                if let Ok(value) = u32::try_from(value) {
+                    round_trip(value);
+                }
+            }
+        }
+
+        round_trip(u128::MAX);
+    }
+
+    #[test]
+    fn round_trip_random_integers() {
+        for value in random_values(10_000) {
+            round_trip(value);
+            round_trip(value as u64);
+            round_trip(value as u32);
+            round_trip(value as u16);
+        }
+    }
+
+    /// Integers, which are too wide for the type, are an error and not
+    /// truncated.
+    #[test]
+    fn overflow() {
+        fn overflow<T>(bits: u32) -> Result<T, Bit7Error> {
+            Err(Bit7Error::Overflow { bits })
+        }
+
+        assert_eq!(decode::<u8>(&encode(256u16)), (overflow(8), 2));
+        assert_eq!(decode::<u16>(&encode(u32::MAX)), (overflow(16), 3));
+        assert_eq!(
+            decode::<u64>(&encode(u64::MAX as u128 + 1)),
+            (overflow(64), 10)
+        );
+        assert_eq!(decode::<u128>(&[0xFF; 19]), (overflow(128), 19));
+
+        // the last byte of a u32 holds four bits
+        assert_eq!(
+            decode::<u32>(&[0xFF, 0xFF, 0xFF, 0xFF, 0x1F]).0,
+            overflow(32)
+        );
+
+        // an integer padded beyond the width overflows, even if it is zero
+        assert_eq!(decode::<u8>(&[0x80, 0x80, 0x00]), (overflow(8), 3));
+    }
+
+    /// Values are zero extended, so a redundant zero byte is allowed while
+    /// it fits into the width.
+    #[test]
+    fn padded_encoding() {
+        assert_eq!(decode::<u8>(&[0x81, 0x00]), (Ok(1), 2));
+        assert_eq!(decode::<u32>(&[0x80, 0x80, 0x80, 0x80, 0x00]), (Ok(0), 5));
+    }
+
+    fn encode_signed<T: Bit7Signed>(value: T) -> Vec<u8> {
+        let bytes = RefCell::new(vec![]);
+        write_7bit_signed(value, &|byte| bytes.borrow_mut().push(byte));
+        bytes.into_inner()
+    }
+
+    fn round_trip_signed<T: Bit7Signed + PartialEq + std::fmt::Debug>(value: T) {
+        let bytes = encode_signed(value);
+        let index = Cell::new(0);
+        let decoded = read_7bit_signed::<T>(&|| {
+            index.set(index.get() + 1);
+            bytes[index.get() - 1]
+        });
+
+        assert_eq!(decoded, Ok(value), "bytes {bytes:x?}");
+        assert_eq!(index.get(), bytes.len());
+        assert!(bytes.len() <= T::Unsigned::MAX_LENGTH);
+    }
+
+    #[test]
+    fn zigzag() {
+        assert_eq!(0i32.zigzag(), 0);
+        assert_eq!((-1i32).zigzag(), 1);
+        assert_eq!(1i32.zigzag(), 2);
+        assert_eq!((-2i32).zigzag(), 3);
+        assert_eq!(i8::MAX.zigzag(), 254);
+        assert_eq!(i8::MIN.zigzag(), u8::MAX);
+        assert_eq!(i64::MIN.zigzag(), u64::MAX);
+        assert_eq!(i128::MAX.zigzag(), u128::MAX - 1);
+
+        assert_eq!(encode_signed(-64i16), vec![0x7F]);
+        assert_eq!(encode_signed(64i16), vec![0x80, 0x01]);
+        assert_eq!(encode_signed(i32::MIN), vec![0xFF, 0xFF, 0xFF, 0xFF, 0x0F]);
+    }
+
+    #[test]
+    fn round_trip_all_small_signed_integers() {
+        (i8::MIN..=i8::MAX).for_each(round_trip_signed);
+        (i16::MIN..=i16::MAX).for_each(round_trip_signed);
+    }
+
+    #[test]
+    fn round_trip_random_signed_integers() {
+        for value in random_values(10_000) {
+            for value in [value as i128, (value as i128).wrapping_neg()] {
+                round_trip_signed(value);
+                round_trip_signed(value as i64);
+                round_trip_signed(value as isize);
+                round_trip_signed(value as i32);
+                round_trip_signed(value as i16);
+            }
+        }
+
+        for value in [i128::MIN, i128::MAX, i64::MIN as i128, i64::MAX as i128] {
+            round_trip_signed(value);
+        }
+    }
+
+    /// Signed integers overflow like the unsigned integers of their' width.
+    #[test]
+    fn signed_overflow() {
+        let bytes = encode_signed(i16::MIN as i32 - 1);
+        let index = Cell::new(0);
+        let decoded = read_7bit_signed::<i16>(&|| {
+            index.set(index.get() + 1);
+            bytes[index.get() - 1]
+        });
+
+        assert_eq!(decoded, Err(Bit7Error::Overflow { bits: 16 }));
+    }
+
+    #[test]
+    fn max_length() {
+        assert_eq!(u8::MAX_LENGTH, 2);
+        assert_eq!(u16::MAX_LENGTH, 3);
+        assert_eq!(u32::MAX_LENGTH, 5);
+        assert_eq!(u64::MAX_LENGTH, 10);
+        assert_eq!(u128::MAX_LENGTH, 19);
+    }
+}
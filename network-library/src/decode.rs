@@ -0,0 +1,489 @@
+//! Class to decode values from their' wire representation, the reverse of
+//! [crate::encode]. Every type, which can be received, implements [Decode]
+//! and reads itself from a [Reader], which is implemented for every
+//! `std::io::Read`, so also for byte slices. Unlike the callbacks of
+//! [crate::bit7], a reader reports the end of the input as an error.
+//!
+//! ```
+//! use network_library::decode::{Decode, DecodeError};
+//!
+//! let value = <(Option<u16>, String)>::from_bytes(&[1, 0xAC, 0x02, 2, b'h', b'i']);
+//! assert_eq!(value.unwrap(), (Some(300), String::from("hi")));
+//!
+//! let value = String::from_bytes(&[2, b'h']);
+//! assert!(matches!(value, Err(DecodeError::UnexpectedEof)));
+//! ```
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Display,
+    hash::{BuildHasher, Hash},
+    io,
+    str::Utf8Error,
+};
+
+use crate::{
+    bit7::{read_7bit_with, Bit7Error, Bit7Integer, Bit7Signed},
+    encode::Fixed,
+};
+
+/// The most bytes, which are reserved in advance for a length read from
+/// the input. Longer values grow while they are read, so a forged length
+/// can not reserve more memory than the input has.
+const PREALLOCATE_LIMIT: usize = 4096;
+
+/// The reasons a value can not be decoded.
+#[derive(Debug)]
+pub enum DecodeError {
+    /// The input ended before the value was read completely.
+    UnexpectedEof,
+    /// A 7-bit integer does not fit into the integer it is read into.
+    VarintOverflow { bits: u32 },
+    /// A discriminant, which does not name a variant of the type.
+    InvalidDiscriminant { name: &'static str, value: u128 },
+    /// A string, which is not valid UTF-8.
+    InvalidUtf8(Utf8Error),
+    /// The input has bytes left after the value.
+    TrailingBytes { count: usize },
+    /// The reader failed for another reason than the end of the input.
+    Io(io::Error),
+}
+
+impl Display for DecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnexpectedEof => write!(f, "the input ended inside of a value"),
+            Self::VarintOverflow { bits } => {
+                write!(f, "the 7-bit integer does not fit into {bits} bits")
+            }
+            Self::InvalidDiscriminant { name, value } => {
+                write!(f, "the discriminant {value} is not a variant of `{name}`")
+            }
+            Self::InvalidUtf8(error) => write!(f, "the string is not valid UTF-8: {error}"),
+            Self::TrailingBytes { count } => {
+                write!(f, "the input has {count} bytes left after the value")
+            }
+            Self::Io(error) => write!(f, "could not read the input: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InvalidUtf8(error) => Some(error),
+            Self::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<Bit7Error> for DecodeError {
+    fn from(error: Bit7Error) -> Self {
+        match error {
+            Bit7Error::Overflow { bits } => Self::VarintOverflow { bits },
+        }
+    }
+}
+
+impl From<io::Error> for DecodeError {
+    fn from(error: io::Error) -> Self {
+        match error.kind() {
+            io::ErrorKind::UnexpectedEof => Self::UnexpectedEof,
+            _ => Self::Io(error),
+        }
+    }
+}
+
+/// The source of encoded bytes.
+pub trait Reader {
+    /// Fills the buffer completely or fails.
+    fn read_bytes(&mut self, buffer: &mut [u8]) -> Result<(), DecodeError>;
+
+    /// Reads one byte.
+    fn read_byte(&mut self) -> Result<u8, DecodeError> {
+        let mut buffer = [0];
+        self.read_bytes(&mut buffer)?;
+        Ok(buffer[0])
+    }
+
+    /// Reads an unsigned integer in 7-bit encoding.
+    fn read_7bit<T: Bit7Integer>(&mut self) -> Result<T, DecodeError> {
+        read_7bit_with(|| self.read_byte())
+    }
+
+    /// Reads a length in 7-bit encoding, which is encoded like an `u64`.
+    fn read_length(&mut self) -> Result<usize, DecodeError> {
+        let length = self.read_7bit::<u64>()?;
+        usize::try_from(length).map_err(|_| DecodeError::VarintOverflow { bits: usize::BITS })
+    }
+}
+
+impl<R: io::Read + ?Sized> Reader for R {
+    fn read_bytes(&mut self, buffer: &mut [u8]) -> Result<(), DecodeError> {
+        Ok(self.read_exact(buffer)?)
+    }
+}
+
+/// Reads the amount of bytes in chunks, so the memory grows with the input.
+pub(crate) fn read_chunked<R: Reader + ?Sized>(
+    reader: &mut R,
+    length: usize,
+) -> Result<Vec<u8>, DecodeError> {
+    let mut bytes = Vec::with_capacity(length.min(PREALLOCATE_LIMIT));
+
+    while bytes.len() < length {
+        let start = bytes.len();
+        bytes.resize(length.min(start + PREALLOCATE_LIMIT), 0);
+        reader.read_bytes(&mut bytes[start..])?;
+    }
+
+    Ok(bytes)
+}
+
+/// A value, which can be decoded from bytes.
+///
+/// ```
+/// use network_library::decode::Decode;
+///
+/// let mut bytes: &[u8] = &[0x01, 2, 1, 0];
+///
+/// assert_eq!(i32::decode(&mut bytes).unwrap(), -1);
+/// assert_eq!(Vec::<bool>::decode(&mut bytes).unwrap(), vec![true, false]);
+/// assert!(bytes.is_empty());
+/// ```
+pub trait Decode: Sized {
+    /// Reads a value from the reader.
+    fn decode<R: Reader + ?Sized>(reader: &mut R) -> Result<Self, DecodeError>;
+
+    /// Decodes a value, which takes all of the bytes.
+    fn from_bytes(mut bytes: &[u8]) -> Result<Self, DecodeError> {
+        let value = Self::decode(&mut bytes)?;
+
+        match bytes.len() {
+            0 => Ok(value),
+            count => Err(DecodeError::TrailingBytes { count }),
+        }
+    }
+}
+
+impl Decode for u8 {
+    fn decode<R: Reader + ?Sized>(reader: &mut R) -> Result<Self, DecodeError> {
+        reader.read_byte()
+    }
+}
+
+impl Decode for i8 {
+    fn decode<R: Reader + ?Sized>(reader: &mut R) -> Result<Self, DecodeError> {
+        reader.read_byte().map(|byte| byte as i8)
+    }
+}
+
+macro_rules! decode_integer {
+    ($($unsigned:ty, $signed:ty);*) => {
+        $(
+            impl Decode for $unsigned {
+                fn decode<R: Reader + ?Sized>(reader: &mut R) -> Result<Self, DecodeError> {
+                    reader.read_7bit()
+                }
+            }
+
+            impl Decode for $signed {
+                fn decode<R: Reader + ?Sized>(reader: &mut R) -> Result<Self, DecodeError> {
+                    reader.read_7bit().map(<$signed>::unzigzag)
+                }
+            }
+
+            impl Decode for Fixed<$unsigned> {
+                fn decode<R: Reader + ?Sized>(reader: &mut R) -> Result<Self, DecodeError> {
+                    let mut bytes = [0; size_of::<$unsigned>()];
+                    reader.read_bytes(&mut bytes)?;
+                    Ok(Fixed(<$unsigned>::from_le_bytes(bytes)))
+                }
+            }
+
+            impl Decode for Fixed<$signed> {
+                fn decode<R: Reader + ?Sized>(reader: &mut R) -> Result<Self, DecodeError> {
+                    let mut bytes = [0; size_of::<$signed>()];
+                    reader.read_bytes(&mut bytes)?;
+                    Ok(Fixed(<$signed>::from_le_bytes(bytes)))
+                }
+            }
+        )*
+    };
+}
+
+decode_integer!(u16, i16; u32, i32; u64, i64; u128, i128);
+
+/// The size types are decoded like the 64 bit integers and overflow, if
+/// the value does not fit into the size of the platform.
+impl Decode for usize {
+    fn decode<R: Reader + ?Sized>(reader: &mut R) -> Result<Self, DecodeError> {
+        reader.read_length()
+    }
+}
+
+impl Decode for isize {
+    fn decode<R: Reader + ?Sized>(reader: &mut R) -> Result<Self, DecodeError> {
+        let value = i64::unzigzag(reader.read_7bit()?);
+        isize::try_from(value).map_err(|_| DecodeError::VarintOverflow { bits: isize::BITS })
+    }
+}
+
+impl Decode for f32 {
+    fn decode<R: Reader + ?Sized>(reader: &mut R) -> Result<Self, DecodeError> {
+        let mut bytes = [0; 4];
+        reader.read_bytes(&mut bytes)?;
+        Ok(f32::from_le_bytes(bytes))
+    }
+}
+
+impl Decode for f64 {
+    fn decode<R: Reader + ?Sized>(reader: &mut R) -> Result<Self, DecodeError> {
+        let mut bytes = [0; 8];
+        reader.read_bytes(&mut bytes)?;
+        Ok(f64::from_le_bytes(bytes))
+    }
+}
+
+/// Every non-zero byte is decoded as `true`, as the standard library
+/// declares `bool` as `u8`.
+impl Decode for bool {
+    fn decode<R: Reader + ?Sized>(reader: &mut R) -> Result<Self, DecodeError> {
+        reader.read_byte().map(|byte| byte != 0)
+    }
+}
+
+impl Decode for () {
+    fn decode<R: Reader + ?Sized>(_: &mut R) -> Result<Self, DecodeError> {
+        Ok(())
+    }
+}
+
+impl Decode for String {
+    fn decode<R: Reader + ?Sized>(reader: &mut R) -> Result<Self, DecodeError> {
+        let length = reader.read_length()?;
+        let bytes = read_chunked(reader, length)?;
+
+        String::from_utf8(bytes).map_err(|error| DecodeError::InvalidUtf8(error.utf8_error()))
+    }
+}
+
+impl<T: Decode> Decode for Vec<T> {
+    fn decode<R: Reader + ?Sized>(reader: &mut R) -> Result<Self, DecodeError> {
+        let length = reader.read_length()?;
+        let mut elements = Vec::with_capacity(length.min(PREALLOCATE_LIMIT));
+
+        for _ in 0..length {
+            elements.push(T::decode(reader)?);
+        }
+
+        Ok(elements)
+    }
+}
+
+impl<T: Decode> Decode for Option<T> {
+    fn decode<R: Reader + ?Sized>(reader: &mut R) -> Result<Self, DecodeError> {
+        match reader.read_byte()? {
+            0 => Ok(None),
+            1 => T::decode(reader).map(Some),
+            value => Err(DecodeError::InvalidDiscriminant {
+                name: "Option",
+                value: value as u128,
+            }),
+        }
+    }
+}
+
+/// A key, which appears twice, keeps the last value.
+impl<K, V, S> Decode for HashMap<K, V, S>
+where
+    K: Decode + Eq + Hash,
+    V: Decode,
+    S: BuildHasher + Default,
+{
+    fn decode<R: Reader + ?Sized>(reader: &mut R) -> Result<Self, DecodeError> {
+        let length = reader.read_length()?;
+        let mut map =
+            HashMap::with_capacity_and_hasher(length.min(PREALLOCATE_LIMIT), S::default());
+
+        for _ in 0..length {
+            map.insert(K::decode(reader)?, V::decode(reader)?);
+        }
+
+        Ok(map)
+    }
+}
+
+/// A key, which appears twice, keeps the last value.
+impl<K: Decode + Ord, V: Decode> Decode for BTreeMap<K, V> {
+    fn decode<R: Reader + ?Sized>(reader: &mut R) -> Result<Self, DecodeError> {
+        let length = reader.read_length()?;
+        let mut map = BTreeMap::new();
+
+        for _ in 0..length {
+            map.insert(K::decode(reader)?, V::decode(reader)?);
+        }
+
+        Ok(map)
+    }
+}
+
+impl<T: Decode> Decode for Box<T> {
+    fn decode<R: Reader + ?Sized>(reader: &mut R) -> Result<Self, DecodeError> {
+        T::decode(reader).map(Box::new)
+    }
+}
+
+macro_rules! decode_tuple {
+    ($($name:ident),*) => {
+        impl<$($name: Decode),*> Decode for ($($name,)*) {
+            fn decode<R: Reader + ?Sized>(reader: &mut R) -> Result<Self, DecodeError> {
+                Ok(($($name::decode(reader)?,)*))
+            }
+        }
+    };
+}
+
+decode_tuple!(A);
+decode_tuple!(A, B);
+decode_tuple!(A, B, C);
+decode_tuple!(A, B, C, D);
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::encode::Encode;
+
+    fn round_trip<T: Encode + Decode + PartialEq + std::fmt::Debug>(value: T) {
+        let bytes = value.to_bytes();
+        assert_eq!(T::from_bytes(&bytes).unwrap(), value, "bytes {bytes:x?}");
+    }
+
+    #[test]
+    fn round_trip_primitives() {
+        for value in [0, 1, 127, 128, u64::MAX] {
+            round_trip(value);
+            round_trip(Fixed(value));
+        }
+
+        for value in [0, -1, 63, -64, i32::MIN, i32::MAX] {
+            round_trip(value);
+            round_trip(Fixed(value));
+        }
+
+        round_trip((u8::MAX, i8::MIN, u128::MAX, i128::MIN));
+        round_trip((usize::MAX, isize::MIN, true, ()));
+        round_trip((1.5f32, f64::NEG_INFINITY));
+    }
+
+    #[test]
+    fn round_trip_containers() {
+        round_trip(String::from("grüße"));
+        round_trip(vec![Some(vec![1u16, 300]), None, Some(vec![])]);
+        round_trip(Box::new(Some(String::new())));
+        round_trip(BTreeMap::from([
+            (1i64, "a".to_string()),
+            (-1, "b".to_string()),
+        ]));
+        round_trip(HashMap::from([("a".to_string(), vec![true])]));
+    }
+
+    #[test]
+    fn read_from_io() {
+        let mut cursor = Cursor::new(vec![0xAC, 0x02, 0xFF]);
+
+        assert_eq!(u16::decode(&mut cursor).unwrap(), 300);
+        assert_eq!(u8::decode(&mut cursor).unwrap(), 0xFF);
+        assert!(matches!(
+            u8::decode(&mut cursor),
+            Err(DecodeError::UnexpectedEof)
+        ));
+    }
+
+    #[test]
+    fn unexpected_eof() {
+        assert!(matches!(
+            u32::from_bytes(&[]),
+            Err(DecodeError::UnexpectedEof)
+        ));
+        assert!(matches!(
+            u32::from_bytes(&[0x80]),
+            Err(DecodeError::UnexpectedEof)
+        ));
+        assert!(matches!(
+            Fixed::<u32>::from_bytes(&[0; 3]),
+            Err(DecodeError::UnexpectedEof)
+        ));
+        assert!(matches!(
+            Vec::<u8>::from_bytes(&[2, 0]),
+            Err(DecodeError::UnexpectedEof)
+        ));
+        assert!(matches!(
+            Option::<u8>::from_bytes(&[1]),
+            Err(DecodeError::UnexpectedEof)
+        ));
+    }
+
+    /// A forged length fails at the end of the input, without reserving the
+    /// memory for the whole length.
+    #[test]
+    fn forged_length() {
+        let bytes = u64::MAX.to_bytes();
+
+        if usize::BITS == 64 {
+            assert!(matches!(
+                String::from_bytes(&bytes),
+                Err(DecodeError::UnexpectedEof)
+            ));
+            assert!(matches!(
+                Vec::<u64>::from_bytes(&bytes),
+                Err(DecodeError::UnexpectedEof)
+            ));
+        }
+    }
+
+    #[test]
+    fn varint_overflow() {
+        assert!(matches!(
+            u16::from_bytes(&[0xFF, 0xFF, 0x04]),
+            Err(DecodeError::VarintOverflow { bits: 16 })
+        ));
+        assert!(matches!(
+            i16::from_bytes(&[0x80, 0x80, 0x80, 0x00]),
+            Err(DecodeError::VarintOverflow { bits: 16 })
+        ));
+    }
+
+    #[test]
+    fn invalid_discriminant() {
+        assert!(matches!(
+            Option::<u8>::from_bytes(&[2, 0]),
+            Err(DecodeError::InvalidDiscriminant {
+                name: "Option",
+                value: 2
+            })
+        ));
+    }
+
+    #[test]
+    fn invalid_utf8() {
+        let error = String::from_bytes(&[2, 0xC3, 0x28]).unwrap_err();
+
+        assert!(matches!(error, DecodeError::InvalidUtf8(_)));
+        assert_eq!(
+            error.to_string(),
+            "the string is not valid UTF-8: invalid utf-8 sequence of 1 bytes from index 0"
+        );
+    }
+
+    #[test]
+    fn trailing_bytes() {
+        assert!(matches!(
+            u8::from_bytes(&[1, 2, 3]),
+            Err(DecodeError::TrailingBytes { count: 2 })
+        ));
+    }
+}
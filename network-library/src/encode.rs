@@ -0,0 +1,304 @@
+//! Class to encode values into their' wire representation. Every type,
+//! which can be sent, implements [Encode] and writes itself into a
+//! [Writer], which is implemented for every `std::io::Write`, so also for
+//! `Vec<u8>` and mutable byte slices. Types are encoded as follows:
+//!
+//! - `u8`, `i8` and `bool` take one byte.
+//! - Wider integers are 7-bit integers, signed integers in zigzag encoding,
+//!   see [crate::bit7]. [Fixed] integers are written in fixed width and
+//!   little endian order instead.
+//! - `f32` and `f64` are written in little endian order, see [crate::float].
+//! - Strings, vectors and maps start with their' length as 7-bit integer,
+//!   followed by the bytes, the elements or the keys and values.
+//! - Options start with the byte 0 for `None` and 1 for `Some`, which is
+//!   followed by the value.
+//!
+//! ```
+//! use network_library::encode::Encode;
+//!
+//! let bytes = (Some(300u16), String::from("hi")).to_bytes();
+//! assert_eq!(bytes, vec![1, 0xAC, 0x02, 2, b'h', b'i']);
+//! ```
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    io,
+};
+
+use crate::bit7::{write_7bit_with, Bit7Integer, Bit7Signed};
+
+/// The destination of encoded bytes.
+pub trait Writer {
+    /// Writes all of the bytes or fails.
+    fn write_bytes(&mut self, bytes: &[u8]) -> io::Result<()>;
+
+    /// Writes one byte.
+    fn write_byte(&mut self, byte: u8) -> io::Result<()> {
+        self.write_bytes(&[byte])
+    }
+
+    /// Writes an unsigned integer in 7-bit encoding.
+    fn write_7bit<T: Bit7Integer>(&mut self, value: T) -> io::Result<()> {
+        let mut buffer = [0; u128::MAX_LENGTH];
+        let mut length = 0;
+
+        write_7bit_with(value, |byte| {
+            buffer[length] = byte;
+            length += 1;
+        });
+
+        self.write_bytes(&buffer[..length])
+    }
+}
+
+impl<W: io::Write + ?Sized> Writer for W {
+    fn write_bytes(&mut self, bytes: &[u8]) -> io::Result<()> {
+        self.write_all(bytes)
+    }
+}
+
+/// A value, which can be encoded into bytes.
+///
+/// ```
+/// use network_library::encode::Encode;
+///
+/// let mut bytes = vec![];
+/// (-1i32).encode(&mut bytes).unwrap();
+/// vec![true, false].encode(&mut bytes).unwrap();
+///
+/// assert_eq!(bytes, vec![0x01, 2, 1, 0]);
+/// ```
+pub trait Encode {
+    /// Writes the value into the writer. Only fails, if the writer fails.
+    fn encode<W: Writer + ?Sized>(&self, writer: &mut W) -> io::Result<()>;
+
+    /// Encodes the value into a new byte vector.
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![];
+        self.encode(&mut bytes)
+            .expect("writing into a vector does not fail");
+        bytes
+    }
+}
+
+/// An integer, which is encoded in fixed width and little endian order
+/// instead of 7-bit encoding. This is the type of fields tagged `@fixed`.
+///
+/// ```
+/// use network_library::encode::{Encode, Fixed};
+///
+/// assert_eq!(1u32.to_bytes(), vec![1]);
+/// assert_eq!(Fixed(1u32).to_bytes(), vec![1, 0, 0, 0]);
+/// ```
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, Default)]
+pub struct Fixed<T>(pub T);
+
+impl Encode for u8 {
+    fn encode<W: Writer + ?Sized>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_byte(*self)
+    }
+}
+
+impl Encode for i8 {
+    fn encode<W: Writer + ?Sized>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_byte(*self as u8)
+    }
+}
+
+macro_rules! encode_integer {
+    ($($unsigned:ty, $signed:ty);*) => {
+        $(
+            impl Encode for $unsigned {
+                fn encode<W: Writer + ?Sized>(&self, writer: &mut W) -> io::Result<()> {
+                    writer.write_7bit(*self)
+                }
+            }
+
+            impl Encode for $signed {
+                fn encode<W: Writer + ?Sized>(&self, writer: &mut W) -> io::Result<()> {
+                    writer.write_7bit(self.zigzag())
+                }
+            }
+
+            impl Encode for Fixed<$unsigned> {
+                fn encode<W: Writer + ?Sized>(&self, writer: &mut W) -> io::Result<()> {
+                    writer.write_bytes(&self.0.to_le_bytes())
+                }
+            }
+
+            impl Encode for Fixed<$signed> {
+                fn encode<W: Writer + ?Sized>(&self, writer: &mut W) -> io::Result<()> {
+                    writer.write_bytes(&self.0.to_le_bytes())
+                }
+            }
+        )*
+    };
+}
+
+encode_integer!(u16, i16; u32, i32; u64, i64; u128, i128);
+
+/// The size types are encoded like the 64 bit integers, so their' encoding
+/// does not depend on the platform.
+impl Encode for usize {
+    fn encode<W: Writer + ?Sized>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_7bit(*self as u64)
+    }
+}
+
+impl Encode for isize {
+    fn encode<W: Writer + ?Sized>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_7bit((*self as i64).zigzag())
+    }
+}
+
+impl Encode for f32 {
+    fn encode<W: Writer + ?Sized>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_bytes(&self.to_le_bytes())
+    }
+}
+
+impl Encode for f64 {
+    fn encode<W: Writer + ?Sized>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_bytes(&self.to_le_bytes())
+    }
+}
+
+impl Encode for bool {
+    fn encode<W: Writer + ?Sized>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_byte(*self as u8)
+    }
+}
+
+/// The unit type `()` does not carry any data.
+impl Encode for () {
+    fn encode<W: Writer + ?Sized>(&self, _: &mut W) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Encode for str {
+    fn encode<W: Writer + ?Sized>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_7bit(self.len() as u64)?;
+        writer.write_bytes(self.as_bytes())
+    }
+}
+
+impl Encode for String {
+    fn encode<W: Writer + ?Sized>(&self, writer: &mut W) -> io::Result<()> {
+        self.as_str().encode(writer)
+    }
+}
+
+impl<T: Encode> Encode for [T] {
+    fn encode<W: Writer + ?Sized>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_7bit(self.len() as u64)?;
+        self.iter().try_for_each(|element| element.encode(writer))
+    }
+}
+
+impl<T: Encode> Encode for Vec<T> {
+    fn encode<W: Writer + ?Sized>(&self, writer: &mut W) -> io::Result<()> {
+        self.as_slice().encode(writer)
+    }
+}
+
+impl<T: Encode> Encode for Option<T> {
+    fn encode<W: Writer + ?Sized>(&self, writer: &mut W) -> io::Result<()> {
+        match self {
+            None => writer.write_byte(0),
+            Some(value) => {
+                writer.write_byte(1)?;
+                value.encode(writer)
+            }
+        }
+    }
+}
+
+impl<K: Encode, V: Encode, S> Encode for HashMap<K, V, S> {
+    fn encode<W: Writer + ?Sized>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_7bit(self.len() as u64)?;
+        self.iter().try_for_each(|(key, value)| {
+            key.encode(writer)?;
+            value.encode(writer)
+        })
+    }
+}
+
+impl<K: Encode, V: Encode> Encode for BTreeMap<K, V> {
+    fn encode<W: Writer + ?Sized>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_7bit(self.len() as u64)?;
+        self.iter().try_for_each(|(key, value)| {
+            key.encode(writer)?;
+            value.encode(writer)
+        })
+    }
+}
+
+impl<T: Encode + ?Sized> Encode for &T {
+    fn encode<W: Writer + ?Sized>(&self, writer: &mut W) -> io::Result<()> {
+        (**self).encode(writer)
+    }
+}
+
+impl<T: Encode + ?Sized> Encode for Box<T> {
+    fn encode<W: Writer + ?Sized>(&self, writer: &mut W) -> io::Result<()> {
+        (**self).encode(writer)
+    }
+}
+
+macro_rules! encode_tuple {
+    ($($name:ident: $index:tt),*) => {
+        /// Tuples are encoded as their' elements in order, like the
+        /// payload of an enum member.
+        impl<$($name: Encode),*> Encode for ($($name,)*) {
+            fn encode<W: Writer + ?Sized>(&self, writer: &mut W) -> io::Result<()> {
+                $(self.$index.encode(writer)?;)*
+                Ok(())
+            }
+        }
+    };
+}
+
+encode_tuple!(A: 0);
+encode_tuple!(A: 0, B: 1);
+encode_tuple!(A: 0, B: 1, C: 2);
+encode_tuple!(A: 0, B: 1, C: 2, D: 3);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integers() {
+        assert_eq!(200u8.to_bytes(), vec![200]);
+        assert_eq!((-1i8).to_bytes(), vec![0xFF]);
+        assert_eq!(624485u32.to_bytes(), vec![0xE5, 0x8E, 0x26]);
+        assert_eq!((-65i64).to_bytes(), vec![0x81, 0x01]);
+        assert_eq!(u128::MAX.to_bytes().len(), 19);
+        assert_eq!(usize::MAX.to_bytes(), u64::MAX.to_bytes());
+        assert_eq!(Fixed(-2i16).to_bytes(), vec![0xFE, 0xFF]);
+    }
+
+    #[test]
+    fn containers() {
+        assert_eq!("hi".to_bytes(), vec![2, b'h', b'i']);
+        assert_eq!(Vec::<u16>::new().to_bytes(), vec![0]);
+        assert_eq!(vec![vec![1u8], vec![]].to_bytes(), vec![2, 1, 1, 0]);
+        assert_eq!(None::<u32>.to_bytes(), vec![0]);
+        assert_eq!(Some(()).to_bytes(), vec![1]);
+
+        let map = BTreeMap::from([(2u8, "b"), (1, "a")]);
+        assert_eq!(map.to_bytes(), vec![2, 1, 1, b'a', 2, 1, b'b']);
+    }
+
+    /// Writing into a full slice fails, but writes the bytes which fit.
+    #[test]
+    fn write_into_slice() {
+        let mut buffer = [0; 2];
+        let mut slice = &mut buffer[..];
+
+        let error = "abc".encode(&mut slice).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::WriteZero);
+        assert_eq!(buffer, [3, b'a']);
+    }
+}
@@ -0,0 +1,206 @@
+//! Class to store a set of flags at runtime. A flags declaration in a
+//! network file names up to 64 bits, the set of flags is encoded into
+//! the smallest unsigned integer that can hold the highest declared bit,
+//! i.e. one, two, four or eight bytes in little endian order.
+//!
+//! Undeclared bits are kept. A peer with a newer network file may send
+//! flags, which are not declared here, so they are decoded, stay in the
+//! raw bits and are encoded again, like in the generated flags.
+
+/// A set of flags with a known amount of declared bits. Bits are adressed
+/// by their zero based index, so the flag declared as `Read = 1;` is bit 0.
+///
+/// ```
+/// use network_library::flags::FlagSet;
+///
+/// let mut permission = FlagSet::new(4);
+/// permission.set(0);
+/// permission.set(3);
+///
+/// assert!(permission.contains(0));
+/// assert!(!permission.contains(1));
+/// assert_eq!(permission.to_le_bytes(), vec![0b1001]);
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct FlagSet {
+    bits: u64,
+    size: u8,
+}
+
+impl FlagSet {
+    /// Create an empty set of flags, which can hold `size` flags. This
+    /// function will panic if more than 64 flags are requested.
+    pub fn new(size: u8) -> Self {
+        assert!(
+            size <= 64,
+            "a flag set can hold at most 64 flags, got {size}"
+        );
+        Self { bits: 0, size }
+    }
+
+    /// Create a set of flags from the raw bits, which keeps undeclared
+    /// bits. Returns `None` if a bit does not fit into the width of the
+    /// set.
+    pub fn from_bits(size: u8, bits: u64) -> Option<Self> {
+        let flags = Self::new(size);
+
+        if bits & !flags.width_mask() != 0 {
+            return None;
+        }
+
+        Some(Self { bits, size })
+    }
+
+    /// The raw bits of the set.
+    pub fn bits(&self) -> u64 {
+        self.bits
+    }
+
+    /// The amount of declared flags.
+    pub fn size(&self) -> u8 {
+        self.size
+    }
+
+    /// The mask of all bits that fit into the width of the set.
+    fn width_mask(&self) -> u64 {
+        match self.width() {
+            8 => u64::MAX,
+            width => (1 << (width * 8)) - 1,
+        }
+    }
+
+    /// Checks if the flag is set.
+    pub fn contains(&self, bit: u8) -> bool {
+        bit < self.size && self.bits & (1 << bit) != 0
+    }
+
+    /// Sets the flag. This function will panic if the bit is out of range.
+    pub fn set(&mut self, bit: u8) {
+        assert!(
+            bit < self.size,
+            "bit {bit} is out of range for {} flags",
+            self.size
+        );
+        self.bits |= 1 << bit;
+    }
+
+    /// Clears the flag. This function will panic if the bit is out of range.
+    pub fn unset(&mut self, bit: u8) {
+        assert!(
+            bit < self.size,
+            "bit {bit} is out of range for {} flags",
+            self.size
+        );
+        self.bits &= !(1 << bit);
+    }
+
+    /// Checks if no flag is set.
+    pub fn is_empty(&self) -> bool {
+        self.bits == 0
+    }
+
+    /// The amount of bytes of the smallest unsigned integer that can hold
+    /// all declared flags.
+    ///
+    /// ```
+    /// use network_library::flags::FlagSet;
+    ///
+    /// assert_eq!(FlagSet::new(0).width(), 1);
+    /// assert_eq!(FlagSet::new(8).width(), 1);
+    /// assert_eq!(FlagSet::new(9).width(), 2);
+    /// assert_eq!(FlagSet::new(17).width(), 4);
+    /// assert_eq!(FlagSet::new(33).width(), 8);
+    /// ```
+    pub fn width(&self) -> usize {
+        match self.size {
+            0..=8 => 1,
+            9..=16 => 2,
+            17..=32 => 4,
+            _ => 8,
+        }
+    }
+
+    /// Encodes the flags into the smallest fitting integer in little
+    /// endian byte order.
+    ///
+    /// ```
+    /// use network_library::flags::FlagSet;
+    ///
+    /// let mut flags = FlagSet::new(12);
+    /// flags.set(9);
+    /// assert_eq!(flags.to_le_bytes(), vec![0, 2]);
+    /// ```
+    pub fn to_le_bytes(&self) -> Vec<u8> {
+        self.bits.to_le_bytes()[..self.width()].to_vec()
+    }
+
+    /// Decodes the flags from the smallest fitting integer in little
+    /// endian byte order, which keeps undeclared bits. Returns `None` if
+    /// the amount of bytes does not match the width.
+    ///
+    /// ```
+    /// use network_library::flags::FlagSet;
+    ///
+    /// let flags = FlagSet::from_le_bytes(12, &[0, 2]).unwrap();
+    /// assert!(flags.contains(9));
+    ///
+    /// // The width of 12 flags is two bytes.
+    /// assert_eq!(FlagSet::from_le_bytes(12, &[0]), None);
+    /// // Bit 12 is not declared, but kept.
+    /// let flags = FlagSet::from_le_bytes(12, &[0, 16]).unwrap();
+    /// assert!(!flags.contains(12));
+    /// assert_eq!(flags.to_le_bytes(), vec![0, 16]);
+    /// ```
+    pub fn from_le_bytes(size: u8, bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != Self::new(size).width() {
+            return None;
+        }
+
+        let mut buffer = [0; 8];
+        buffer[..bytes.len()].copy_from_slice(bytes);
+        Self::from_bits(size, u64::from_le_bytes(buffer))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        for size in [1, 8, 9, 16, 17, 32, 33, 64] {
+            let mut flags = FlagSet::new(size);
+            flags.set(0);
+            flags.set(size - 1);
+
+            let bytes = flags.to_le_bytes();
+            assert_eq!(bytes.len(), flags.width());
+            assert_eq!(FlagSet::from_le_bytes(size, &bytes), Some(flags));
+        }
+    }
+
+    #[test]
+    fn set_and_unset() {
+        let mut flags = FlagSet::new(64);
+        assert!(flags.is_empty());
+        flags.set(63);
+        assert!(flags.contains(63));
+        flags.unset(63);
+        assert!(flags.is_empty());
+    }
+
+    #[test]
+    fn undeclared_bits() {
+        assert_eq!(FlagSet::from_bits(3, 0b1000).unwrap().bits(), 0b1000);
+        assert_eq!(FlagSet::from_bits(3, 0x100), None);
+        assert_eq!(FlagSet::from_bits(9, 0xffff).unwrap().bits(), 0xffff);
+        assert_eq!(FlagSet::from_bits(64, u64::MAX).unwrap().bits(), u64::MAX);
+        assert!(!FlagSet::new(3).contains(5));
+    }
+
+    #[test]
+    #[should_panic]
+    fn out_of_range() {
+        FlagSet::new(3).set(3);
+    }
+}
@@ -0,0 +1,147 @@
+//! Class to perform floating point decoding and encoding. The primitives
+//! `f32` and `f64` are encoded as IEEE 754 binary32 and binary64 numbers
+//! in little endian order, so they take four and eight bytes.
+//!
+//! ```text
+//! 0000 0000 0000 0000 1100 0000 0011 1111  f32 1.5
+//! ^-------- ^-------- ^-------- ^--------
+//! mantisse  mantisse  exponent  sign and exponent
+//! ```
+//!
+//! The bits are written as they are, so every value round-trips exactly:
+//!
+//! - Negative zero keeps its' sign, although it compares equal to zero.
+//! - NaN keeps its' sign and payload bits. As NaN is not equal to itself,
+//!   decoded values should be compared with `to_bits`.
+//! - Infinities are written like any other value.
+
+/// Reads in the four bytes of an `f32` in little endian order. The callback
+/// function for providing the bytes will be called four times.
+///
+/// ```
+/// use network_library::float::read_f32;
+///
+/// let bytes = [0x00, 0x00, 0xC0, 0x3F];
+/// let index = std::cell::Cell::new(0);
+///
+/// let value = read_f32(&|| {
+///     index.set(index.get() + 1);
+///     bytes[index.get() - 1]
+/// });
+///
+/// assert_eq!(value, 1.5);
+/// ```
+pub fn read_f32(callback: &dyn Fn() -> u8) -> f32 {
+    let mut bytes = [0; 4];
+    bytes.iter_mut().for_each(|byte| *byte = callback());
+    f32::from_le_bytes(bytes)
+}
+
+/// Reads in the eight bytes of an `f64` in little endian order. The
+/// callback function for providing the bytes will be called eight times.
+pub fn read_f64(callback: &dyn Fn() -> u8) -> f64 {
+    let mut bytes = [0; 8];
+    bytes.iter_mut().for_each(|byte| *byte = callback());
+    f64::from_le_bytes(bytes)
+}
+
+/// Yield the four bytes of an `f32` in little endian order. This function
+/// will stream data to an function that accepts one byte (`u8`) as
+/// parameter.
+///
+/// ```
+/// use network_library::float::write_f32;
+///
+/// let bytes = std::cell::RefCell::new(vec![]);
+/// write_f32(-0.0, &|byte| bytes.borrow_mut().push(byte));
+///
+/// assert_eq!(bytes.into_inner(), vec![0x00, 0x00, 0x00, 0x80]);
+/// ```
+pub fn write_f32(value: f32, callback: &dyn Fn(u8)) {
+    value.to_le_bytes().into_iter().for_each(callback);
+}
+
+/// Yield the eight bytes of an `f64` in little endian order. This function
+/// will stream data to an function that accepts one byte (`u8`) as
+/// parameter.
+pub fn write_f64(value: f64, callback: &dyn Fn(u8)) {
+    value.to_le_bytes().into_iter().for_each(callback);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::{Cell, RefCell};
+
+    use super::*;
+
+    fn encode_f32(value: f32) -> Vec<u8> {
+        let bytes = RefCell::new(vec![]);
+        write_f32(value, &|byte| bytes.borrow_mut().push(byte));
+        bytes.into_inner()
+    }
+
+    fn encode_f64(value: f64) -> Vec<u8> {
+        let bytes = RefCell::new(vec![]);
+        write_f64(value, &|byte| bytes.borrow_mut().push(byte));
+        bytes.into_inner()
+    }
+
+    /// A callback, which yields the bytes in order.
+    fn reader(bytes: &[u8]) -> impl Fn() -> u8 + '_ {
+        let index = Cell::new(0);
+
+        move || {
+            index.set(index.get() + 1);
+            bytes[index.get() - 1]
+        }
+    }
+
+    #[test]
+    fn little_endian() {
+        assert_eq!(encode_f32(1.5), vec![0x00, 0x00, 0xC0, 0x3F]);
+        assert_eq!(
+            encode_f64(1.5),
+            vec![0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xF8, 0x3F]
+        );
+        assert_eq!(read_f64(&reader(&encode_f64(-2.25))), -2.25);
+    }
+
+    #[test]
+    fn round_trip() {
+        for value in [0.0, 1.0, -1.0, f32::MIN_POSITIVE, f32::MAX, f32::MIN, f32::EPSILON] {
+            assert_eq!(read_f32(&reader(&encode_f32(value))), value);
+        }
+
+        for value in [0.1, f64::MAX, f64::MIN, 5e-324, f64::INFINITY, f64::NEG_INFINITY] {
+            assert_eq!(read_f64(&reader(&encode_f64(value))), value);
+        }
+    }
+
+    /// Negative zero equals zero, but keeps its' sign bit.
+    #[test]
+    fn negative_zero() {
+        assert_eq!(encode_f32(-0.0), vec![0x00, 0x00, 0x00, 0x80]);
+
+        let value = read_f32(&reader(&encode_f32(-0.0)));
+        assert!(value == 0.0 && value.is_sign_negative());
+
+        let value = read_f64(&reader(&encode_f64(-0.0)));
+        assert_eq!(value.to_bits(), (-0.0f64).to_bits());
+    }
+
+    /// NaN is not equal to itself, its' bits round-trip unchanged.
+    #[test]
+    fn not_a_number() {
+        let quiet = f32::NAN;
+        let negative = f64::from_bits(0xFFF8_0000_0000_0001);
+
+        let value = read_f32(&reader(&encode_f32(quiet)));
+        assert!(value.is_nan());
+        assert_ne!(value, quiet);
+        assert_eq!(value.to_bits(), quiet.to_bits());
+
+        let value = read_f64(&reader(&encode_f64(negative)));
+        assert!(value.is_nan() && value.is_sign_negative());
+        assert_eq!(value.to_bits(), 0xFFF8_0000_0000_0001);
+    }
+}
@@ -1,4 +1,9 @@
 pub mod bit7;
+pub mod decode;
+pub mod encode;
+pub mod flags;
+pub mod float;
+pub mod message;
 
 pub fn add(left: usize, right: usize) -> usize {
     left + right
@@ -0,0 +1,317 @@
+//! Class to encode and decode messages, the structures declared with
+//! `option struct`. Every field of a message is optional and identified
+//! by its' number, so a message only carries the fields which are present.
+//!
+//! A present field is written as its' number in one byte, followed by the
+//! length of the value as `u32` in little endian order and the encoded
+//! value itself. The message ends with the number 0, the end marker.
+//!
+//! ```text
+//! 0000 0001  0000 0010 0000 0000 0000 0000 0000 0000  0110 1000 0110 1001  0000 0000
+//! ^--------  ^--------------------------------------  ^------------------  ^--------
+//! number 1   length 2                                 value "hi"           end marker
+//! ```
+//!
+//! As every value is preceded by its' length, a reader can skip the fields
+//! with numbers it does not know. Old readers can so decode the messages
+//! written with a newer schema, which added fields.
+
+use std::fmt::Display;
+
+use crate::decode::{read_chunked, DecodeError, Reader};
+
+/// The number, which marks the end of a message. Fields are numbered
+/// starting with 1.
+pub const END_MARKER: u8 = 0;
+
+/// The amount of bytes the length of a field value takes.
+const LENGTH_BYTES: usize = 4;
+
+/// The reasons a message can not be decoded.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum MessageError {
+    /// The input ended before the end marker was read.
+    MissingEndMarker,
+    /// The input ended inside of the field with this number.
+    UnexpectedEnd { number: u8 },
+}
+
+impl Display for MessageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingEndMarker => write!(f, "the message ended without the end marker"),
+            Self::UnexpectedEnd { number } => {
+                write!(f, "the message ended inside of the field {number}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for MessageError {}
+
+/// Writes the present fields of a message into a byte buffer.
+///
+/// ```
+/// use network_library::message::MessageWriter;
+///
+/// let mut message = MessageWriter::new();
+/// message.write_field(1, b"hi");
+///
+/// assert_eq!(message.finish(), vec![1, 2, 0, 0, 0, b'h', b'i', 0]);
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct MessageWriter {
+    bytes: Vec<u8>,
+}
+
+impl MessageWriter {
+    /// Create a writer for an empty message.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Writes a present field with its' encoded value. This function will
+    /// panic if the number is the end marker or the value is longer than
+    /// `u32::MAX` bytes.
+    pub fn write_field(&mut self, number: u8, value: &[u8]) {
+        assert!(
+            number != END_MARKER,
+            "the field number {END_MARKER} is reserved for the end marker"
+        );
+        let length = u32::try_from(value.len()).expect("a field value is at most u32::MAX bytes");
+
+        self.bytes.push(number);
+        self.bytes.extend_from_slice(&length.to_le_bytes());
+        self.bytes.extend_from_slice(value);
+    }
+
+    /// Writes the end marker and returns the encoded message.
+    pub fn finish(mut self) -> Vec<u8> {
+        self.bytes.push(END_MARKER);
+        self.bytes
+    }
+}
+
+/// A field read from a message, with the encoded value.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct MessageField<'a> {
+    number: u8,
+    value: &'a [u8],
+}
+
+impl<'a> MessageField<'a> {
+    /// The number of the field, as declared in the network file.
+    pub fn number(&self) -> u8 {
+        self.number
+    }
+
+    /// The encoded value of the field.
+    pub fn value(&self) -> &'a [u8] {
+        self.value
+    }
+}
+
+/// Reads the present fields of a message in the order they were written,
+/// until the end marker. A reader matches the numbers it knows and skips
+/// the other fields.
+///
+/// ```
+/// use network_library::message::{MessageReader, MessageWriter};
+///
+/// let mut message = MessageWriter::new();
+/// message.write_field(1, b"hi");
+/// message.write_field(7, &[42]);
+/// let bytes = message.finish();
+///
+/// let mut reader = MessageReader::new(&bytes);
+/// let mut content = None;
+///
+/// for field in reader.by_ref() {
+///     match field.unwrap() {
+///         field if field.number() == 1 => content = Some(field.value()),
+///         // the field 7 was added by a newer schema
+///         _ => continue,
+///     }
+/// }
+///
+/// assert_eq!(content, Some(&b"hi"[..]));
+/// assert!(reader.remainder().is_empty());
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct MessageReader<'a> {
+    bytes: &'a [u8],
+    finished: bool,
+}
+
+impl<'a> MessageReader<'a> {
+    /// Create a reader for the message at the beginning of the bytes.
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self {
+            bytes,
+            finished: false,
+        }
+    }
+
+    /// The bytes after the message, once the end marker was read, or the
+    /// bytes which were not read yet.
+    pub fn remainder(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// Reads the next field, `None` if the end marker was read.
+    fn read_field(&mut self) -> Result<Option<MessageField<'a>>, MessageError> {
+        let (&number, rest) = self
+            .bytes
+            .split_first()
+            .ok_or(MessageError::MissingEndMarker)?;
+
+        if number == END_MARKER {
+            self.bytes = rest;
+            return Ok(None);
+        }
+
+        if rest.len() < LENGTH_BYTES {
+            return Err(MessageError::UnexpectedEnd { number });
+        }
+
+        let (length, rest) = rest.split_at(LENGTH_BYTES);
+        let length = u32::from_le_bytes(length.try_into().unwrap()) as usize;
+
+        if rest.len() < length {
+            return Err(MessageError::UnexpectedEnd { number });
+        }
+
+        let (value, rest) = rest.split_at(length);
+        self.bytes = rest;
+
+        Ok(Some(MessageField { number, value }))
+    }
+}
+
+impl<'a> Iterator for MessageReader<'a> {
+    type Item = Result<MessageField<'a>, MessageError>;
+
+    /// Reads the next field. After the end marker or an error, no more
+    /// fields are read.
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        let field = self.read_field();
+        self.finished = !matches!(field, Ok(Some(_)));
+        field.transpose()
+    }
+}
+
+/// Reads the next field of a message from a reader, with the number and
+/// the encoded value. Returns `None` once the end marker was read. Unlike
+/// the [MessageReader], the message does not have to be in memory.
+///
+/// ```
+/// use network_library::message::{read_field, MessageWriter};
+///
+/// let mut message = MessageWriter::new();
+/// message.write_field(1, b"hi");
+/// let bytes = message.finish();
+///
+/// let mut reader = &bytes[..];
+/// assert_eq!(read_field(&mut reader).unwrap(), Some((1, b"hi".to_vec())));
+/// assert_eq!(read_field(&mut reader).unwrap(), None);
+/// ```
+pub fn read_field<R: Reader + ?Sized>(
+    reader: &mut R,
+) -> Result<Option<(u8, Vec<u8>)>, DecodeError> {
+    let number = reader.read_byte()?;
+
+    if number == END_MARKER {
+        return Ok(None);
+    }
+
+    let mut length = [0; LENGTH_BYTES];
+    reader.read_bytes(&mut length)?;
+    let value = read_chunked(reader, u32::from_le_bytes(length) as usize)?;
+
+    Ok(Some((number, value)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read_all(bytes: &[u8]) -> Result<Vec<(u8, Vec<u8>)>, MessageError> {
+        MessageReader::new(bytes)
+            .map(|field| field.map(|field| (field.number(), field.value().to_vec())))
+            .collect()
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut message = MessageWriter::new();
+        message.write_field(2, &[1, 2, 3]);
+        message.write_field(1, &[]);
+        message.write_field(255, &[9; 300]);
+        let bytes = message.finish();
+
+        assert_eq!(
+            read_all(&bytes),
+            Ok(vec![(2, vec![1, 2, 3]), (1, vec![]), (255, vec![9; 300])])
+        );
+    }
+
+    #[test]
+    fn empty_message() {
+        assert_eq!(MessageWriter::new().finish(), vec![END_MARKER]);
+        assert_eq!(read_all(&[END_MARKER]), Ok(vec![]));
+    }
+
+    /// The reader stops at the end marker, so messages can be followed
+    /// by other values.
+    #[test]
+    fn remainder() {
+        let mut bytes = MessageWriter::new().finish();
+        bytes.extend_from_slice(&[7, 7]);
+
+        let mut reader = MessageReader::new(&bytes);
+        assert_eq!(reader.next(), None);
+        assert_eq!(reader.next(), None);
+        assert_eq!(reader.remainder(), &[7, 7]);
+    }
+
+    #[test]
+    fn truncated_message() {
+        assert_eq!(read_all(&[]), Err(MessageError::MissingEndMarker));
+        assert_eq!(
+            read_all(&[1, 2, 0]),
+            Err(MessageError::UnexpectedEnd { number: 1 })
+        );
+        assert_eq!(
+            read_all(&[3, 2, 0, 0, 0, 1]),
+            Err(MessageError::UnexpectedEnd { number: 3 })
+        );
+        assert_eq!(
+            read_all(&[1, 0, 0, 0, 0]),
+            Err(MessageError::MissingEndMarker)
+        );
+    }
+
+    #[test]
+    fn read_fields_from_reader() {
+        let mut message = MessageWriter::new();
+        message.write_field(4, &[1, 2]);
+        let bytes = message.finish();
+
+        let mut reader = &bytes[..];
+        assert_eq!(read_field(&mut reader).unwrap(), Some((4, vec![1, 2])));
+        assert_eq!(read_field(&mut reader).unwrap(), None);
+
+        let mut reader = &bytes[..4];
+        assert!(matches!(read_field(&mut reader), Err(DecodeError::UnexpectedEof)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn reserved_number() {
+        MessageWriter::new().write_field(END_MARKER, &[]);
+    }
+}
@@ -12,10 +12,12 @@ use nom::{
 };
 use util_cases::CaseStyles;
 
-///
+/// An identifier stored in its' split case representation.
+#[allow(dead_code)] // not used by the parser yet
 #[derive(Clone)]
 pub struct Identifier(Vec<String>);
 
+#[allow(dead_code)]
 impl Identifier {
     /// Create a new, empty Identifier
     pub fn new() -> Self {
@@ -24,7 +26,7 @@ impl Identifier {
 
     /// Parse a new identifier from input.
     pub fn parse(input: &str) -> nom::IResult<&str, Self> {
-        Ok(Self::new().parse(input)?)
+        Self::new().parse(input)
     }
 }
 
@@ -15,6 +15,7 @@ use nom::{
 /// trailing whitespace, returning the output of `inner`.
 ///
 /// https://github.com/rust-bakery/nom/blob/main/doc/nom_recipes.md#whitespace
+#[allow(dead_code)] // not used by the parser yet
 fn whitespace0(input: &str) -> IResult<&str, ()> {
     value((), multispace0).parse(input)
 }
@@ -23,6 +24,7 @@ fn whitespace0(input: &str) -> IResult<&str, ()> {
 /// trailing whitespace, returning the output of `inner`.
 ///
 /// https://github.com/rust-bakery/nom/blob/main/doc/nom_recipes.md#whitespace
+#[allow(dead_code)] // not used by the parser yet
 pub fn whitespace1(input: &str) -> IResult<&str, ()> {
     value((), multispace1).parse(input)
 }
@@ -31,6 +33,7 @@ pub fn whitespace1(input: &str) -> IResult<&str, ()> {
 /// `//` and reads till a line-breaking character was discovered.
 ///
 /// https://github.com/rust-bakery/nom/blob/main/doc/nom_recipes.md#comments
+#[allow(dead_code)] // not used by the parser yet
 pub fn c_comment(input: &str) -> IResult<&str, ()> {
     value(
         (),
@@ -46,17 +49,19 @@ pub fn c_comment(input: &str) -> IResult<&str, ()> {
 /// comments.
 ///
 /// https://github.com/rust-bakery/nom/blob/main/doc/nom_recipes.md#comments
+#[allow(dead_code)] // not used by the parser yet
 pub fn c_multiline_comment(input: &str) -> IResult<&str, ()> {
     value((), delimited(tag("/*"), take_until("*/"), tag("*/"))).parse(input)
 }
 
 /// Reads in as many ignored characters as possible and terminates if the
 /// input no longer advances.
+#[allow(dead_code)] // not used by the parser yet
 pub fn read_ignored(mut input: &str) -> IResult<&str, bool> {
     let tmp_input = input;
 
     loop {
-        let (tmp_input, output) = alt((c_comment, c_multiline_comment, whitespace0))(input)?;
+        let (tmp_input, _output) = alt((c_comment, c_multiline_comment, whitespace0))(input)?;
 
         // To avoid running into an infinite loop, if we did not
         // advance, break, if continuing, update the input variable.
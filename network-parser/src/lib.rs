@@ -1,5 +1,5 @@
 mod combinator;
-// mod parser;
+pub mod parser;
 
 // mod network;
 
@@ -0,0 +1,177 @@
+use std::slice::Iter;
+
+use nom::{combinator::cut, multi::many0};
+
+use super::{
+    comment::Comment,
+    diagnostic::SourceSpan,
+    documentation::Documentation,
+    error::{in_context, keyword, symbol, ParseResult, Span},
+    expression::TypeExpression,
+    identifier::NetworkIdentifier,
+    interface::NetworkParser,
+    tag::Tag,
+};
+
+/// Definition for a `type` alias in a network file. An alias starts
+/// with the keyword 'type', followed by an identifier declaring the
+/// aliases' name, followed by the aliased type after an equal sign. The
+/// alias is a custom type of its' own for the parser, but encodes exactly
+/// like the aliased type.
+///
+/// #### Example
+///
+/// ```net
+/// @sanitize
+/// type Content = string;
+///
+/// type string = u8[];
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+pub struct NetworkType {
+    identity: String,
+    span: SourceSpan,
+    aliased: TypeExpression,
+    documentation: Option<Documentation>,
+    tags: Vec<Tag>,
+}
+
+impl NetworkType {
+    pub fn name(&self) -> &str {
+        self.identity.as_str()
+    }
+
+    /// The location of the name in the network file.
+    pub fn span(&self) -> SourceSpan {
+        self.span
+    }
+
+    /// The type expression on the right hand side of the alias.
+    pub fn aliased(&self) -> &TypeExpression {
+        &self.aliased
+    }
+
+    pub fn documentation(&self) -> Option<&Documentation> {
+        self.documentation.as_ref()
+    }
+
+    pub fn tags(&self) -> Iter<'_, Tag> {
+        self.tags.iter()
+    }
+
+    /// Reads the aliased type after the equal sign and the closing
+    /// semicolon.
+    fn definition(input: Span) -> ParseResult<TypeExpression> {
+        // expect '=' symbol
+        let (input, _) = Comment::parse(input)?;
+        let (input, _) = symbol("=")(input)?;
+
+        // expect the aliased type
+        let (input, aliased) = TypeExpression::parse(input)?;
+
+        // read the semicolon
+        let (input, _) = Comment::parse(input)?;
+        let (input, _) = symbol(";")(input)?;
+
+        ParseResult::Ok((input, aliased))
+    }
+}
+
+impl NetworkParser for NetworkType {
+    fn parse(input: Span) -> ParseResult<Self> {
+        let (input, comment) = Comment::parse(input)?;
+
+        // read optionally several tags, including those in the documentation
+        let (input, tags) = many0(Tag::parse)(input)?;
+        let tags = [comment.read_tags(), tags].concat();
+
+        // read the 'type' keyword
+        let (input, _) = Comment::parse(input)?;
+        let (input, _) = keyword("type")(input)?;
+
+        // expect alias name
+        let (input, alias_name) = cut(NetworkIdentifier::parse)(input)?;
+        let context = format!("type `{}`", alias_name.identity);
+
+        // expect the aliased type after an equal sign
+        let (input, aliased) = in_context(context, cut(Self::definition))(input)?;
+
+        Ok((
+            input,
+            Self {
+                identity: alias_name.identity,
+                span: alias_name.span,
+                aliased,
+                documentation: comment.read_documentation(),
+                tags,
+            },
+        ))
+    }
+}
+
+#[cfg(test)]
+mod alias_test {
+    use super::*;
+    use crate::parser::tag::TagArgument;
+
+    #[test]
+    fn simple_alias() {
+        let (input, alias) = NetworkType::parse_str("type Name = string;").unwrap();
+        assert_eq!(*input.fragment(), "");
+        assert_eq!(alias.name(), "Name");
+        assert_eq!(alias.aliased().name(), Some("string"));
+        assert_eq!(alias.aliased().array_dimension(), 0);
+    }
+
+    #[test]
+    fn array_alias() {
+        let (input, alias) = NetworkType::parse_str("type string = u8[];").unwrap();
+        assert_eq!(*input.fragment(), "");
+        assert_eq!(alias.aliased().element().name(), Some("u8"));
+        assert_eq!(alias.aliased().array_dimension(), 1);
+    }
+
+    /// Tests an alias with documentation and tags.
+    #[test]
+    fn annotated_alias() {
+        let (input, alias) =
+            NetworkType::parse_str("/// Some content.\n@sanitize type Content = string;").unwrap();
+        assert_eq!(*input.fragment(), "");
+        assert_eq!(
+            alias.documentation().map(Documentation::description),
+            Some("Some content.")
+        );
+        assert_eq!(alias.tags().next().unwrap().name(), "sanitize");
+    }
+
+    /// Tags can be written inside of the documentation, as in
+    /// `playground/net/forum.net`. They precede tags written in front
+    /// of the declaration.
+    #[test]
+    fn documentation_tags() {
+        let (input, alias) = NetworkType::parse_str(
+            "/**\n * @!limit(70B)\n * @!regex(\"[a-z]+@example\\.com\")\n */\n@trim type EMail = string;",
+        )
+        .unwrap();
+        assert_eq!(*input.fragment(), "");
+
+        let tags = alias.tags().collect::<Vec<_>>();
+        assert_eq!(tags.len(), 3);
+        assert_eq!(tags[0].name(), "limit");
+        assert!(tags[0].is_required());
+        assert_eq!(tags[0].arguments(), &[TagArgument::ByteSize(70)]);
+        assert_eq!(
+            tags[1].arguments(),
+            &[TagArgument::String("[a-z]+@example\\.com".to_owned())]
+        );
+        assert_eq!(tags[2].name(), "trim");
+        assert!(!tags[2].is_required());
+    }
+
+    #[test]
+    fn incomplete_alias() {
+        assert!(NetworkType::parse_str("type Name;").is_err());
+        assert!(NetworkType::parse_str("type = string;").is_err());
+        assert!(NetworkType::parse_str("type Name = string").is_err());
+    }
+}
@@ -1,14 +1,17 @@
 use nom::{
     branch::alt,
-    bytes::complete::{is_a, is_not, tag, take, take_until, take_while},
-    character::complete::{anychar, line_ending, multispace1},
+    bytes::complete::{is_a, is_not, take_until},
+    character::complete::multispace1,
     combinator::{eof, opt},
-    multi::{many0, many_m_n, many_till},
-    sequence::{delimited, pair},
-    AsChar, IResult, Parser,
+    sequence::delimited,
 };
 
-use super::interface::NetworkParser;
+use super::{
+    documentation::Documentation,
+    error::{symbol, ParseResult, Span},
+    interface::NetworkParser,
+    tag::Tag,
+};
 
 /// A network comment is a comment
 #[derive(Debug, PartialEq)]
@@ -17,39 +20,64 @@ pub enum Comment {
     Singleline(String),
     Multiline(String),
     DocSingleline(String),
+    InnerDocSingleline(String),
 }
 
 impl Comment {
-    fn read_comment(&self) -> Option<String> {
+    /// Read the text content of a comment without the comment markers.
+    /// Lines are kept, leading asterisks of multiline comments are removed.
+    /// Whitespace does not carry any content and returns `None`.
+    pub fn read_comment(&self) -> Option<String> {
         match self {
             Comment::Whitespace => None,
-            Comment::Singleline(comm) => Some(comm.trim().to_owned()),
+            Comment::Singleline(comm) | Comment::InnerDocSingleline(comm) => {
+                Some(comm.trim().to_owned())
+            }
             Comment::Multiline(comm) => Some(
-                comm.to_owned()
-                    .lines()
+                comm.lines()
                     .map(|line| {
                         let line = line.trim();
-                        if line.starts_with("*") {
-                            line[1..].trim().to_owned()
-                        } else {
-                            line.to_owned()
-                        }
+                        line.strip_prefix('*').unwrap_or(line).trim()
                     })
-                    .collect(),
+                    .collect::<Vec<_>>()
+                    .join("\n")
+                    .trim()
+                    .to_owned(),
+            ),
+            Comment::DocSingleline(comm) => Some(
+                comm.lines()
+                    .map(|line| line.trim())
+                    .collect::<Vec<_>>()
+                    .join("\n")
+                    .trim()
+                    .to_owned(),
             ),
-            Comment::DocSingleline(comm) => {
-                Some(comm.to_owned().lines().map(|line| line.trim()).collect())
-            }
         }
     }
 
+    /// Read the structured documentation of the comment. Returns `None`
+    /// if the comment has no content apart from extension tags.
+    pub fn read_documentation(&self) -> Option<Documentation> {
+        self.read_comment()
+            .map(|comment| Documentation::new(&comment))
+            .filter(|documentation| !documentation.is_empty())
+    }
+
+    /// Read the tags written on their' own line inside of the comment,
+    /// see [Tag::from_documentation].
+    pub fn read_tags(&self) -> Vec<Tag> {
+        self.read_comment()
+            .map(|comment| Tag::from_documentation(&comment))
+            .unwrap_or_default()
+    }
+
     /// A combinator that takes a parser `inner` and produces a parser that also consumes both leading and
     /// trailing whitespace, returning the output of `inner`.
     ///
     /// https://github.com/rust-bakery/nom/blob/main/doc/nom_recipes.md#whitespace
-    fn whitespace(i: &str) -> IResult<&str, Comment> {
+    fn whitespace(i: Span) -> ParseResult<Comment> {
         let (input, _) = multispace1(i)?;
-        IResult::Ok((input, Self::Whitespace))
+        ParseResult::Ok((input, Self::Whitespace))
     }
 
     /// A combinator that takes a tag parser from the C-style comment starting with
@@ -58,9 +86,9 @@ impl Comment {
     /// This is not a documentation comment.
     ///
     /// https://github.com/rust-bakery/nom/blob/main/doc/nom_recipes.md#comments
-    pub fn c_comment(i: &str) -> IResult<&str, Comment> {
-        let (input, comm) = delimited(tag("//"), many0(anychar), alt((is_a("\n\r"), eof)))(i)?;
-        IResult::Ok((input, Self::Singleline(comm.into_iter().collect())))
+    pub fn c_comment(i: Span) -> ParseResult<Comment> {
+        let (input, comm) = delimited(symbol("//"), opt(is_not("\n\r")), alt((is_a("\n\r"), eof)))(i)?;
+        ParseResult::Ok((input, Self::Singleline(comm.map(|comm| comm.to_string()).unwrap_or_default())))
     }
 
     /// A combinator that takes a tag parser from the C-style documentation comment
@@ -69,9 +97,18 @@ impl Comment {
     /// This is a documentation comment.
     ///
     /// https://github.com/rust-bakery/nom/blob/main/doc/nom_recipes.md#comments
-    pub fn c_comment_docs(i: &str) -> IResult<&str, Comment> {
-        let (input, comm) = delimited(tag("///"), many0(anychar), alt((is_a("\n\r"), eof)))(i)?;
-        IResult::Ok((input, Self::DocSingleline(comm.into_iter().collect())))
+    pub fn c_comment_docs(i: Span) -> ParseResult<Comment> {
+        let (input, comm) = delimited(symbol("///"), opt(is_not("\n\r")), alt((is_a("\n\r"), eof)))(i)?;
+        ParseResult::Ok((input, Self::DocSingleline(comm.map(|comm| comm.to_string()).unwrap_or_default())))
+    }
+
+    /// A combinator that takes a tag parser from the inner documentation
+    /// comment starting with `//!` and reads till a line-breaking character
+    /// was discovered. Inner documentation comments document the network
+    /// file itself and are only read at the beginning of the file.
+    pub fn c_comment_inner_docs(i: Span) -> ParseResult<Comment> {
+        let (input, comm) = delimited(symbol("//!"), opt(is_not("\n\r")), alt((is_a("\n\r"), eof)))(i)?;
+        ParseResult::Ok((input, Self::InnerDocSingleline(comm.map(|comm| comm.to_string()).unwrap_or_default())))
     }
 
     /// A combinator that takes a delimited parser and returns the comments'
@@ -81,10 +118,10 @@ impl Comment {
     /// comments.
     ///
     /// https://github.com/rust-bakery/nom/blob/main/doc/nom_recipes.md#comments
-    pub fn c_multiline_comment(i: &str) -> IResult<&str, Comment> {
-        let (input, comm) = delimited(tag("/*"), take_until("*/"), tag("*/"))(i)?;
-        let comment = comm.lines().map(|l| l.trim()).collect();
-        IResult::Ok((input, Self::Multiline(comment)))
+    pub fn c_multiline_comment(i: Span) -> ParseResult<Comment> {
+        let (input, comm) = delimited(symbol("/*"), take_until("*/"), symbol("*/"))(i)?;
+        let comment = comm.lines().map(|l| l.trim()).collect::<Vec<_>>().join("\n");
+        ParseResult::Ok((input, Self::Multiline(comment)))
     }
 }
 
@@ -93,7 +130,7 @@ impl NetworkParser for Comment {
     /// can be used to identify as many "ignored" characters as possible, as
     /// this will also parse through comments and read the documentation from
     /// it.
-    fn parse(mut input: &str) -> IResult<&str, Self> {
+    fn parse(mut input: Span) -> ParseResult<Self> {
         let mut comments = Vec::new();
         // let (mut input, mut comments) =
 
@@ -101,6 +138,7 @@ impl NetworkParser for Comment {
             let (tmp_input, comm) = opt(alt((
                 Self::whitespace,
                 Self::c_comment_docs,
+                Self::c_comment_inner_docs,
                 Self::c_comment,
                 Self::c_multiline_comment,
             )))(input)?;
@@ -129,20 +167,22 @@ impl NetworkParser for Comment {
         while let Some(comment) = comments.pop() {
             match (comment, &single_line_docs) {
                 (Self::Multiline(comm), None) => {
-                    return IResult::Ok((input, Self::Multiline(comm)));
+                    return ParseResult::Ok((input, Self::Multiline(comm)));
                 }
                 (Self::DocSingleline(comm), None) => {
                     single_line_docs = Some(comm);
                     continue;
                 }
                 (Self::Singleline(_), Some(comm)) => {
-                    return IResult::Ok((input, Self::DocSingleline(comm.to_owned())))
+                    return ParseResult::Ok((input, Self::DocSingleline(comm.to_owned())))
                 }
                 (Self::Multiline(_), Some(comm)) => {
-                    return IResult::Ok((input, Self::Multiline(comm.to_owned())))
+                    return ParseResult::Ok((input, Self::Multiline(comm.to_owned())))
                 }
                 (Self::DocSingleline(append), Some(comm)) => {
-                    single_line_docs = Some(comm.to_owned() + append.as_str());
+                    // comments are popped from the back, so the earlier
+                    // line is prepended
+                    single_line_docs = Some(append + "\n" + comm);
                     continue;
                 }
                 _ => continue,
@@ -150,19 +190,17 @@ impl NetworkParser for Comment {
         }
 
         if let Some(comment) = single_line_docs {
-            IResult::Ok((input, Self::DocSingleline(comment)))
+            ParseResult::Ok((input, Self::DocSingleline(comment)))
         } else {
-            IResult::Ok((input, Self::Whitespace))
+            ParseResult::Ok((input, Self::Whitespace))
         }
     }
 }
 
 #[cfg(test)]
-
 mod individual_comment_methods_test {
-    use std::string::ParseError;
-
     use super::*;
+    use crate::parser::error::span;
 
     /// The function `Comment::whitespace` should not accept
     /// the empty word. It is used from `Comment::parse` in
@@ -170,44 +208,59 @@ mod individual_comment_methods_test {
     /// to be used.
     #[test]
     fn empty_string() {
-        assert!(Comment::whitespace("").is_err());
+        assert!(Comment::whitespace(span("")).is_err());
     }
 
     #[test]
     fn simple_whitespace() {
-        let (input, comm) = Comment::whitespace(" \t \r \n").unwrap();
-        assert_eq!(input, "");
+        let (input, comm) = Comment::whitespace(span(" \t \r \n")).unwrap();
+        assert_eq!(*input.fragment(), "");
         assert_eq!(comm, Comment::Whitespace);
     }
 
     #[test]
     fn empty_comment() {
-        let (input, comm) = Comment::c_comment("//\n").unwrap();
-        assert_eq!(input, "");
+        let (input, _comm) = Comment::c_comment(span("//\n")).unwrap();
+        assert_eq!(*input.fragment(), "");
     }
 
     #[test]
     fn empty_comment_without_newline() {
-        let (input, comm) = Comment::c_comment("//").unwrap();
-        assert_eq!(input, "");
+        let (input, _comm) = Comment::c_comment(span("//")).unwrap();
+        assert_eq!(*input.fragment(), "");
     }
 
     #[test]
     fn simple_comment() {
-        let (input, comm) = Comment::c_comment("// Hello!\n").unwrap();
-        assert_eq!(input, "");
+        let (input, _comm) = Comment::c_comment(span("// Hello!\n")).unwrap();
+        assert_eq!(*input.fragment(), "");
+    }
+
+    #[test]
+    fn comment_stops_at_newline() {
+        let (input, comm) = Comment::c_comment_docs(span("/// Hello!\nstruct")).unwrap();
+        assert_eq!(*input.fragment(), "struct");
+        assert_eq!(comm, Comment::DocSingleline(" Hello!".to_owned()));
+    }
+
+    #[test]
+    fn inner_doc_comment() {
+        let (input, comm) = Comment::c_comment_inner_docs(span("//! A file.\n")).unwrap();
+        assert_eq!(*input.fragment(), "");
+        assert_eq!(comm.read_comment().unwrap(), "A file.");
+        assert!(Comment::c_comment_inner_docs(span("/// An item.\n")).is_err());
     }
 
     #[test]
     fn empty_multiline_comment() {
-        let (input, comm) = Comment::c_multiline_comment("/**/").unwrap();
-        assert_eq!(input, "");
+        let (input, _comm) = Comment::c_multiline_comment(span("/**/")).unwrap();
+        assert_eq!(*input.fragment(), "");
     }
 
     #[test]
     fn simple_multiline_comment() {
-        let (input, comm) = Comment::c_multiline_comment("/*Hello!*/").unwrap();
-        assert_eq!(input, "");
+        let (input, comm) = Comment::c_multiline_comment(span("/*Hello!*/")).unwrap();
+        assert_eq!(*input.fragment(), "");
         assert_eq!(comm.read_comment().unwrap().as_str(), "Hello!");
     }
 }
@@ -215,46 +268,62 @@ mod individual_comment_methods_test {
 #[cfg(test)]
 mod comment_test {
     use super::*;
+    use crate::parser::error::span;
 
     #[test]
     fn empty_string() {
-        let (input, _) = Comment::parse("").unwrap();
-        assert_eq!(input, "");
+        let (input, _) = Comment::parse_str("").unwrap();
+        assert_eq!(*input.fragment(), "");
     }
 
     #[test]
     fn empty_string_parse1() {
-        assert!(Comment::parse1("").is_err());
+        assert!(Comment::parse1(span("")).is_err());
     }
 
     #[test]
     fn simple_whitespace() {
-        let (input, _) = Comment::parse(" \t\r\n").unwrap();
-        assert_eq!(input, "");
+        let (input, _) = Comment::parse_str(" \t\r\n").unwrap();
+        assert_eq!(*input.fragment(), "");
     }
 
     #[test]
     fn simple_newline() {
-        let (input, _) = Comment::parse(" \n ").unwrap();
-        assert_eq!(input, "");
+        let (input, _) = Comment::parse_str(" \n ").unwrap();
+        assert_eq!(*input.fragment(), "");
     }
 
     #[test]
     fn simple_comment() {
-        let (input, network_comment) = Comment::parse("\n /* This is a comment. */ \n").unwrap();
-        assert_eq!(input, "");
+        let (input, network_comment) = Comment::parse_str("\n /* This is a comment. */ \n").unwrap();
+        assert_eq!(*input.fragment(), "");
         assert_eq!(network_comment, Comment::Multiline("This is a comment.".to_owned()));
     }
 
     #[test]
     #[ignore = "ignored, invalid test: returned output does not result in error, but does not finish reading till the end."]
     fn invalid_comment() {
-        assert!(Comment::parse("\n/* Multiline comment was not finished.\n").is_err());
+        assert!(Comment::parse_str("\n/* Multiline comment was not finished.\n").is_err());
+    }
+
+    /// A run of documentation comments is merged in reading order.
+    #[test]
+    fn doc_comment_lines() {
+        let (input, comment) = Comment::parse_str("/// First.\n/// Second.\n").unwrap();
+        assert_eq!(*input.fragment(), "");
+        assert_eq!(comment.read_comment().unwrap(), "First.\nSecond.");
+    }
+
+    #[test]
+    fn multiline_comment_lines() {
+        let (_, comment) = Comment::parse_str("/**\n * First.\n * @tag\n */").unwrap();
+        assert_eq!(comment.read_comment().unwrap(), "First.\n@tag");
+        assert_eq!(comment.read_tags()[0].name(), "tag");
     }
 
     #[test]
     fn many_comments() {
-        let (input, _) = Comment::parse("/***///Hello\n  \t\n// /*\n").unwrap();
-        assert_eq!(input, "");
+        let (input, _) = Comment::parse_str("/***///Hello\n  \t\n// /*\n").unwrap();
+        assert_eq!(*input.fragment(), "");
     }
 }
@@ -0,0 +1,461 @@
+use std::slice::Iter;
+
+use nom::{
+    branch::alt,
+    bytes::complete::{is_a, is_not},
+    character::complete::{char, digit1, hex_digit1, one_of},
+    combinator::{cut, map_res, opt, recognize, value},
+    multi::many0,
+    sequence::{delimited, pair, preceded, tuple},
+    Parser,
+};
+
+use super::{
+    comment::Comment,
+    diagnostic::SourceSpan,
+    documentation::Documentation,
+    error::{expect, in_context, keyword, symbol, Expected, ParseResult, Span},
+    expression::TypeExpression,
+    identifier::NetworkIdentifier,
+    interface::NetworkParser,
+    tag::Tag,
+};
+
+/// The limit of an integer or floating point type that can be read with
+/// `Type.MAX` and `Type.MIN`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum TypeLimit {
+    Max,
+    Min,
+}
+
+/// A fragment of an interpolated string. The text between variables
+/// is kept as a literal.
+#[derive(Debug, PartialEq, Clone)]
+pub enum InterpolationPart {
+    Literal(String),
+    Variable(String),
+}
+
+/// A constant expression is the right hand side of a `const` declaration.
+/// Expressions are folded to a value by the
+/// [ConstEvaluator](super::evaluate::ConstEvaluator).
+///
+/// #### Example
+///
+/// ```net
+/// 42  0xFF  0b1010  -1
+/// 1.5  -0.0  6.02e23
+/// "Hello"
+/// $"Version: ${NETZ_VERSION}"
+/// u32.MAX
+/// OTHER_CONSTANT
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+pub enum ConstExpression {
+    Integer(i128),
+    Float(f64),
+    String(String),
+    Interpolation(Vec<InterpolationPart>),
+    Limit(String, TypeLimit),
+    Reference(String),
+}
+
+impl ConstExpression {
+    /// Reads an integer literal in decimal, hexadecimal (`0x`) or
+    /// binary (`0b`) notation. Decimal literals can be negative.
+    fn integer(input: Span) -> ParseResult<i128> {
+        alt((
+            map_res(preceded(symbol("0x"), hex_digit1), |s: Span| {
+                i128::from_str_radix(s.fragment(), 16)
+            }),
+            map_res(preceded(symbol("0b"), is_a("01")), |s: Span| {
+                i128::from_str_radix(s.fragment(), 2)
+            }),
+            map_res(recognize(pair(opt(char('-')), digit1)), |s: Span| {
+                s.fragment().parse::<i128>()
+            }),
+        ))(input)
+    }
+
+    /// Reads a decimal floating point literal, which has a fraction or an
+    /// exponent, such as `1.5`, `-0.0` or `6.02e23`. Literals too large for
+    /// `f64` are read as infinity.
+    fn float(input: Span) -> ParseResult<f64> {
+        let exponent = || recognize(tuple((one_of("eE"), opt(one_of("+-")), digit1)));
+
+        map_res(
+            recognize(tuple((
+                opt(char('-')),
+                digit1,
+                alt((
+                    recognize(pair(pair(char('.'), digit1), opt(exponent()))),
+                    exponent(),
+                )),
+            ))),
+            |s: Span| s.fragment().parse::<f64>(),
+        )(input)
+    }
+
+    /// Reads the content of a string literal after the opening quotation
+    /// mark, including the closing quotation mark. The backslash escapes
+    /// the following character, unknown escape sequences are kept as they
+    /// are written, so regular expressions can be used in strings.
+    /// Interpolated strings additionally split on `${VARIABLE}`.
+    fn string_parts(mut input: Span, interpolate: bool) -> ParseResult<Vec<InterpolationPart>> {
+        let mut parts = Vec::new();
+        let mut literal = String::new();
+        let stop = if interpolate { "\\\"$" } else { "\\\"" };
+
+        loop {
+            let (tmp_input, fragment) = opt(is_not(stop))(input)?;
+            input = tmp_input;
+            if let Some(fragment) = fragment {
+                literal += fragment.fragment();
+            }
+
+            if let Ok((tmp_input, variable)) =
+                delimited(symbol("${"), NetworkIdentifier::parse, symbol("}"))(input)
+            {
+                if !literal.is_empty() {
+                    parts.push(InterpolationPart::Literal(std::mem::take(&mut literal)));
+                }
+                parts.push(InterpolationPart::Variable(variable.identity));
+                input = tmp_input;
+                continue;
+            }
+
+            let (tmp_input, c) = nom::character::complete::anychar(input)?;
+            input = tmp_input;
+
+            match c {
+                '\\' => {
+                    let (tmp_input, escaped) = opt(alt((
+                        value('\n', char('n')),
+                        value('\t', char('t')),
+                        value('\\', char('\\')),
+                        value('"', char('"')),
+                        value('$', char('$')),
+                    )))(input)?;
+                    input = tmp_input;
+
+                    match escaped {
+                        Some(escaped) => literal.push(escaped),
+                        None => literal.push('\\'),
+                    }
+                }
+                '"' => break,
+                c => literal.push(c),
+            }
+        }
+
+        if !literal.is_empty() {
+            parts.push(InterpolationPart::Literal(literal));
+        }
+
+        ParseResult::Ok((input, parts))
+    }
+
+    /// Reads a string literal in quotation marks.
+    pub(crate) fn string(input: Span) -> ParseResult<String> {
+        let (input, _) = symbol("\"")(input)?;
+        let (input, parts) = Self::string_parts(input, false)?;
+
+        let string = parts
+            .into_iter()
+            .map(|part| match part {
+                InterpolationPart::Literal(s) | InterpolationPart::Variable(s) => s,
+            })
+            .collect();
+
+        ParseResult::Ok((input, string))
+    }
+
+    /// Reads an interpolated string literal, starting with `$"`.
+    fn interpolation(input: Span) -> ParseResult<Vec<InterpolationPart>> {
+        let (input, _) = symbol("$\"")(input)?;
+        Self::string_parts(input, true)
+    }
+
+    /// Reads a reference to another constant or the limit of a type.
+    fn reference(input: Span) -> ParseResult<Self> {
+        let (input, identifier) = NetworkIdentifier::parse(input)?;
+        let (input, limit) = opt(preceded(
+            symbol("."),
+            alt((
+                value(TypeLimit::Max, symbol("MAX")),
+                value(TypeLimit::Min, symbol("MIN")),
+            )),
+        ))(input)?;
+
+        let expression = match limit {
+            Some(limit) => Self::Limit(identifier.identity, limit),
+            None => Self::Reference(identifier.identity),
+        };
+
+        ParseResult::Ok((input, expression))
+    }
+}
+
+impl NetworkParser for ConstExpression {
+    fn parse(input: Span) -> ParseResult<Self> {
+        let (input, _) = Comment::parse(input)?;
+
+        expect(
+            Expected::Expression,
+            alt((
+                Self::interpolation.map(Self::Interpolation),
+                Self::string.map(Self::String),
+                Self::float.map(Self::Float),
+                Self::integer.map(Self::Integer),
+                Self::reference,
+            )),
+        )(input)
+    }
+}
+
+/// Definition for a `const` in a network file. A constant starts with
+/// the keyword 'const', followed by an identifier declaring the constants'
+/// name, its' type after a colon and the value after an equal sign.
+///
+/// #### Example
+///
+/// ```net
+/// const MAGIC: u32 = u32.MAX;
+/// const VERSION: string = $"Version: ${NETZ_VERSION}";
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+pub struct NetworkConst {
+    identity: String,
+    span: SourceSpan,
+    const_type: TypeExpression,
+    expression: ConstExpression,
+    documentation: Option<Documentation>,
+    tags: Vec<Tag>,
+}
+
+impl NetworkConst {
+    pub fn name(&self) -> &str {
+        self.identity.as_str()
+    }
+
+    /// The location of the name in the network file.
+    pub fn span(&self) -> SourceSpan {
+        self.span
+    }
+
+    /// The declared type of the constant.
+    pub fn const_type(&self) -> &TypeExpression {
+        &self.const_type
+    }
+
+    pub fn expression(&self) -> &ConstExpression {
+        &self.expression
+    }
+
+    pub fn documentation(&self) -> Option<&Documentation> {
+        self.documentation.as_ref()
+    }
+
+    pub fn tags(&self) -> Iter<'_, Tag> {
+        self.tags.iter()
+    }
+
+    /// Reads the type of the constant after the colon, the value after
+    /// the equal sign and the closing semicolon.
+    fn definition(input: Span) -> ParseResult<(TypeExpression, ConstExpression)> {
+        // expect the constant type
+        let (input, _) = Comment::parse(input)?;
+        let (input, _) = symbol(":")(input)?;
+        let (input, const_type) = TypeExpression::parse(input)?;
+
+        // expect the constant value
+        let (input, _) = Comment::parse(input)?;
+        let (input, _) = symbol("=")(input)?;
+        let (input, expression) = ConstExpression::parse(input)?;
+
+        // read the semicolon
+        let (input, _) = Comment::parse(input)?;
+        let (input, _) = symbol(";")(input)?;
+
+        ParseResult::Ok((input, (const_type, expression)))
+    }
+}
+
+impl NetworkParser for NetworkConst {
+    fn parse(input: Span) -> ParseResult<Self> {
+        let (input, comment) = Comment::parse(input)?;
+
+        // read optionally several tags, including those in the documentation
+        let (input, tags) = many0(Tag::parse)(input)?;
+        let tags = [comment.read_tags(), tags].concat();
+
+        // read the 'const' keyword
+        let (input, _) = Comment::parse(input)?;
+        let (input, _) = keyword("const")(input)?;
+
+        // expect constant name
+        let (input, const_name) = cut(NetworkIdentifier::parse)(input)?;
+        let context = format!("const `{}`", const_name.identity);
+
+        // expect the constant type and value
+        let (input, (const_type, expression)) = in_context(context, cut(Self::definition))(input)?;
+
+        Ok((
+            input,
+            Self {
+                identity: const_name.identity,
+                span: const_name.span,
+                const_type,
+                expression,
+                documentation: comment.read_documentation(),
+                tags,
+            },
+        ))
+    }
+}
+
+#[cfg(test)]
+mod const_test {
+    use super::*;
+
+    #[test]
+    fn integer_literals() {
+        assert_eq!(
+            ConstExpression::parse_str("42").unwrap().1,
+            ConstExpression::Integer(42)
+        );
+        assert_eq!(
+            ConstExpression::parse_str("-7").unwrap().1,
+            ConstExpression::Integer(-7)
+        );
+        assert_eq!(
+            ConstExpression::parse_str("0xFF").unwrap().1,
+            ConstExpression::Integer(255)
+        );
+        assert_eq!(
+            ConstExpression::parse_str("0b1010").unwrap().1,
+            ConstExpression::Integer(10)
+        );
+    }
+
+    #[test]
+    fn float_literals() {
+        assert_eq!(
+            ConstExpression::parse_str("1.5").unwrap().1,
+            ConstExpression::Float(1.5)
+        );
+        assert_eq!(
+            ConstExpression::parse_str("6.02e23").unwrap().1,
+            ConstExpression::Float(6.02e23)
+        );
+        assert_eq!(
+            ConstExpression::parse_str("1E-3").unwrap().1,
+            ConstExpression::Float(0.001)
+        );
+
+        // negative zero keeps its' sign
+        let (input, expression) = ConstExpression::parse_str("-0.0").unwrap();
+        assert_eq!(*input.fragment(), "");
+        let ConstExpression::Float(zero) = expression else {
+            panic!("expected a float");
+        };
+        assert!(zero == 0.0 && zero.is_sign_negative());
+
+        // integers and hexadecimal literals are no floats
+        assert_eq!(
+            ConstExpression::parse_str("15").unwrap().1,
+            ConstExpression::Integer(15)
+        );
+        assert_eq!(
+            ConstExpression::parse_str("0x1e5").unwrap().1,
+            ConstExpression::Integer(0x1e5)
+        );
+    }
+
+    #[test]
+    fn string_literal() {
+        let (input, expression) = ConstExpression::parse_str("\"Say \\\"Hi\\\"\"").unwrap();
+        assert_eq!(*input.fragment(), "");
+        assert_eq!(expression, ConstExpression::String("Say \"Hi\"".to_owned()));
+        assert_eq!(
+            ConstExpression::parse_str("\"\"").unwrap().1,
+            ConstExpression::String("".to_owned())
+        );
+    }
+
+    #[test]
+    fn unknown_escape() {
+        assert_eq!(
+            ConstExpression::parse_str("\"\\d+\\.\\d+\"").unwrap().1,
+            ConstExpression::String("\\d+\\.\\d+".to_owned())
+        );
+    }
+
+    #[test]
+    fn interpolated_string() {
+        let (input, expression) =
+            ConstExpression::parse_str("$\"Version: ${NETZ_VERSION}!\"").unwrap();
+        assert_eq!(*input.fragment(), "");
+        assert_eq!(
+            expression,
+            ConstExpression::Interpolation(vec![
+                InterpolationPart::Literal("Version: ".to_owned()),
+                InterpolationPart::Variable("NETZ_VERSION".to_owned()),
+                InterpolationPart::Literal("!".to_owned()),
+            ])
+        );
+    }
+
+    #[test]
+    fn type_limits() {
+        assert_eq!(
+            ConstExpression::parse_str("u32.MAX").unwrap().1,
+            ConstExpression::Limit("u32".to_owned(), TypeLimit::Max)
+        );
+        assert_eq!(
+            ConstExpression::parse_str("i8.MIN").unwrap().1,
+            ConstExpression::Limit("i8".to_owned(), TypeLimit::Min)
+        );
+    }
+
+    #[test]
+    fn reference() {
+        assert_eq!(
+            ConstExpression::parse_str("NETZ_SCHEMA").unwrap().1,
+            ConstExpression::Reference("NETZ_SCHEMA".to_owned())
+        );
+    }
+
+    #[test]
+    fn simple_const() {
+        let (input, constant) = NetworkConst::parse_str("const MAGIC: u32 = u32.MAX;").unwrap();
+        assert_eq!(*input.fragment(), "");
+        assert_eq!(constant.name(), "MAGIC");
+        assert_eq!(constant.const_type().name(), Some("u32"));
+        assert_eq!(
+            constant.expression(),
+            &ConstExpression::Limit("u32".to_owned(), TypeLimit::Max)
+        );
+    }
+
+    #[test]
+    fn documented_const() {
+        let (input, constant) =
+            NetworkConst::parse_str("/** The truth. */\nconst true: bool = 1;").unwrap();
+        assert_eq!(*input.fragment(), "");
+        assert_eq!(constant.name(), "true");
+        assert_eq!(
+            constant.documentation().map(Documentation::description),
+            Some("The truth.")
+        );
+    }
+
+    #[test]
+    fn incomplete_const() {
+        assert!(NetworkConst::parse_str("const X = 1;").is_err());
+        assert!(NetworkConst::parse_str("const X: u8;").is_err());
+        assert!(NetworkConst::parse_str("const X: u8 = 1").is_err());
+        assert!(NetworkConst::parse_str("const X: u8 = \"open;").is_err());
+    }
+}
@@ -0,0 +1,250 @@
+use std::fmt::Display;
+
+/// The severity of a diagnostic.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+/// A region of a network file. The offset and length are counted in
+/// bytes, the line and column start at one.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct SourceSpan {
+    offset: usize,
+    length: usize,
+    line: u32,
+    column: usize,
+}
+
+impl SourceSpan {
+    pub fn new(offset: usize, length: usize, line: u32, column: usize) -> Self {
+        Self {
+            offset,
+            length,
+            line,
+            column,
+        }
+    }
+
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    pub fn length(&self) -> usize {
+        self.length
+    }
+
+    pub fn line(&self) -> u32 {
+        self.line
+    }
+
+    pub fn column(&self) -> usize {
+        self.column
+    }
+}
+
+/// A message about a location in a network file, such as a parser error.
+/// Diagnostics are rendered like the diagnostics of `rustc`, with the
+/// line of the source file and carets under the span.
+///
+/// ```
+/// use network_parser::parser::diagnostic::{Diagnostic, SourceSpan};
+///
+/// let source = "struct Post {\n    author User;\n}\n";
+/// let diagnostic = Diagnostic::error("expected `:`, found `User`", SourceSpan::new(25, 4, 2, 12))
+///     .with_label("unexpected `User`")
+///     .with_note("in field `author` of struct `Post`");
+///
+/// assert_eq!(
+///     diagnostic.render("forum.net", source),
+///     "error: expected `:`, found `User`
+///  --> forum.net:2:12
+///   |
+/// 2 |     author User;
+///   |            ^^^^ unexpected `User`
+///   |
+///   = note: in field `author` of struct `Post`
+/// "
+/// );
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+pub struct Diagnostic {
+    severity: Severity,
+    message: String,
+    span: SourceSpan,
+    file: Option<String>,
+    label: Option<String>,
+    notes: Vec<String>,
+    help: Option<String>,
+}
+
+impl Diagnostic {
+    pub fn new(severity: Severity, message: impl Into<String>, span: SourceSpan) -> Self {
+        Self {
+            severity,
+            message: message.into(),
+            span,
+            file: None,
+            label: None,
+            notes: Vec::new(),
+            help: None,
+        }
+    }
+
+    pub fn error(message: impl Into<String>, span: SourceSpan) -> Self {
+        Self::new(Severity::Error, message, span)
+    }
+
+    pub fn warning(message: impl Into<String>, span: SourceSpan) -> Self {
+        Self::new(Severity::Warning, message, span)
+    }
+
+    /// Sets the network file the span refers to, for schemas of several
+    /// files.
+    pub fn with_file(mut self, file: impl Into<String>) -> Self {
+        self.file = Some(file.into());
+        self
+    }
+
+    /// Sets the text printed next to the carets.
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Adds a note printed below the source snippet.
+    pub fn with_note(mut self, note: impl Into<String>) -> Self {
+        self.notes.push(note.into());
+        self
+    }
+
+    /// Sets a help message, for example a suggested fix.
+    pub fn with_help(mut self, help: impl Into<String>) -> Self {
+        self.help = Some(help.into());
+        self
+    }
+
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    pub fn message(&self) -> &str {
+        self.message.as_str()
+    }
+
+    pub fn span(&self) -> SourceSpan {
+        self.span
+    }
+
+    /// The network file the span refers to, if the diagnostic was
+    /// reported for a schema of several files.
+    pub fn file(&self) -> Option<&str> {
+        self.file.as_deref()
+    }
+
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
+    pub fn notes(&self) -> &[String] {
+        self.notes.as_slice()
+    }
+
+    pub fn help(&self) -> Option<&str> {
+        self.help.as_deref()
+    }
+
+    /// Renders the diagnostic with a snippet of the source file, which
+    /// has to be the file the span refers to. Tabs are printed as four
+    /// spaces, so the carets line up.
+    pub fn render(&self, file_name: &str, source: &str) -> String {
+        let offset = self.span.offset.min(source.len());
+        let line_start = source[..offset].rfind('\n').map_or(0, |idx| idx + 1);
+        let line_end = source[offset..]
+            .find(['\n', '\r'])
+            .map_or(source.len(), |idx| offset + idx);
+
+        let expand = |text: &str| text.replace('\t', "    ");
+        let line = expand(&source[line_start..line_end]);
+        let indent = expand(&source[line_start..offset]).chars().count();
+
+        // the carets underline at least one character and stop at the
+        // end of the line
+        let end = (offset + self.span.length).min(line_end);
+        let carets = source
+            .get(offset..end)
+            .map_or(0, |underlined| expand(underlined).chars().count())
+            .max(1);
+
+        let line_number = self.span.line.to_string();
+        let gutter = " ".repeat(line_number.len());
+
+        let mut output = format!("{}: {}\n", self.severity, self.message);
+        output += &format!(
+            "{gutter}--> {file_name}:{}:{}\n",
+            self.span.line, self.span.column
+        );
+        output += &format!("{gutter} |\n");
+        output += &format!("{line_number} | {line}\n");
+        output += &format!("{gutter} | {}{}", " ".repeat(indent), "^".repeat(carets));
+
+        if let Some(label) = &self.label {
+            output += &format!(" {label}");
+        }
+
+        output += "\n";
+
+        if !self.notes.is_empty() || self.help.is_some() {
+            output += &format!("{gutter} |\n");
+        }
+
+        for note in self.notes.iter() {
+            output += &format!("{gutter} = note: {note}\n");
+        }
+
+        if let Some(help) = &self.help {
+            output += &format!("{gutter} = help: {help}\n");
+        }
+
+        output
+    }
+}
+
+#[cfg(test)]
+mod diagnostic_test {
+    use super::*;
+
+    #[test]
+    fn end_of_file() {
+        let diagnostic = Diagnostic::error(
+            "expected `}`, found end of file",
+            SourceSpan::new(10, 0, 1, 11),
+        )
+        .with_label("unexpected end of file");
+        assert_eq!(
+            diagnostic.render("a.net", "struct A {"),
+            "error: expected `}`, found end of file\n --> a.net:1:11\n  |\n1 | struct A {\n  |           ^ unexpected end of file\n"
+        );
+    }
+
+    #[test]
+    fn tabs_and_wide_gutter() {
+        let source = "\n\n\n\n\n\n\n\n\nstruct A {\n\tid u8;\n}";
+        let diagnostic = Diagnostic::warning("odd field", SourceSpan::new(21, 2, 11, 2))
+            .with_help("add a colon");
+        assert_eq!(
+            diagnostic.render("a.net", source),
+            "warning: odd field\n  --> a.net:11:2\n   |\n11 |     id u8;\n   |     ^^\n   |\n   = help: add a colon\n"
+        );
+    }
+}
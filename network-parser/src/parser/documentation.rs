@@ -0,0 +1,191 @@
+use std::slice::Iter;
+
+use super::{interface::NetworkParser, tag::Tag};
+
+/// The documentation tags are written like tags inside of a documentation
+/// comment, but carry the text that follows them instead of extending the
+/// definition. They are split from the description, so generators can
+/// translate them to their' counterparts in JSDoc or rustdoc.
+pub static DOCUMENTATION_TAGS: &[&str] = &["description", "note", "example", "see", "deprecated"];
+
+/// A documentation tag with its' text, which continues until the next
+/// tag in the documentation comment.
+///
+/// #### Example
+///
+/// ```net
+/// /// @note The content
+/// /// can span several lines.
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+pub struct DocTag {
+    tag_name: String,
+    content: String,
+}
+
+impl DocTag {
+    pub fn name(&self) -> &str {
+        self.tag_name.as_str()
+    }
+
+    pub fn content(&self) -> &str {
+        self.content.as_str()
+    }
+}
+
+/// The structured documentation of a definition, read from the
+/// documentation comment in front of it. The text outside of any
+/// documentation tag and the text of `@description` form the
+/// description, tags on their' own line which are not documentation
+/// tags are removed, see [Tag::from_documentation].
+///
+/// ```
+/// use network_parser::parser::documentation::Documentation;
+///
+/// let documentation = Documentation::new(
+///     "@description A user.\n@!limit(20B)\n@note Stored in\nthe database.",
+/// );
+///
+/// assert_eq!(documentation.description(), "A user.");
+/// assert_eq!(documentation.doc_tag("note").unwrap().content(), "Stored in\nthe database.");
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+pub struct Documentation {
+    description: String,
+    doc_tags: Vec<DocTag>,
+}
+
+impl Documentation {
+    /// Splits the text content of a documentation comment into the
+    /// description and documentation tags.
+    pub fn new(text: &str) -> Self {
+        let mut description = String::new();
+        let mut doc_tags: Vec<DocTag> = Vec::new();
+
+        // whether the lines are read into the last documentation tag
+        let mut in_doc_tag = false;
+
+        for line in text.lines() {
+            let tag = match line.trim_start() {
+                trimmed if trimmed.starts_with('@') => Tag::parse_str(trimmed).ok(),
+                _ => None,
+            };
+
+            let Some((rest, tag)) = tag else {
+                let content = match (in_doc_tag, doc_tags.last_mut()) {
+                    (true, Some(doc_tag)) => &mut doc_tag.content,
+                    _ => &mut description,
+                };
+
+                content.push('\n');
+                content.push_str(line);
+                continue;
+            };
+
+            if !Self::is_documentation_tag(&tag) {
+                // extension tags are read with `Tag::from_documentation`
+                in_doc_tag = false;
+            } else if tag.name() == "description" {
+                in_doc_tag = false;
+                description.push('\n');
+                description.push_str(rest.fragment().trim());
+            } else {
+                in_doc_tag = true;
+                doc_tags.push(DocTag {
+                    tag_name: tag.name().to_owned(),
+                    content: rest.fragment().trim().to_owned(),
+                });
+            }
+        }
+
+        for doc_tag in doc_tags.iter_mut() {
+            doc_tag.content = doc_tag.content.trim().to_owned();
+        }
+
+        Self {
+            description: description.trim().to_owned(),
+            doc_tags,
+        }
+    }
+
+    /// Checks if the tag is a documentation tag. Required tags and tags
+    /// with arguments are always extensions.
+    pub fn is_documentation_tag(tag: &Tag) -> bool {
+        !tag.is_required() && tag.arguments().is_empty() && DOCUMENTATION_TAGS.contains(&tag.name())
+    }
+
+    pub fn description(&self) -> &str {
+        self.description.as_str()
+    }
+
+    pub fn doc_tags(&self) -> Iter<'_, DocTag> {
+        self.doc_tags.iter()
+    }
+
+    /// Read the first documentation tag with the given name.
+    pub fn doc_tag(&self, name: &str) -> Option<&DocTag> {
+        self.doc_tags.iter().find(|doc_tag| doc_tag.name() == name)
+    }
+
+    /// Checks if neither a description nor documentation tags were written.
+    pub fn is_empty(&self) -> bool {
+        self.description.is_empty() && self.doc_tags.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod documentation_test {
+    use super::*;
+
+    #[test]
+    fn plain_description() {
+        let documentation = Documentation::new("A simple\nstructure.");
+        assert_eq!(documentation.description(), "A simple\nstructure.");
+        assert_eq!(documentation.doc_tags().len(), 0);
+    }
+
+    /// The documentation of `type string` in the standard library.
+    #[test]
+    fn description_tag() {
+        let documentation = Documentation::new(
+            "@description The primitive string type.\nIt is encoded as bytes.\n\n@string_convert(\"ascii\")",
+        );
+        assert_eq!(
+            documentation.description(),
+            "The primitive string type.\nIt is encoded as bytes."
+        );
+        assert!(documentation.doc_tags().next().is_none());
+    }
+
+    #[test]
+    fn note_tag() {
+        let documentation = Documentation::new(
+            "@!limit(20B)\n\n@note The `!` symbol.\n\nThe `limit` extension.\n@see Tag",
+        );
+        assert_eq!(documentation.description(), "");
+
+        let doc_tags = documentation.doc_tags().collect::<Vec<_>>();
+        assert_eq!(doc_tags.len(), 2);
+        assert_eq!(doc_tags[0].name(), "note");
+        assert_eq!(
+            doc_tags[0].content(),
+            "The `!` symbol.\n\nThe `limit` extension."
+        );
+        assert_eq!(doc_tags[1].name(), "see");
+        assert_eq!(doc_tags[1].content(), "Tag");
+    }
+
+    /// Text after an extension tag belongs to the description again.
+    #[test]
+    fn text_after_extension() {
+        let documentation = Documentation::new("@note A note.\n@!hello\nThe hello modifier.");
+        assert_eq!(documentation.description(), "The hello modifier.");
+        assert_eq!(documentation.doc_tag("note").unwrap().content(), "A note.");
+    }
+
+    #[test]
+    fn required_doc_tag_is_extension() {
+        let documentation = Documentation::new("@!deprecated");
+        assert!(documentation.is_empty());
+    }
+}
@@ -0,0 +1,376 @@
+use std::slice::Iter;
+
+use nom::{
+    character::complete::u64,
+    combinator::{cut, opt},
+    multi::{many0, separated_list1},
+    sequence::{delimited, pair, preceded},
+};
+
+use super::{
+    comment::Comment,
+    diagnostic::SourceSpan,
+    documentation::Documentation,
+    error::{expect, in_context, keyword, members, symbol, Expected, ParseResult, Span},
+    expression::TypeExpression,
+    identifier::NetworkIdentifier,
+    interface::NetworkParser,
+    tag::Tag,
+};
+
+/// An enum member is an entry in an enumeration. It contains the
+/// member identifier, an optional tuple of payload types and an
+/// optional discriminator.
+///
+/// #### Example
+///
+/// ```net
+/// @deprecated Member(u8, string) = 1;
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+pub struct EnumMember {
+    identity: String,
+    span: SourceSpan,
+    payload: Vec<TypeExpression>,
+    discriminator: Option<u64>,
+    documentation: Option<Documentation>,
+    tags: Vec<Tag>,
+}
+
+impl EnumMember {
+    pub fn name(&self) -> &str {
+        self.identity.as_str()
+    }
+
+    /// The location of the name in the network file.
+    pub fn span(&self) -> SourceSpan {
+        self.span
+    }
+
+    /// The types carried by the member, empty if it is a unit member.
+    pub fn payload(&self) -> Iter<'_, TypeExpression> {
+        self.payload.iter()
+    }
+
+    /// The discriminator as written in the network file, `None` if it
+    /// was omitted. See [NetworkEnum::discriminators] for the resolved
+    /// values.
+    pub fn discriminator(&self) -> Option<u64> {
+        self.discriminator
+    }
+
+    pub fn documentation(&self) -> Option<&Documentation> {
+        self.documentation.as_ref()
+    }
+
+    pub fn tags(&self) -> Iter<'_, Tag> {
+        self.tags.iter()
+    }
+
+    /// Reads the optional payload and discriminator of the member and
+    /// the closing semicolon.
+    fn definition(input: Span) -> ParseResult<(Vec<TypeExpression>, Option<u64>)> {
+        // read the optional payload in round brackets
+        let (input, _) = Comment::parse(input)?;
+        let (input, payload) = opt(delimited(
+            symbol("("),
+            separated_list1(pair(Comment::parse, symbol(",")), TypeExpression::parse),
+            pair(Comment::parse, symbol(")")),
+        ))(input)?;
+
+        // read the optional discriminator
+        let (input, _) = Comment::parse(input)?;
+        let (input, discriminator) = opt(preceded(
+            pair(symbol("="), Comment::parse),
+            expect(Expected::Integer, u64),
+        ))(input)?;
+
+        // read the semicolon
+        let (input, _) = Comment::parse(input)?;
+        let (input, _) = symbol(";")(input)?;
+
+        ParseResult::Ok((input, (payload.unwrap_or_default(), discriminator)))
+    }
+}
+
+impl NetworkParser for EnumMember {
+    fn parse(input: Span) -> ParseResult<Self> {
+        let (input, comment) = Comment::parse(input)?;
+
+        // read optionally several tags, including those in the documentation
+        let (input, tags) = many0(Tag::parse)(input)?;
+        let tags = [comment.read_tags(), tags].concat();
+
+        // read the member name, after which the declaration is expected
+        // to be complete
+        let (input, member_name) = NetworkIdentifier::parse(input)?;
+        let context = format!("member `{}`", member_name.identity);
+
+        let (input, (payload, discriminator)) = in_context(context, cut(Self::definition))(input)?;
+
+        ParseResult::Ok((
+            input,
+            EnumMember {
+                identity: member_name.identity,
+                span: member_name.span,
+                payload,
+                discriminator,
+                documentation: comment.read_documentation(),
+                tags,
+            },
+        ))
+    }
+}
+
+/// Definition for an `enum` in a network file. Such an enumeration
+/// starts with the keyword 'enum', followed by an identifier declaring
+/// the enumerations' name, an optional discriminator type after a colon,
+/// followed by an array of members inside curly brackets.
+///
+/// #### Example
+///
+/// ```net
+/// enum Bar: u16 {
+///     Empty = 0;
+///     Small(u8) = 1;
+///     Large(u32);
+/// }
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+pub struct NetworkEnum {
+    identity: String,
+    span: SourceSpan,
+    discriminator_type: Option<TypeExpression>,
+    members: Vec<EnumMember>,
+    documentation: Option<Documentation>,
+    tags: Vec<Tag>,
+}
+
+impl NetworkEnum {
+    pub fn name(&self) -> &str {
+        self.identity.as_str()
+    }
+
+    /// The location of the name in the network file.
+    pub fn span(&self) -> SourceSpan {
+        self.span
+    }
+
+    /// The type of the discriminator, if it was declared explicitly.
+    pub fn discriminator_type(&self) -> Option<&TypeExpression> {
+        self.discriminator_type.as_ref()
+    }
+
+    pub fn members(&self) -> Iter<'_, EnumMember> {
+        self.members.iter()
+    }
+
+    /// Read a member from the enumeration.
+    pub fn member(&self, name: &str) -> Option<&EnumMember> {
+        self.members.iter().find(|member| member.name() == name)
+    }
+
+    pub fn documentation(&self) -> Option<&Documentation> {
+        self.documentation.as_ref()
+    }
+
+    pub fn tags(&self) -> Iter<'_, Tag> {
+        self.tags.iter()
+    }
+
+    /// Resolves the discriminator of every member. A member without an
+    /// explicit discriminator continues counting from its' predecessor,
+    /// the first member starts at zero. The list ends before the first
+    /// member, whose' implicit discriminator would overflow, which is
+    /// reported by the [Resolver](super::resolver::Resolver).
+    pub fn discriminators(&self) -> Vec<(&str, u64)> {
+        let mut next = Some(0);
+
+        self.members
+            .iter()
+            .map_while(|member| {
+                let discriminator = member.discriminator.or(next)?;
+                next = discriminator.checked_add(1);
+                Some((member.name(), discriminator))
+            })
+            .collect()
+    }
+
+    /// Reads the optional discriminator type and the member declarations
+    /// inside of curly brackets.
+    fn body(input: Span) -> ParseResult<(Option<TypeExpression>, Vec<EnumMember>)> {
+        // read the optional discriminator type
+        let (input, _) = Comment::parse(input)?;
+        let (input, discriminator_type) =
+            opt(preceded(symbol(":"), NetworkIdentifier::parse))(input)?;
+
+        // expect '{' symbol
+        let (input, _) = Comment::parse(input)?;
+        let (input, _) = symbol("{")(input)?;
+
+        // expect member declarations
+        let (input, members) = members(EnumMember::parse)(input)?;
+
+        let (input, _) = Comment::parse(input)?;
+        let (input, _) = symbol("}")(input)?;
+
+        ParseResult::Ok((
+            input,
+            (
+                discriminator_type.map(TypeExpression::from),
+                members,
+            ),
+        ))
+    }
+}
+
+impl NetworkParser for NetworkEnum {
+    fn parse(input: Span) -> ParseResult<Self> {
+        let (input, comment) = Comment::parse(input)?;
+
+        // read optionally several tags, including those in the documentation
+        let (input, tags) = many0(Tag::parse)(input)?;
+        let tags = [comment.read_tags(), tags].concat();
+
+        // read the 'enum' keyword
+        let (input, _) = Comment::parse(input)?;
+        let (input, _) = keyword("enum")(input)?;
+
+        // expect enumeration name
+        let (input, enum_name) = cut(NetworkIdentifier::parse)(input)?;
+        let context = format!("enum `{}`", enum_name.identity);
+
+        // expect the optional discriminator type and the member
+        // declarations inside of curly brackets
+        let (input, (discriminator_type, members)) = in_context(context, cut(Self::body))(input)?;
+
+        Ok((
+            input,
+            Self {
+                identity: enum_name.identity,
+                span: enum_name.span,
+                discriminator_type,
+                members,
+                documentation: comment.read_documentation(),
+                tags,
+            },
+        ))
+    }
+}
+
+#[cfg(test)]
+mod enum_test {
+    use super::*;
+
+    /// Tests a simple enumeration without declared members.
+    #[test]
+    fn simple_enum() {
+        let (input, network_enum) = NetworkEnum::parse_str("enum Enum {}").unwrap();
+        assert_eq!(*input.fragment(), "");
+        assert_eq!(network_enum.identity, "Enum");
+        assert_eq!(network_enum.members.len(), 0);
+    }
+
+    /// An enumeration requires a name.
+    #[test]
+    fn nameless_enum() {
+        assert!(NetworkEnum::parse_str("enum {}").is_err());
+    }
+
+    /// Tests an enumeration of unit members with explicit discriminators.
+    #[test]
+    fn unit_enum() {
+        let (input, network_enum) = NetworkEnum::parse_str(
+            "enum MessageType {\n\tPost = 0;\n\tTopic = 1;\n\tThread = 2;\n}",
+        )
+        .unwrap();
+        assert_eq!(*input.fragment(), "");
+        assert_eq!(network_enum.members.len(), 3);
+        assert_eq!(
+            network_enum.member("Topic").unwrap().discriminator(),
+            Some(1)
+        );
+        assert_eq!(network_enum.member("Thread").unwrap().payload().len(), 0);
+    }
+
+    /// Tests an enumeration with payload members.
+    #[test]
+    fn payload_enum() {
+        let (input, network_enum) =
+            NetworkEnum::parse_str("enum Bar { Small(u8) = 1; Pair(u16, Foo[]) = 2; }").unwrap();
+        assert_eq!(*input.fragment(), "");
+
+        let small = network_enum.member("Small").unwrap();
+        assert_eq!(small.payload().next().unwrap().name(), Some("u8"));
+
+        let pair = network_enum.member("Pair").unwrap();
+        let payload = pair.payload().collect::<Vec<_>>();
+        assert_eq!(payload.len(), 2);
+        assert_eq!(*payload[1], TypeExpression::array(TypeExpression::named("Foo")));
+    }
+
+    /// Tests the optional discriminator type.
+    #[test]
+    fn typed_enum() {
+        let (input, network_enum) = NetworkEnum::parse_str("enum Bar : u16 { A; }").unwrap();
+        assert_eq!(*input.fragment(), "");
+        assert_eq!(
+            network_enum.discriminator_type().and_then(TypeExpression::name),
+            Some("u16")
+        );
+        assert_eq!(
+            NetworkEnum::parse_str("enum Bar { A; }")
+                .unwrap()
+                .1
+                .discriminator_type(),
+            None
+        );
+    }
+
+    /// Members without discriminator continue counting from the
+    /// previous member.
+    #[test]
+    fn implicit_discriminators() {
+        let (_, network_enum) = NetworkEnum::parse_str("enum Bar { A; B = 5; C; }").unwrap();
+        assert_eq!(
+            network_enum.discriminators(),
+            vec![("A", 0), ("B", 5), ("C", 6)]
+        );
+
+        let (_, network_enum) =
+            NetworkEnum::parse_str("enum Bar { A = 18446744073709551615; B; C = 1; }").unwrap();
+        assert_eq!(
+            network_enum.discriminators(),
+            vec![("A", 18446744073709551615)]
+        );
+    }
+
+    /// Tests an enumeration with tags and documentation on both the
+    /// enumeration and its' members.
+    #[test]
+    fn annotated_enum() {
+        let (input, network_enum) = NetworkEnum::parse_str(
+            "/** An enum. */ @special enum Bar {\n\t/// A member.\n\t@deprecated A = 0;\n}",
+        )
+        .unwrap();
+        assert_eq!(*input.fragment(), "");
+        assert_eq!(
+            network_enum.documentation().map(Documentation::description),
+            Some("An enum.")
+        );
+        assert_eq!(network_enum.tags().next().unwrap().name(), "special");
+
+        let member = network_enum.member("A").unwrap();
+        assert_eq!(
+            member.documentation().map(Documentation::description),
+            Some("A member.")
+        );
+        assert_eq!(member.tags().next().unwrap().name(), "deprecated");
+    }
+
+    /// A member requires a closing semicolon.
+    #[test]
+    fn unterminated_member() {
+        assert!(NetworkEnum::parse_str("enum Bar { A = 0 }").is_err());
+    }
+}
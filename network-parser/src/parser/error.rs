@@ -0,0 +1,553 @@
+use std::{
+    cell::RefCell,
+    fmt::{Debug, Display},
+};
+
+use nom::{
+    bytes::complete::tag,
+    error::{ContextError, ErrorKind, FromExternalError, ParseError},
+    Err, IResult, InputTake,
+};
+use nom_locate::LocatedSpan;
+
+use super::diagnostic::{Diagnostic, SourceSpan};
+
+/// The input of all network parsers. A span is a slice of the network
+/// file, which keeps track of its' offset, line and column in the file.
+pub type Span<'a> = LocatedSpan<&'a str, Recovery<'a>>;
+
+/// Creates a span over an entire source text, which does not recover
+/// from errors.
+pub fn span(source: &str) -> Span<'_> {
+    Span::new_extra(source, Recovery::default())
+}
+
+/// The region of `length` bytes at the beginning of `input`.
+pub fn source_span(input: Span, length: usize) -> SourceSpan {
+    SourceSpan::new(
+        input.location_offset(),
+        length,
+        input.location_line(),
+        input.get_utf8_column(),
+    )
+}
+
+/// The keywords, which begin a top-level declaration. Lines starting
+/// with one of them are the synchronisation points of the error recovery.
+pub const DECLARATION_KEYWORDS: [&str; 8] = [
+    "import", "struct", "option", "enum", "flags", "type", "const", "protocol",
+];
+
+/// The error recovery state, which is shared by all spans of a parse.
+///
+/// If the parse is recovering, the errors of invalid declarations and
+/// members are collected and the parser continues after the next `;`,
+/// `}` or declaration keyword, so every error of a file can be reported
+/// at once.
+#[derive(Clone, Copy, Default)]
+pub struct Recovery<'a> {
+    errors: Option<&'a RefCell<Vec<NetworkError<'a>>>>,
+}
+
+impl<'a> Recovery<'a> {
+    /// A recovering parse, which collects its' errors in `errors`.
+    pub fn new(errors: &'a RefCell<Vec<NetworkError<'a>>>) -> Self {
+        Self {
+            errors: Some(errors),
+        }
+    }
+
+    pub fn is_recovering(&self) -> bool {
+        self.errors.is_some()
+    }
+
+    /// Collects the error, if the parse is recovering.
+    pub fn report(&self, error: NetworkError<'a>) {
+        if let Some(errors) = self.errors {
+            errors.borrow_mut().push(error);
+        }
+    }
+
+    /// The number of collected errors.
+    fn reported(&self) -> usize {
+        self.errors.map_or(0, |errors| errors.borrow().len())
+    }
+
+    /// Adds the declaration to the context of the errors, which were
+    /// collected after the first `from` errors.
+    fn add_context(&self, from: usize, context: &str) {
+        if let Some(errors) = self.errors {
+            for error in errors.borrow_mut().iter_mut().skip(from) {
+                error.context.push(context.to_owned());
+            }
+        }
+    }
+}
+
+// the errors are left out, since they contain spans of the same parse
+impl Debug for Recovery<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Recovery")
+            .field("recovering", &self.is_recovering())
+            .finish()
+    }
+}
+
+/// The result of all network parsers.
+pub type ParseResult<'a, O> = IResult<Span<'a>, O, NetworkError<'a>>;
+
+/// A description of the input, which a parser expected to read.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Expected {
+    /// A symbol or keyword, such as `;` or `struct`.
+    Symbol(&'static str),
+    Identifier,
+    Integer,
+    Expression,
+    Type,
+    Declaration,
+    EndOfFile,
+    /// A parser error, that was not described.
+    Kind(ErrorKind),
+}
+
+impl Display for Expected {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Expected::Symbol(symbol) => write!(f, "`{symbol}`"),
+            Expected::Identifier => write!(f, "identifier"),
+            Expected::Integer => write!(f, "integer"),
+            Expected::Expression => write!(f, "expression"),
+            Expected::Type => write!(f, "type"),
+            Expected::Declaration => write!(f, "declaration"),
+            Expected::EndOfFile => write!(f, "end of file"),
+            Expected::Kind(kind) => write!(f, "{}", kind.description()),
+        }
+    }
+}
+
+/// The error of all network parsers. It records where the error occurred,
+/// what was expected at that location and in which declarations the parser
+/// was, innermost first.
+///
+/// Errors of alternatives at the same location are merged, so the error
+/// lists every expected input.
+#[derive(Debug, PartialEq, Clone)]
+pub struct NetworkError<'a> {
+    input: Span<'a>,
+    expected: Vec<Expected>,
+    message: Option<String>,
+    context: Vec<String>,
+}
+
+impl<'a> NetworkError<'a> {
+    /// An error at the location of `input`, which expected something else.
+    pub fn expected(input: Span<'a>, expected: Expected) -> Self {
+        Self {
+            input,
+            expected: vec![expected],
+            message: None,
+            context: Vec::new(),
+        }
+    }
+
+    /// An error for input that could be read, but is invalid, such as
+    /// overlapping flags.
+    pub fn invalid(input: Span<'a>, message: String) -> Self {
+        Self {
+            input,
+            expected: Vec::new(),
+            message: Some(message),
+            context: Vec::new(),
+        }
+    }
+
+    /// The location of the error.
+    pub fn input(&self) -> Span<'a> {
+        self.input
+    }
+
+    pub fn expected_inputs(&self) -> &[Expected] {
+        self.expected.as_slice()
+    }
+
+    /// The declarations the parser was in, innermost first.
+    pub fn context(&self) -> &[String] {
+        self.context.as_slice()
+    }
+
+    /// Adds the declaration, in which the error occurred.
+    pub fn with_context(mut self, context: String) -> Self {
+        self.context.push(context);
+        self
+    }
+
+    /// The text the parser found instead of the expected input, which
+    /// is a word or a single character.
+    fn found(&self) -> &'a str {
+        let fragment: &'a str = self.input.fragment();
+        let word = fragment
+            .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .unwrap_or(fragment.len());
+
+        match word {
+            0 => fragment
+                .chars()
+                .next()
+                .map_or("", |c| &fragment[..c.len_utf8()]),
+            len => &fragment[..len],
+        }
+    }
+
+    /// A human readable description of the error.
+    pub fn message(&self) -> String {
+        if let Some(message) = &self.message {
+            return message.clone();
+        }
+
+        let mut expected = self
+            .expected
+            .iter()
+            .filter(|expected| !matches!(expected, Expected::Kind(_)))
+            .map(Expected::to_string)
+            .collect::<Vec<_>>();
+
+        let found = match self.found() {
+            "" => "end of file".to_owned(),
+            found => format!("`{found}`"),
+        };
+
+        match expected.pop() {
+            None => format!("unexpected {found}"),
+            Some(last) if expected.is_empty() => format!("expected {last}, found {found}"),
+            Some(last) => format!("expected {} or {last}, found {found}", expected.join(", ")),
+        }
+    }
+
+    /// Converts the error into a diagnostic, that can be rendered with
+    /// the source file.
+    pub fn to_diagnostic(&self) -> Diagnostic {
+        let found = self.found();
+        let span = source_span(self.input, found.len());
+
+        let label = match (&self.message, found) {
+            (Some(_), _) => None,
+            (None, "") => Some("unexpected end of file".to_owned()),
+            (None, found) => Some(format!("unexpected `{found}`")),
+        };
+
+        let mut diagnostic = Diagnostic::error(self.message(), span);
+
+        if let Some(label) = label {
+            diagnostic = diagnostic.with_label(label);
+        }
+
+        if !self.context.is_empty() {
+            diagnostic = diagnostic.with_note(format!("in {}", self.context.join(" of ")));
+        }
+
+        diagnostic
+    }
+}
+
+impl<'a> ParseError<Span<'a>> for NetworkError<'a> {
+    fn from_error_kind(input: Span<'a>, kind: ErrorKind) -> Self {
+        Self::expected(input, Expected::Kind(kind))
+    }
+
+    fn append(_input: Span<'a>, _kind: ErrorKind, other: Self) -> Self {
+        other
+    }
+
+    /// Keeps the error that advanced further into the input, merging the
+    /// expected inputs if both failed at the same location.
+    fn or(mut self, other: Self) -> Self {
+        let offset = self.input.location_offset();
+        let other_offset = other.input.location_offset();
+
+        if other_offset > offset
+            || (other_offset == offset && self.message.is_none() && other.message.is_some())
+        {
+            return other;
+        }
+
+        if other_offset == offset && self.message.is_none() {
+            for expected in other.expected {
+                if !self.expected.contains(&expected) {
+                    self.expected.push(expected);
+                }
+            }
+        }
+
+        self
+    }
+}
+
+impl<'a> ContextError<Span<'a>> for NetworkError<'a> {
+    fn add_context(_input: Span<'a>, context: &'static str, other: Self) -> Self {
+        other.with_context(context.to_owned())
+    }
+}
+
+impl<'a, E> FromExternalError<Span<'a>, E> for NetworkError<'a> {
+    fn from_external_error(input: Span<'a>, kind: ErrorKind, _e: E) -> Self {
+        Self::from_error_kind(input, kind)
+    }
+}
+
+/// Reads a symbol or keyword. On failure the error expects the symbol.
+pub fn symbol<'a>(symbol: &'static str) -> impl FnMut(Span<'a>) -> ParseResult<'a, Span<'a>> {
+    move |input: Span<'a>| {
+        tag(symbol)(input).map_err(|_: Err<NetworkError>| {
+            Err::Error(NetworkError::expected(input, Expected::Symbol(symbol)))
+        })
+    }
+}
+
+/// Reads a keyword, which must not be followed by further identifier
+/// characters, so `type` does not match the beginning of `types`.
+pub fn keyword<'a>(keyword: &'static str) -> impl FnMut(Span<'a>) -> ParseResult<'a, Span<'a>> {
+    move |input: Span<'a>| {
+        let (rest, matched) = symbol(keyword)(input)?;
+
+        if rest
+            .fragment()
+            .starts_with(|c: char| c.is_alphanumeric() || c == '_')
+        {
+            return Err(Err::Error(NetworkError::expected(
+                input,
+                Expected::Symbol(keyword),
+            )));
+        }
+
+        Ok((rest, matched))
+    }
+}
+
+/// Replaces the expected inputs of a recoverable error from `parser`.
+pub fn expect<'a, O, P>(
+    expected: Expected,
+    mut parser: P,
+) -> impl FnMut(Span<'a>) -> ParseResult<'a, O>
+where
+    P: FnMut(Span<'a>) -> ParseResult<'a, O>,
+{
+    move |input: Span<'a>| match parser(input) {
+        Err(Err::Error(_)) => Err(Err::Error(NetworkError::expected(input, expected.clone()))),
+        result => result,
+    }
+}
+
+/// Adds a declaration to the context of any error from `parser`, for
+/// example "field `author`" or "struct `Post`".
+pub fn in_context<'a, O, P>(
+    context: String,
+    mut parser: P,
+) -> impl FnMut(Span<'a>) -> ParseResult<'a, O>
+where
+    P: FnMut(Span<'a>) -> ParseResult<'a, O>,
+{
+    move |input: Span<'a>| {
+        let reported = input.extra.reported();
+        let result =
+            parser(input).map_err(|err| err.map(|error| error.with_context(context.clone())));
+
+        // the errors of skipped members are in the declaration as well
+        input.extra.add_context(reported, &context);
+        result
+    }
+}
+
+/// Whether a line, without its' indentation, starts with a declaration
+/// keyword followed by a name, so members named like a keyword are not
+/// mistaken for a declaration.
+fn starts_declaration(line: &str) -> bool {
+    DECLARATION_KEYWORDS.iter().any(|keyword| {
+        line.strip_prefix(keyword).is_some_and(|rest| {
+            let name = rest.trim_start_matches([' ', '\t']);
+            name.len() < rest.len()
+                && name.starts_with(|c: char| c.is_alphabetic() || c == '_' || c == '"')
+        })
+    })
+}
+
+/// Skips an invalid declaration, which begins at `input` and failed at
+/// `error`. The input continues at the next line starting with a
+/// declaration keyword, which is not before the error, or at the end of
+/// the file.
+pub fn skip_declaration<'a>(input: Span<'a>, error: Span<'a>) -> Span<'a> {
+    let fragment: &'a str = input.fragment();
+    let error_offset = error
+        .location_offset()
+        .saturating_sub(input.location_offset());
+    let mut line_start = 0;
+
+    for line in fragment.split_inclusive('\n') {
+        let indentation = line.len() - line.trim_start_matches([' ', '\t']).len();
+        let begin = line_start + indentation;
+
+        if begin > 0 && begin >= error_offset && starts_declaration(&line[indentation..]) {
+            return input.take_split(begin).0;
+        }
+
+        line_start += line.len();
+    }
+
+    input.take_split(fragment.len()).0
+}
+
+/// Skips an invalid member of a declaration, which failed at `error`.
+/// The input continues after the next semicolon, before the closing brace
+/// of the declaration or at the next line starting with a declaration
+/// keyword, whichever comes first.
+fn skip_member(error: Span) -> Span {
+    let fragment: &str = error.fragment();
+
+    if starts_declaration(fragment) {
+        return error;
+    }
+
+    let skipped = fragment
+        .char_indices()
+        .find_map(|(idx, c)| match c {
+            ';' => Some(idx + 1),
+            '}' => Some(idx),
+            '\n' if starts_declaration(fragment[idx + 1..].trim_start_matches([' ', '\t'])) => {
+                Some(idx + 1)
+            }
+            _ => None,
+        })
+        .unwrap_or(fragment.len());
+
+    error.take_split(skipped).0
+}
+
+/// Reads the members of a declaration with `parser` until it returns a
+/// recoverable error, like [many0](nom::multi::many0), or the next
+/// declaration begins. If the parse is recovering, invalid members are
+/// reported and skipped, otherwise their' error is returned.
+pub fn members<'a, O, P>(mut parser: P) -> impl FnMut(Span<'a>) -> ParseResult<'a, Vec<O>>
+where
+    P: FnMut(Span<'a>) -> ParseResult<'a, O>,
+{
+    move |mut input: Span<'a>| {
+        let mut members = Vec::new();
+
+        loop {
+            // a declaration ends the members of an unclosed declaration
+            if starts_declaration(input.fragment().trim_start()) {
+                return Ok((input, members));
+            }
+
+            match parser(input) {
+                Ok((tmp_input, _)) if tmp_input == input => {
+                    return Err(Err::Error(NetworkError::from_error_kind(
+                        input,
+                        ErrorKind::Many0,
+                    )));
+                }
+                Ok((tmp_input, member)) => {
+                    input = tmp_input;
+                    members.push(member);
+                }
+                Err(Err::Error(_)) => return Ok((input, members)),
+                Err(Err::Failure(error)) if input.extra.is_recovering() => {
+                    let tmp_input = skip_member(error.input());
+
+                    // an error without progress can not be skipped
+                    if tmp_input.location_offset() <= input.location_offset() {
+                        return Err(Err::Failure(error));
+                    }
+
+                    input.extra.report(error);
+                    input = tmp_input;
+                }
+                Err(error) => return Err(error),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod error_test {
+    use super::*;
+    use nom::{
+        branch::alt,
+        character::complete::{alpha1, multispace0},
+        combinator::cut,
+        sequence::{delimited, terminated},
+    };
+
+    #[test]
+    fn expected_symbol() {
+        let error = match symbol(";")(span("}")) {
+            Err(Err::Error(error)) => error,
+            _ => panic!("expected an error"),
+        };
+        assert_eq!(error.expected_inputs(), &[Expected::Symbol(";")]);
+        assert_eq!(error.message(), "expected `;`, found `}`");
+    }
+
+    /// Alternatives failing at the same location list all expected inputs.
+    #[test]
+    fn merged_alternatives() {
+        let error = match alt((symbol("struct"), symbol("enum"), symbol("type")))(span("strcut")) {
+            Err(Err::Error(error)) => error,
+            _ => panic!("expected an error"),
+        };
+        assert_eq!(
+            error.message(),
+            "expected `struct`, `enum` or `type`, found `strcut`"
+        );
+    }
+
+    #[test]
+    fn keyword_boundary() {
+        assert!(keyword("type")(span("type Name")).is_ok());
+        assert!(keyword("type")(span("types")).is_err());
+    }
+
+    #[test]
+    fn end_of_file() {
+        let error = NetworkError::expected(span(""), Expected::Identifier);
+        assert_eq!(error.message(), "expected identifier, found end of file");
+    }
+
+    #[test]
+    fn context() {
+        let error = match in_context("struct `Foo`".to_owned(), symbol("{"))(span("(")) {
+            Err(Err::Error(error)) => error,
+            _ => panic!("expected an error"),
+        };
+        assert_eq!(error.context(), &["struct `Foo`".to_owned()]);
+    }
+
+    /// Invalid members are skipped to the next semicolon, if the parse
+    /// is recovering.
+    #[test]
+    fn recovering_members() {
+        let errors = RefCell::new(Vec::new());
+        let mut member = members(delimited(multispace0, alpha1, cut(symbol(";"))));
+        assert!(matches!(member(span("a; b!; c;")), Err(Err::Failure(_))));
+
+        let (input, names) = member(Span::new_extra("a; b!; c;", Recovery::new(&errors))).unwrap();
+        assert_eq!(*input.fragment(), "");
+        assert_eq!(
+            names
+                .iter()
+                .map(|name| *name.fragment())
+                .collect::<Vec<_>>(),
+            vec!["a", "c"]
+        );
+        assert_eq!(errors.borrow()[0].message(), "expected `;`, found `!`");
+    }
+
+    /// An invalid declaration is skipped to the next line, which starts
+    /// with a declaration keyword.
+    #[test]
+    fn skipped_declaration() {
+        let input = span("strcut A {\n    type: u8;\n}\n\nstruct B {}");
+        let error = terminated(alpha1, symbol(" "))(input).unwrap().0;
+        assert_eq!(*skip_declaration(input, error).fragment(), "struct B {}");
+    }
+}
@@ -0,0 +1,599 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+};
+
+use super::{
+    constant::{ConstExpression, InterpolationPart, NetworkConst, TypeLimit},
+    expression::{TypeExpression, TypeKind},
+    integer_primitive, integer_range,
+    network::NetworkFileReader,
+};
+
+/// The value of a folded constant expression.
+#[derive(Debug, PartialEq, Clone)]
+pub enum ConstValue {
+    Integer(i128),
+    Float(f64),
+    String(String),
+}
+
+impl Display for ConstValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Integer(i) => write!(f, "{i}"),
+            Self::Float(x) => write!(f, "{x:?}"),
+            Self::String(s) => write!(f, "{s}"),
+        }
+    }
+}
+
+/// The reasons a constant can not be evaluated.
+#[derive(Debug, PartialEq, Clone)]
+pub enum ConstError {
+    /// The expression references a constant or variable that does not exist.
+    UnknownReference { constant: String, reference: String },
+    /// The constant references itself, possibly through other constants.
+    CyclicReference { constant: String },
+    /// `Type.MAX` or `Type.MIN` was used on a type without numeric limits.
+    UnknownLimit { constant: String, type_name: String },
+    /// `Type.MAX` of an unsigned 128-bit integer, which exceeds the
+    /// values a constant can hold.
+    LimitOverflow { constant: String, type_name: String },
+    /// The value does not fit the declared type.
+    TypeMismatch {
+        constant: String,
+        type_name: String,
+        value: ConstValue,
+    },
+}
+
+impl ConstError {
+    /// The name of the constant, which can not be evaluated.
+    pub fn constant(&self) -> &str {
+        match self {
+            Self::UnknownReference { constant, .. }
+            | Self::CyclicReference { constant }
+            | Self::UnknownLimit { constant, .. }
+            | Self::LimitOverflow { constant, .. }
+            | Self::TypeMismatch { constant, .. } => constant.as_str(),
+        }
+    }
+}
+
+impl Display for ConstError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownReference {
+                constant,
+                reference,
+            } => write!(f, "the constant `{constant}` references the unknown `{reference}`"),
+            Self::CyclicReference { constant } => {
+                write!(f, "the constant `{constant}` references itself")
+            }
+            Self::UnknownLimit {
+                constant,
+                type_name,
+            } => write!(
+                f,
+                "the constant `{constant}` reads the limit of `{type_name}`, which is no numeric type"
+            ),
+            Self::LimitOverflow {
+                constant,
+                type_name,
+            } => write!(
+                f,
+                "the constant `{constant}` reads the maximum of `{type_name}`, which does not fit a constant"
+            ),
+            Self::TypeMismatch {
+                constant,
+                type_name,
+                value,
+            } => write!(
+                f,
+                "the constant `{constant}` of type `{type_name}` can not hold the value `{value}`"
+            ),
+        }
+    }
+}
+
+/// Returns the sign and the amount of bits of an integer primitive or of
+/// a sized integer, such as `u(12)`.
+fn integer_type(type_expression: &TypeExpression) -> Option<(bool, u32)> {
+    match type_expression.kind() {
+        TypeKind::Integer { signed, bits } => Some((*signed, *bits)),
+        TypeKind::Named(name) => integer_primitive(name),
+        _ => None,
+    }
+}
+
+/// Returns the range of an integer primitive or of a sized integer.
+fn integer_bounds(type_expression: &TypeExpression) -> Option<(i128, i128)> {
+    integer_type(type_expression).map(|(signed, bits)| integer_range(signed, bits))
+}
+
+/// Returns the range of finite values of a floating point primitive.
+fn float_range(type_name: &str) -> Option<(f64, f64)> {
+    match type_name {
+        "f32" => Some((f32::MIN as f64, f32::MAX as f64)),
+        "f64" => Some((f64::MIN, f64::MAX)),
+        _ => None,
+    }
+}
+
+/// Whether the integer converts into the floating point primitive
+/// without rounding.
+fn exact_float(type_name: &str, i: i128) -> bool {
+    match type_name {
+        "f32" => i as f32 as i128 == i,
+        "f64" => i as f64 as i128 == i,
+        _ => false,
+    }
+}
+
+/// The constant evaluator folds the constant expressions of a network
+/// file into values and checks them against their declared types.
+/// Besides the declared constants, expressions can reference built-in
+/// variables, like `NETZ_VERSION`.
+///
+/// ```
+/// use network_parser::parser::{
+///     evaluate::{ConstEvaluator, ConstValue},
+///     interface::NetworkParser,
+///     network::NetworkFileReader,
+/// };
+///
+/// let (_, file) = NetworkFileReader::parse_str("const MAGIC: u32 = u32.MAX;").unwrap();
+/// let values = ConstEvaluator::new(&file).evaluate().unwrap();
+/// assert_eq!(values["MAGIC"], ConstValue::Integer(u32::MAX as i128));
+/// ```
+pub struct ConstEvaluator<'a> {
+    file: &'a NetworkFileReader,
+    variables: HashMap<String, ConstValue>,
+}
+
+impl<'a> ConstEvaluator<'a> {
+    /// Create an evaluator over a network file with the default built-in
+    /// variables.
+    pub fn new(file: &'a NetworkFileReader) -> Self {
+        let mut variables = HashMap::new();
+        variables.insert(
+            "NETZ_VERSION".to_owned(),
+            ConstValue::String(env!("CARGO_PKG_VERSION").to_owned()),
+        );
+
+        Self { file, variables }
+    }
+
+    /// Register a built-in variable, or override an existing one.
+    pub fn with_variable(mut self, name: &str, value: ConstValue) -> Self {
+        self.variables.insert(name.to_owned(), value);
+        self
+    }
+
+    /// Evaluate every constant of the file.
+    pub fn evaluate(&self) -> Result<HashMap<String, ConstValue>, ConstError> {
+        let mut values = HashMap::new();
+
+        for constant in self.file.consts() {
+            self.evaluate_const(constant, &mut values, &mut vec![])?;
+        }
+
+        Ok(values)
+    }
+
+    /// Evaluate every constant of the file and collect the errors of all
+    /// of them, instead of stopping at the first one. The error of a
+    /// constant is reported once, even if other constants reference it.
+    pub fn errors(&self) -> Vec<ConstError> {
+        let mut values = HashMap::new();
+        let mut errors = Vec::new();
+
+        for constant in self.file.consts() {
+            if let Err(error) = self.evaluate_const(constant, &mut values, &mut vec![]) {
+                if !errors.contains(&error) {
+                    errors.push(error);
+                }
+            }
+        }
+
+        errors
+    }
+
+    /// Evaluate a single constant by name. Returns `None` if the constant
+    /// is not declared.
+    pub fn evaluate_name(&self, name: &str) -> Option<Result<ConstValue, ConstError>> {
+        let constant = self.file.constant(name)?;
+        Some(self.evaluate_const(&constant, &mut HashMap::new(), &mut vec![]))
+    }
+
+    fn evaluate_const(
+        &self,
+        constant: &NetworkConst,
+        values: &mut HashMap<String, ConstValue>,
+        stack: &mut Vec<String>,
+    ) -> Result<ConstValue, ConstError> {
+        if let Some(value) = values.get(constant.name()) {
+            return Ok(value.clone());
+        }
+
+        if stack.iter().any(|name| name == constant.name()) {
+            return Err(ConstError::CyclicReference {
+                constant: constant.name().to_owned(),
+            });
+        }
+
+        stack.push(constant.name().to_owned());
+        let value = self.evaluate_expression(constant, constant.expression(), values, stack)?;
+        stack.pop();
+
+        self.check_type(constant, &value)?;
+        values.insert(constant.name().to_owned(), value.clone());
+        Ok(value)
+    }
+
+    fn evaluate_expression(
+        &self,
+        constant: &NetworkConst,
+        expression: &ConstExpression,
+        values: &mut HashMap<String, ConstValue>,
+        stack: &mut Vec<String>,
+    ) -> Result<ConstValue, ConstError> {
+        match expression {
+            ConstExpression::Integer(i) => Ok(ConstValue::Integer(*i)),
+            ConstExpression::Float(x) => Ok(ConstValue::Float(*x)),
+            ConstExpression::String(s) => Ok(ConstValue::String(s.clone())),
+            ConstExpression::Interpolation(parts) => {
+                let mut output = String::new();
+
+                for part in parts {
+                    match part {
+                        InterpolationPart::Literal(s) => output += s,
+                        InterpolationPart::Variable(name) => {
+                            let value = self.lookup(constant, name, values, stack)?;
+                            output += value.to_string().as_str();
+                        }
+                    }
+                }
+
+                Ok(ConstValue::String(output))
+            }
+            ConstExpression::Limit(type_name, limit) => {
+                let primitive = self.primitive_type(&TypeExpression::named(type_name));
+
+                if let Some((min, max)) = primitive.name().and_then(float_range) {
+                    return Ok(ConstValue::Float(match limit {
+                        TypeLimit::Max => max,
+                        TypeLimit::Min => min,
+                    }));
+                }
+
+                let (signed, bits) =
+                    integer_type(&primitive).ok_or_else(|| ConstError::UnknownLimit {
+                        constant: constant.name().to_owned(),
+                        type_name: type_name.clone(),
+                    })?;
+
+                // the range is capped at `i128::MAX`, which is not the
+                // maximum of an unsigned 128-bit integer
+                if !signed && bits >= 128 && *limit == TypeLimit::Max {
+                    return Err(ConstError::LimitOverflow {
+                        constant: constant.name().to_owned(),
+                        type_name: type_name.clone(),
+                    });
+                }
+
+                let (min, max) = integer_range(signed, bits);
+
+                Ok(ConstValue::Integer(match limit {
+                    TypeLimit::Max => max,
+                    TypeLimit::Min => min,
+                }))
+            }
+            ConstExpression::Reference(name) => self.lookup(constant, name, values, stack),
+        }
+    }
+
+    /// Look up a constant or a built-in variable. Declared constants take
+    /// precedence over the constants of the prelude, which take precedence
+    /// over built-in variables.
+    fn lookup(
+        &self,
+        constant: &NetworkConst,
+        name: &str,
+        values: &mut HashMap<String, ConstValue>,
+        stack: &mut Vec<String>,
+    ) -> Result<ConstValue, ConstError> {
+        let referenced = self.file.constant(name).or_else(|| {
+            self.file
+                .prelude()
+                .and_then(|prelude| prelude.constant(name))
+        });
+
+        if let Some(referenced) = referenced {
+            return self.evaluate_const(&referenced, values, stack);
+        }
+
+        self.variables
+            .get(name)
+            .cloned()
+            .ok_or_else(|| ConstError::UnknownReference {
+                constant: constant.name().to_owned(),
+                reference: name.to_owned(),
+            })
+    }
+
+    /// Resolves the type aliases of a type one alias at a time, as long
+    /// as they resolve to another type name or to a sized integer. The
+    /// chain stops at `string`, so an alias of `string` is a `string`,
+    /// even though `string` itself aliases `u8[]`.
+    fn primitive_type(&self, type_expression: &TypeExpression) -> TypeExpression {
+        let mut visited = HashSet::new();
+        let mut resolved = type_expression.clone();
+
+        while let Some(name) = resolved.name().filter(|name| *name != "string") {
+            let Some(alias) = self.file.visible_alias(name) else {
+                break;
+            };
+            let aliased = alias.aliased();
+
+            if !visited.insert(name.to_owned())
+                || !(aliased.name().is_some() || matches!(aliased.kind(), TypeKind::Integer { .. }))
+            {
+                break;
+            }
+
+            resolved = aliased.clone();
+        }
+
+        resolved
+    }
+
+    /// Check if the value fits into the declared type of the constant.
+    fn check_type(&self, constant: &NetworkConst, value: &ConstValue) -> Result<(), ConstError> {
+        let const_type = constant.const_type();
+        let primitive = self.primitive_type(const_type);
+
+        let fits = match (value, primitive.name().unwrap_or_default()) {
+            (ConstValue::String(_), "string") => true,
+            // Booleans are encoded as `u8`, see the standard library.
+            (ConstValue::Integer(i), "bool") => {
+                let (min, max) = integer_range(false, 8);
+                (min..=max).contains(i)
+            }
+            // Floats are finite, integers have to convert without rounding.
+            (ConstValue::Float(x), name) => float_range(name)
+                .map(|(min, max)| (min..=max).contains(x))
+                .unwrap_or_default(),
+            (ConstValue::Integer(i), name @ ("f32" | "f64")) => exact_float(name, *i),
+            (ConstValue::Integer(i), _) => integer_bounds(&primitive)
+                .map(|(min, max)| (min..=max).contains(i))
+                .unwrap_or_default(),
+            _ => false,
+        };
+
+        if fits {
+            Ok(())
+        } else {
+            Err(ConstError::TypeMismatch {
+                constant: constant.name().to_owned(),
+                type_name: const_type.to_string(),
+                value: value.clone(),
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod evaluate_test {
+    use super::*;
+    use crate::parser::interface::NetworkParser;
+
+    fn evaluate(file: &str) -> Result<HashMap<String, ConstValue>, ConstError> {
+        let (input, file) = NetworkFileReader::parse_str(file).unwrap();
+        assert_eq!(*input.fragment(), "");
+        ConstEvaluator::new(&file).evaluate()
+    }
+
+    #[test]
+    fn integer_consts() {
+        let values =
+            evaluate("const A: u8 = 0xFF;\nconst B: i8 = i8.MIN;\nconst C: u64 = u64.MAX;\n")
+                .unwrap();
+        assert_eq!(values["A"], ConstValue::Integer(255));
+        assert_eq!(values["B"], ConstValue::Integer(-128));
+        assert_eq!(values["C"], ConstValue::Integer(u64::MAX as i128));
+    }
+
+    #[test]
+    fn out_of_range() {
+        assert_eq!(
+            evaluate("const X: u8 = 300;"),
+            Err(ConstError::TypeMismatch {
+                constant: "X".to_owned(),
+                type_name: "u8".to_owned(),
+                value: ConstValue::Integer(300),
+            })
+        );
+        assert!(evaluate("const X: u16 = -1;").is_err());
+        assert!(evaluate("const X: i32 = u32.MAX;").is_err());
+    }
+
+    #[test]
+    fn all_errors() {
+        let (_, file) = NetworkFileReader::parse_str(
+            "const X: u8 = 300;\nconst Y: u16 = Z;\nconst W: u8 = X;\nconst V: u8 = 1;",
+        )
+        .unwrap();
+        let errors = ConstEvaluator::new(&file).errors();
+        assert_eq!(
+            errors,
+            [
+                ConstError::TypeMismatch {
+                    constant: "X".to_owned(),
+                    type_name: "u8".to_owned(),
+                    value: ConstValue::Integer(300),
+                },
+                ConstError::UnknownReference {
+                    constant: "Y".to_owned(),
+                    reference: "Z".to_owned(),
+                },
+            ]
+        );
+        assert_eq!(errors[1].constant(), "Y");
+    }
+
+    /// Sized integers are checked against the range of their' bits, also
+    /// through aliases.
+    #[test]
+    fn sized_integers() {
+        let values = evaluate(
+            "type Small = i(4);\nconst A: u(12) = 4095;\nconst B: Small = Small.MIN;\nconst C: i(128) = -170141183460469231731687303715884105728;\nconst D: u(128) = 170141183460469231731687303715884105727;\n",
+        )
+        .unwrap();
+        assert_eq!(values["A"], ConstValue::Integer(4095));
+        assert_eq!(values["B"], ConstValue::Integer(-8));
+        assert_eq!(values["C"], ConstValue::Integer(i128::MIN));
+        assert_eq!(values["D"], ConstValue::Integer(i128::MAX));
+
+        assert!(evaluate("const A: u(12) = 4096;\n").is_err());
+        assert!(evaluate("type Small = i(4);\nconst A: Small = 8;\n").is_err());
+        assert!(evaluate("const A: u(128) = -1;\n").is_err());
+    }
+
+    /// A constant typed by an alias of `string` holds a string, not the
+    /// bytes `string` aliases.
+    #[test]
+    fn string_alias() {
+        let values =
+            evaluate("type Name = string;\ntype Label = Name;\nconst X: Label = \"a\";\n").unwrap();
+        assert_eq!(values["X"], ConstValue::String("a".to_owned()));
+
+        assert!(evaluate("type Name = string;\nconst X: Name = 1;\n").is_err());
+    }
+
+    /// The maximum of an unsigned 128-bit integer does not fit a constant,
+    /// so reading it is an error instead of a capped value.
+    #[test]
+    fn unsigned_128_limit() {
+        assert_eq!(
+            evaluate("type Big = u(128);\nconst M: Big = Big.MAX;\n"),
+            Err(ConstError::LimitOverflow {
+                constant: "M".to_owned(),
+                type_name: "Big".to_owned(),
+            })
+        );
+
+        let values = evaluate("type Big = u(128);\nconst M: Big = Big.MIN;\n").unwrap();
+        assert_eq!(values["M"], ConstValue::Integer(0));
+    }
+
+    #[test]
+    fn type_mismatch() {
+        assert!(evaluate("const X: string = 1;").is_err());
+        assert!(evaluate("const X: u8 = \"1\";").is_err());
+        assert!(evaluate("const X: Unknown = 1;").is_err());
+    }
+
+    #[test]
+    fn references() {
+        let values = evaluate("const A: u16 = B;\nconst B: u8 = 12;\n").unwrap();
+        assert_eq!(values["A"], ConstValue::Integer(12));
+
+        assert_eq!(
+            evaluate("const A: u8 = A;"),
+            Err(ConstError::CyclicReference {
+                constant: "A".to_owned()
+            })
+        );
+        assert!(evaluate("const A: u8 = B;\nconst B: u8 = A;\n").is_err());
+        assert!(evaluate("const A: u8 = MISSING;").is_err());
+        assert!(evaluate("const A: u8 = string.MAX;").is_err());
+    }
+
+    #[test]
+    fn interpolation() {
+        let values = evaluate(
+            "const NAME: string = \"Netz\";\nconst GREETING: string = $\"${NAME} ${NETZ_VERSION}\";\n",
+        )
+        .unwrap();
+        assert_eq!(
+            values["GREETING"],
+            ConstValue::String(format!("Netz {}", env!("CARGO_PKG_VERSION")))
+        );
+    }
+
+    /// Aliased types are checked against the aliased primitive.
+    #[test]
+    fn aliased_consts() {
+        let values =
+            evaluate("type bool = u8;\nconst true: bool = 1;\nconst false: bool = 0;\n").unwrap();
+        assert_eq!(values["true"], ConstValue::Integer(1));
+        assert!(evaluate("type Byte = u8;\nconst X: Byte = 256;\n").is_err());
+    }
+
+    /// The types and constants of the prelude are visible in every file.
+    #[test]
+    fn prelude_consts() {
+        let values = evaluate("const ENABLED: bool = true;\nconst NAME: string = \"netz\";\n").unwrap();
+        assert_eq!(values["ENABLED"], ConstValue::Integer(1));
+        assert_eq!(values["NAME"], ConstValue::String("netz".to_owned()));
+        assert!(evaluate("const X: bool = 256;\n").is_err());
+    }
+
+    #[test]
+    fn float_consts() {
+        let values = evaluate(
+            "const PI: f64 = 3.141592653589793;\nconst HALF: f32 = 0.5;\nconst ONE: f32 = 1;\nconst LARGEST: f32 = f32.MAX;\n",
+        )
+        .unwrap();
+        assert_eq!(values["PI"], ConstValue::Float(std::f64::consts::PI));
+        assert_eq!(values["HALF"], ConstValue::Float(0.5));
+        assert_eq!(values["ONE"], ConstValue::Integer(1));
+        assert_eq!(values["LARGEST"], ConstValue::Float(f32::MAX as f64));
+        assert_eq!(values["LARGEST"].to_string(), "3.4028234663852886e38");
+
+        // out of range, not finite or rounded values do not fit
+        assert!(evaluate("const X: f32 = 1e39;\n").is_err());
+        assert!(evaluate("const X: f64 = 1e999;\n").is_err());
+        assert!(evaluate("const X: f32 = 16777217;\n").is_err());
+        assert!(evaluate("const X: u8 = 1.0;\n").is_err());
+    }
+
+    /// The constants from `consts.net` evaluate with the schema variable.
+    #[test]
+    fn playground_consts() {
+        let file = r#"
+/**
+ * @description Provides the local Netz version. It
+ * can be different accross devices.
+ */
+const VERSION: string = $"Version: ${NETZ_VERSION}";
+
+/**
+ * @description Exposes the Netz schema.
+ */
+const SCHEMA: string = NETZ_SCHEMA;
+
+/**
+ * @description A magic number.
+ */
+const MAGIC: u32 = u32.MAX;
+"#;
+        let (_, file) = NetworkFileReader::parse_str(file).unwrap();
+        let evaluator = ConstEvaluator::new(&file)
+            .with_variable("NETZ_SCHEMA", ConstValue::String("{}".to_owned()));
+
+        let values = evaluator.evaluate().unwrap();
+        assert_eq!(values["MAGIC"], ConstValue::Integer(u32::MAX as i128));
+        assert_eq!(values["SCHEMA"], ConstValue::String("{}".to_owned()));
+        assert_eq!(
+            evaluator.evaluate_name("VERSION"),
+            Some(Ok(ConstValue::String(format!(
+                "Version: {}",
+                env!("CARGO_PKG_VERSION")
+            ))))
+        );
+        assert_eq!(evaluator.evaluate_name("MISSING"), None);
+    }
+}
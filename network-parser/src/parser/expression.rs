@@ -0,0 +1,382 @@
+use std::fmt::Display;
+
+use nom::{
+    branch::alt,
+    character::complete::u32,
+    combinator::{cut, opt},
+    sequence::preceded,
+    Err,
+};
+
+use super::{
+    comment::Comment,
+    diagnostic::SourceSpan,
+    error::{expect, source_span, symbol, Expected, NetworkError, ParseResult, Span},
+    identifier::NetworkIdentifier,
+    interface::NetworkParser,
+};
+
+/// The built-in type constructors, which take their' arguments in round
+/// brackets, such as `i(64)` or `map(string -> Any)`.
+pub const TYPE_CONSTRUCTORS: &[&str] = &["u", "i", "option", "map"];
+
+/// The widest integer, that can be declared with `u(N)` or `i(N)`.
+pub const MAX_INTEGER_BITS: u32 = 128;
+
+/// The shape of a type expression. Nested expressions carry their' own
+/// location in the network file.
+#[derive(Debug, PartialEq, Clone)]
+pub enum TypeKind {
+    /// A declared or primitive type, such as `Author` or `u8`.
+    Named(String),
+    /// An array of the element type, such as `Tag[]`.
+    Array(Box<TypeExpression>),
+    /// A value, which can be absent, such as `option(string)`.
+    Option(Box<TypeExpression>),
+    /// A map from keys to values, such as `map(string -> Any)`.
+    Map(Box<TypeExpression>, Box<TypeExpression>),
+    /// An integer with the amount of bits, such as `u(128)` or `i(64)`.
+    Integer { signed: bool, bits: u32 },
+    /// The empty type `()`, which does not carry any data.
+    Unit,
+}
+
+/// A type expression is the usage of a type inside of a declaration,
+/// like the type of a field or the payload of an enum member. Types are
+/// named, or built from other types with array brackets and the type
+/// constructors `option(T)`, `map(K -> V)`, `u(N)` and `i(N)`.
+///
+/// ```
+/// use network_parser::parser::{expression::TypeExpression, interface::NetworkParser};
+///
+/// let (_, expression) = TypeExpression::parse_str("map(string -> option(u(128)))[]").unwrap();
+/// assert_eq!(expression.array_dimension(), 1);
+/// assert_eq!(expression.to_string(), "map(string -> option(u(128)))[]");
+/// ```
+///
+/// #### Example
+///
+/// ```net
+/// Foo
+/// u8[][]
+/// map(string -> Any)
+/// ```
+#[derive(Debug, Clone)]
+pub struct TypeExpression {
+    kind: TypeKind,
+    span: Option<SourceSpan>,
+}
+
+/// The location is not compared, so a parsed expression equals a
+/// constructed one.
+impl PartialEq for TypeExpression {
+    fn eq(&self, other: &Self) -> bool {
+        self.kind == other.kind
+    }
+}
+
+impl TypeExpression {
+    pub fn new(kind: TypeKind) -> Self {
+        Self { kind, span: None }
+    }
+
+    pub fn named(name: &str) -> Self {
+        Self::new(TypeKind::Named(name.to_owned()))
+    }
+
+    pub fn array(element: Self) -> Self {
+        Self::new(TypeKind::Array(Box::new(element)))
+    }
+
+    pub fn option(inner: Self) -> Self {
+        Self::new(TypeKind::Option(Box::new(inner)))
+    }
+
+    pub fn map(key: Self, value: Self) -> Self {
+        Self::new(TypeKind::Map(Box::new(key), Box::new(value)))
+    }
+
+    pub fn integer(signed: bool, bits: u32) -> Self {
+        Self::new(TypeKind::Integer { signed, bits })
+    }
+
+    pub fn unit() -> Self {
+        Self::new(TypeKind::Unit)
+    }
+
+    pub fn kind(&self) -> &TypeKind {
+        &self.kind
+    }
+
+    /// The location of the entire expression, if it was read from a
+    /// network file.
+    pub fn span(&self) -> Option<SourceSpan> {
+        self.span
+    }
+
+    /// The type name, if the expression is a named type.
+    pub fn name(&self) -> Option<&str> {
+        match &self.kind {
+            TypeKind::Named(name) => Some(name.as_str()),
+            _ => None,
+        }
+    }
+
+    /// The element type of an array, which is not an array itself. Any
+    /// other expression is its' own element.
+    pub fn element(&self) -> &Self {
+        match &self.kind {
+            TypeKind::Array(element) => element.element(),
+            _ => self,
+        }
+    }
+
+    /// The dimension of the array, 0 if none.
+    pub fn array_dimension(&self) -> usize {
+        match &self.kind {
+            TypeKind::Array(element) => element.array_dimension() + 1,
+            _ => 0,
+        }
+    }
+
+    /// Wraps the expression into the given amount of array dimensions.
+    pub fn with_array_dimension(self, array_dimension: usize) -> Self {
+        (0..array_dimension).fold(self, |expression, _| Self::array(expression))
+    }
+
+    /// An expression, that spans from the beginning of `start` to the
+    /// location of `end`.
+    fn spanning(kind: TypeKind, start: SourceSpan, end: Span) -> Self {
+        Self {
+            kind,
+            span: Some(SourceSpan::new(
+                start.offset(),
+                end.location_offset() - start.offset(),
+                start.line(),
+                start.column(),
+            )),
+        }
+    }
+
+    /// Reads the array brackets after the element type. Every pair of
+    /// brackets adds a dimension.
+    pub(crate) fn arrays(mut input: Span, mut element: Self) -> ParseResult<Self> {
+        let start = element.span.unwrap_or_else(|| source_span(input, 0));
+
+        while let (rest, Some(_)) = opt(preceded(Comment::parse, symbol("[]")))(input)? {
+            element = Self::spanning(TypeKind::Array(Box::new(element)), start, rest);
+            input = rest;
+        }
+
+        ParseResult::Ok((input, element))
+    }
+
+    /// Reads the unit type `()`.
+    fn unit_type(input: Span) -> ParseResult<Self> {
+        let start = source_span(input, 0);
+        let (input, _) = symbol("(")(input)?;
+        let (input, _) = Comment::parse(input)?;
+        let (input, _) = symbol(")")(input)?;
+
+        ParseResult::Ok((input, Self::spanning(TypeKind::Unit, start, input)))
+    }
+
+    /// Reads a type name, or a type constructor with its' arguments in
+    /// round brackets.
+    fn constructed(input: Span) -> ParseResult<Self> {
+        let declaration = input;
+        let (input, name) = NetworkIdentifier::parse(input)?;
+        let start = name.span;
+
+        // a name followed by round brackets is a type constructor
+        let (input, brackets) = opt(preceded(Comment::parse, symbol("(")))(input)?;
+
+        if brackets.is_none() {
+            return ParseResult::Ok((input, name.into()));
+        }
+
+        let (input, kind) = match name.identity.as_str() {
+            "option" => {
+                let (input, inner) = cut(Self::parse)(input)?;
+                (input, TypeKind::Option(Box::new(inner)))
+            }
+            "map" => {
+                let (input, key) = cut(Self::parse)(input)?;
+                let (input, _) = Comment::parse(input)?;
+                let (input, _) = cut(symbol("->"))(input)?;
+                let (input, value) = cut(Self::parse)(input)?;
+                (input, TypeKind::Map(Box::new(key), Box::new(value)))
+            }
+            constructor @ ("u" | "i") => {
+                let (input, _) = Comment::parse(input)?;
+                let (rest, bits) = cut(expect(Expected::Integer, u32))(input)?;
+
+                if bits == 0 || bits > MAX_INTEGER_BITS {
+                    return ParseResult::Err(Err::Failure(NetworkError::invalid(
+                        input,
+                        format!(
+                            "the integer `{constructor}({bits})` has {bits} bits, expected 1 till {MAX_INTEGER_BITS}"
+                        ),
+                    )));
+                }
+
+                let signed = constructor == "i";
+                (rest, TypeKind::Integer { signed, bits })
+            }
+            constructor => {
+                return ParseResult::Err(Err::Failure(NetworkError::invalid(
+                    declaration,
+                    format!(
+                        "cannot find type constructor `{constructor}`, expected `option`, `map`, `u` or `i`"
+                    ),
+                )));
+            }
+        };
+
+        let (input, _) = Comment::parse(input)?;
+        let (input, _) = cut(symbol(")"))(input)?;
+
+        ParseResult::Ok((input, Self::spanning(kind, start, input)))
+    }
+}
+
+impl From<NetworkIdentifier> for TypeExpression {
+    /// A named type at the location of the identifier.
+    fn from(identifier: NetworkIdentifier) -> Self {
+        Self {
+            kind: TypeKind::Named(identifier.identity),
+            span: Some(identifier.span),
+        }
+    }
+}
+
+impl Display for TypeExpression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.kind {
+            TypeKind::Named(name) => write!(f, "{name}"),
+            TypeKind::Array(element) => write!(f, "{element}[]"),
+            TypeKind::Option(inner) => write!(f, "option({inner})"),
+            TypeKind::Map(key, value) => write!(f, "map({key} -> {value})"),
+            TypeKind::Integer {
+                signed: false,
+                bits,
+            } => write!(f, "u({bits})"),
+            TypeKind::Integer { signed: true, bits } => write!(f, "i({bits})"),
+            TypeKind::Unit => write!(f, "()"),
+        }
+    }
+}
+
+impl NetworkParser for TypeExpression {
+    fn parse(input: Span) -> ParseResult<Self> {
+        let (input, _) = Comment::parse(input)?;
+        let (input, element) =
+            expect(Expected::Type, alt((Self::unit_type, Self::constructed)))(input)?;
+
+        Self::arrays(input, element)
+    }
+}
+
+#[cfg(test)]
+mod expression_test {
+    use super::*;
+
+    #[test]
+    fn named_type() {
+        let (input, expression) = TypeExpression::parse_str("Foo").unwrap();
+        assert_eq!(*input.fragment(), "");
+        assert_eq!(expression.name(), Some("Foo"));
+        assert_eq!(expression.array_dimension(), 0);
+    }
+
+    #[test]
+    fn array_type() {
+        let (input, expression) = TypeExpression::parse_str(" u8 [][]").unwrap();
+        assert_eq!(*input.fragment(), "");
+        assert_eq!(
+            expression,
+            TypeExpression::named("u8").with_array_dimension(2)
+        );
+        assert_eq!(expression.element().name(), Some("u8"));
+        assert_eq!(expression.span().unwrap().offset(), 1);
+        assert_eq!(expression.span().unwrap().length(), 7);
+    }
+
+    #[test]
+    fn nested_constructors() {
+        let (input, expression) =
+            TypeExpression::parse_str("map(string -> option(Any))[]").unwrap();
+        assert_eq!(*input.fragment(), "");
+        assert_eq!(
+            expression,
+            TypeExpression::array(TypeExpression::map(
+                TypeExpression::named("string"),
+                TypeExpression::option(TypeExpression::named("Any")),
+            ))
+        );
+        assert_eq!(expression.span().unwrap().length(), 28);
+
+        let TypeKind::Map(key, value) = expression.element().kind() else {
+            panic!("expected a map");
+        };
+        assert_eq!(key.span().unwrap().offset(), 4);
+        assert_eq!(value.span().unwrap().length(), 11);
+    }
+
+    #[test]
+    fn sized_integers() {
+        let (_, expression) = TypeExpression::parse_str("u( 128 )").unwrap();
+        assert_eq!(expression, TypeExpression::integer(false, 128));
+        assert_eq!(expression.to_string(), "u(128)");
+
+        let (_, expression) = TypeExpression::parse_str("i(64)").unwrap();
+        assert_eq!(
+            expression.kind(),
+            &TypeKind::Integer {
+                signed: true,
+                bits: 64
+            }
+        );
+
+        // without round brackets `u` is a type name
+        assert_eq!(TypeExpression::parse_str("u").unwrap().1.name(), Some("u"));
+    }
+
+    #[test]
+    fn integer_out_of_range() {
+        for source in ["u(0)", "i(129)"] {
+            let Err(Err::Failure(error)) = TypeExpression::parse_str(source) else {
+                panic!("expected a failure for `{source}`");
+            };
+            assert!(error.message().ends_with("bits, expected 1 till 128"));
+        }
+
+        assert!(TypeExpression::parse_str("u(N)").is_err());
+    }
+
+    #[test]
+    fn unit_type() {
+        let (input, expression) = TypeExpression::parse_str("( )").unwrap();
+        assert_eq!(*input.fragment(), "");
+        assert_eq!(expression, TypeExpression::unit());
+        assert_eq!(expression.to_string(), "()");
+    }
+
+    #[test]
+    fn unknown_constructor() {
+        let Err(Err::Failure(error)) = TypeExpression::parse_str("lsit(Any)") else {
+            panic!("expected a failure");
+        };
+        assert_eq!(
+            error.message(),
+            "cannot find type constructor `lsit`, expected `option`, `map`, `u` or `i`"
+        );
+        assert_eq!(error.input().location_offset(), 0);
+    }
+
+    #[test]
+    fn unbalanced_brackets() {
+        assert!(TypeExpression::parse_str("i(64").is_err());
+        assert!(TypeExpression::parse_str("map(string Any)").is_err());
+    }
+}
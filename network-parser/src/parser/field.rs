@@ -1,26 +1,46 @@
 use std::slice::Iter;
 
-use super::{comment::Comment, identifier::NetworkIdentifier, interface::NetworkParser, tag::Tag};
+use super::{
+    comment::Comment,
+    diagnostic::SourceSpan,
+    documentation::Documentation,
+    error::{expect, in_context, symbol, Expected, ParseResult, Span},
+    expression::TypeExpression,
+    identifier::NetworkIdentifier,
+    interface::NetworkParser,
+    tag::Tag,
+};
 use nom::{
-    bytes::complete::tag,
-    multi::{many0, many0_count},
-    IResult,
+    character::complete::u64,
+    combinator::{cut, opt},
+    multi::many0,
+    sequence::{pair, preceded},
 };
+use util_cases::CaseStyles;
 
 /// A struct field is an entry in a structure. It contains
-/// the type and field identifier, and the dimension of the
-/// array, 0 if none.
+/// the field identifier and the type expression.
+///
+/// The field name can be omitted, in which case it is derived
+/// from the type, see [StructField::derive_name]. The fields of
+/// an `option struct` are numbered after an equal sign.
 ///
 /// #### Example
 ///
 /// ```net
-/// @optional FieldType field;
+/// @optional field: FieldType;
+/// Author;
+/// Tag[];
+/// edited: string = 2;
 /// ```
 #[derive(Debug, PartialEq, Clone)]
 pub struct StructField {
-    field_type: String,
+    field_type: TypeExpression,
     field_name: String,
-    array_dimension: usize,
+    span: SourceSpan,
+    shorthand: bool,
+    number: Option<u64>,
+    documentation: Option<Documentation>,
     tags: Vec<Tag>,
 }
 
@@ -37,43 +57,176 @@ impl StructField {
         self.field_name.as_str()
     }
 
-    pub fn field_type(&self) -> &str {
-        &self.field_type.as_str()
+    pub fn field_type(&self) -> &TypeExpression {
+        &self.field_type
+    }
+
+    /// The location of the name in the network file.
+    pub fn span(&self) -> SourceSpan {
+        self.span
+    }
+
+    /// Whether the field was declared by its' type only, so the name
+    /// was derived from the type.
+    pub fn is_shorthand(&self) -> bool {
+        self.shorthand
+    }
+
+    /// The number of the field, which identifies it on the wire, if the
+    /// field is declared in an `option struct`.
+    pub fn number(&self) -> Option<u64> {
+        self.number
+    }
+
+    /// Derives the name of a field, which was declared by its' type only.
+    /// The name is the type name in snake case, so `HTTPRequest` becomes
+    /// `http_request`. The name of an array is pluralized like English
+    /// nouns:
+    ///
+    /// - a name ending in `s`, `x`, `z`, `ch` or `sh` ends in `es`,
+    /// - a name ending in a consonant followed by `y` ends in `ies`,
+    /// - any other name ends in `s`.
+    ///
+    /// Arrays of more than one dimension are pluralized once.
+    ///
+    /// ```
+    /// use network_parser::parser::field::StructField;
+    ///
+    /// assert_eq!(StructField::derive_name("Author", 0), "author");
+    /// assert_eq!(StructField::derive_name("PostTag", 1), "post_tags");
+    /// assert_eq!(StructField::derive_name("Category", 1), "categories");
+    /// assert_eq!(StructField::derive_name("Address", 2), "addresses");
+    /// ```
+    pub fn derive_name(type_name: &str, array_dimension: usize) -> String {
+        let name = type_name.to_snake_case();
+
+        if array_dimension == 0 {
+            return name;
+        }
+
+        let consonant_y = name.strip_suffix('y').filter(|stem| {
+            stem.ends_with(|c: char| c.is_ascii_alphabetic() && !"aeiou".contains(c))
+        });
+
+        match consonant_y {
+            Some(stem) => format!("{stem}ies"),
+            None if ["s", "x", "z", "ch", "sh"]
+                .iter()
+                .any(|suffix| name.ends_with(suffix)) =>
+            {
+                format!("{name}es")
+            }
+            None => format!("{name}s"),
+        }
+    }
+
+    pub fn documentation(&self) -> Option<&Documentation> {
+        self.documentation.as_ref()
     }
 
     pub fn tags(&self) -> Iter<'_, Tag> {
         self.tags.iter()
     }
+
+    /// Reads the optional field number after an equal sign and the
+    /// closing semicolon.
+    fn number_and_end(input: Span) -> ParseResult<Option<u64>> {
+        // read the optional field number, which is required after the
+        // equal sign
+        let (input, _) = Comment::parse(input)?;
+        let (input, number) = opt(preceded(
+            pair(symbol("="), Comment::parse),
+            cut(expect(Expected::Integer, u64)),
+        ))(input)?;
+
+        // read the semicolon
+        let (input, _) = Comment::parse(input)?;
+        let (input, _) = symbol(";")(input)?;
+
+        ParseResult::Ok((input, number))
+    }
+
+    /// Reads the array dimension, the optional field number and the
+    /// closing semicolon of a field, which was declared by its' type only.
+    fn shorthand(
+        input: Span,
+        type_name: NetworkIdentifier,
+    ) -> ParseResult<(TypeExpression, Option<u64>)> {
+        let (input, field_type) = TypeExpression::arrays(input, type_name.into())?;
+        let (input, number) = Self::number_and_end(input)?;
+
+        ParseResult::Ok((input, (field_type, number)))
+    }
+
+    /// Reads the type of the field after the colon, the optional field
+    /// number and the closing semicolon.
+    fn declared_type(input: Span) -> ParseResult<(TypeExpression, Option<u64>)> {
+        let (input, _) = Comment::parse(input)?;
+        let (input, _) = symbol(":")(input)?;
+
+        // read the field type
+        let (input, field_type) = TypeExpression::parse(input)?;
+        let (input, number) = Self::number_and_end(input)?;
+
+        ParseResult::Ok((input, (field_type, number)))
+    }
 }
 
 impl NetworkParser for StructField {
-    fn parse(input: &str) -> IResult<&str, Self> {
-        // read optionally several tags
+    fn parse(input: Span) -> ParseResult<Self> {
+        let (input, comment) = Comment::parse(input)?;
+
+        // read optionally several tags, including those in the documentation
         let (input, tags) = many0(Tag::parse)(input)?;
+        let tags = [comment.read_tags(), tags].concat();
 
-        // read the field name
+        // read the field name, after which the declaration is expected
+        // to be complete
         let (input, field_name) = NetworkIdentifier::parse(input)?;
 
-        let (input, _) = Comment::parse(input)?;
-        let (input, _) = tag(":")(input)?;
+        // a field declared by its' type only derives the name from the type,
+        // the type is followed by array brackets, a field number or the
+        // semicolon, so the errors after them belong to the shorthand
+        let (rest, _) = Comment::parse(input)?;
 
-        // read the field type
-        let (input, field_type) = NetworkIdentifier::parse(input)?;
-        let (input, _) = Comment::parse(input)?;
+        if rest.fragment().starts_with(['[', '=', ';']) {
+            let context = format!("field `{}`", Self::derive_name(&field_name.identity, 0));
+            let (input, (field_type, number)) = in_context(
+                context,
+                cut(|input| Self::shorthand(input, field_name.clone())),
+            )(input)?;
 
-        // read the array dimension
-        let (input, _) = Comment::parse(input)?;
-        let (input, array_dimension) = many0_count(tag("[]"))(input)?;
+            return ParseResult::Ok((
+                input,
+                StructField {
+                    field_name: Self::derive_name(
+                        &field_name.identity,
+                        field_type.array_dimension(),
+                    ),
+                    field_type,
+                    span: field_name.span,
+                    shorthand: true,
+                    number,
+                    documentation: comment.read_documentation(),
+                    tags,
+                },
+            ));
+        }
 
-        // read the semicolon
-        let (input, _) = tag(";")(input)?;
+        let context = format!("field `{}`", field_name.identity);
 
-        IResult::Ok((
+        let (input, (field_type, number)) =
+            in_context(context, cut(Self::declared_type))(input)?;
+
+        ParseResult::Ok((
             input,
             StructField {
-                field_type: field_type.identity,
+                field_type,
                 field_name: field_name.identity,
-                array_dimension,
+                span: field_name.span,
+                shorthand: false,
+                number,
+                documentation: comment.read_documentation(),
                 tags,
             },
         ))
@@ -86,49 +239,121 @@ mod field_test {
 
     #[test]
     fn simple_field() {
-        let (_, field) = StructField::parse("field: Field;").unwrap();
-        assert_eq!(field.field_type, "Field");
+        let (_, field) = StructField::parse_str("field: Field;").unwrap();
+        assert_eq!(field.field_type.name(), Some("Field"));
         assert_eq!(field.field_name, "field");
-        assert_eq!(field.array_dimension, 0);
+        assert_eq!(field.field_type.array_dimension(), 0);
         assert_eq!(field.tags.len(), 0);
     }
 
     #[test]
     fn many_spaces() {
-        let (_, field) = StructField::parse("   name   :   string   ;   ").unwrap();
-        assert_eq!(field.field_type, "string");
+        let (_, field) = StructField::parse_str("   name   :   string   ;   ").unwrap();
+        assert_eq!(field.field_type.name(), Some("string"));
         assert_eq!(field.field_name, "name");
     }
 
     #[test]
     fn documented_field() {
-        let (_, field) = StructField::parse("/* Good documentation. */ field: Field;").unwrap();
-        assert_eq!(field.field_type, "Field");
+        let (_, field) = StructField::parse_str("/* Good documentation. */ field: Field;").unwrap();
+        assert_eq!(field.field_type.name(), Some("Field"));
         assert_eq!(field.field_name, "field");
+        assert_eq!(
+            field.documentation().map(Documentation::description),
+            Some("Good documentation.")
+        );
     }
 
     #[test]
     fn field_array() {
-        let (_, field) = StructField::parse("name: string[];").unwrap();
-        assert_eq!(field.field_type, "string");
+        let (_, field) = StructField::parse_str("name: string[];").unwrap();
+        assert_eq!(
+            field.field_type,
+            TypeExpression::named("string").with_array_dimension(1)
+        );
         assert_eq!(field.field_name, "name");
-        assert_eq!(field.array_dimension, 1);
+        assert_eq!(field.field_type.element().span().unwrap().length(), 6);
     }
 
     #[test]
     fn field_array_two_dimensional() {
-        let (_, field) = StructField::parse("name: string[][];").unwrap();
-        assert_eq!(field.field_type, "string");
+        let (_, field) = StructField::parse_str("name: string[][];").unwrap();
+        assert_eq!(
+            field.field_type,
+            TypeExpression::named("string").with_array_dimension(2)
+        );
         assert_eq!(field.field_name, "name");
-        assert_eq!(field.array_dimension, 2);
+        assert_eq!(field.field_type.element().span().unwrap().length(), 6);
     }
 
     #[test]
     fn deprecated_field() {
-        let (_, field) = StructField::parse("@deprecated b: A;").unwrap();
-        assert_eq!(field.field_type, "A");
+        let (_, field) = StructField::parse_str("@deprecated b: A;").unwrap();
+        assert_eq!(field.field_type.name(), Some("A"));
         assert_eq!(field.field_name, "b");
         assert_eq!(field.tags.len(), 1);
         assert_eq!(field.tags.first().unwrap().name(), "deprecated");
     }
+
+    #[test]
+    fn constructed_field() {
+        let (input, field) = StructField::parse_str("edited: option(string);").unwrap();
+        assert_eq!(*input.fragment(), "");
+        assert_eq!(field.field_name, "edited");
+        assert_eq!(
+            field.field_type,
+            TypeExpression::option(TypeExpression::named("string"))
+        );
+        assert!(!field.is_shorthand());
+    }
+
+    #[test]
+    fn shorthand_field() {
+        let (input, field) = StructField::parse_str("/// The author.\nHTTPRequest;").unwrap();
+        assert_eq!(*input.fragment(), "");
+        assert_eq!(field.field_type.name(), Some("HTTPRequest"));
+        assert_eq!(field.field_name, "http_request");
+        assert!(field.is_shorthand());
+        assert_eq!(
+            field.documentation().map(Documentation::description),
+            Some("The author.")
+        );
+        assert!(!StructField::parse_str("name: string;").unwrap().1.is_shorthand());
+    }
+
+    #[test]
+    fn shorthand_array() {
+        let (input, field) = StructField::parse_str("Foo[][] ;").unwrap();
+        assert_eq!(*input.fragment(), "");
+        assert_eq!(
+            field.field_type,
+            TypeExpression::named("Foo").with_array_dimension(2)
+        );
+        assert_eq!(field.field_name, "foos");
+        assert_eq!(field.field_type.element().span().unwrap().length(), 3);
+    }
+
+    #[test]
+    fn numbered_fields() {
+        let (input, field) = StructField::parse_str("edited: option(string) = 2;").unwrap();
+        assert_eq!(*input.fragment(), "");
+        assert_eq!(field.number(), Some(2));
+
+        let (input, field) = StructField::parse_str("Field[] = 1 ;").unwrap();
+        assert_eq!(*input.fragment(), "");
+        assert_eq!(field.field_name, "fields");
+        assert_eq!(field.number(), Some(1));
+
+        assert_eq!(StructField::parse_str("name: string;").unwrap().1.number(), None);
+        assert!(StructField::parse_str("name: string = ;").is_err());
+    }
+
+    #[test]
+    fn plural_names() {
+        assert_eq!(StructField::derive_name("Box", 1), "boxes");
+        assert_eq!(StructField::derive_name("Match", 1), "matches");
+        assert_eq!(StructField::derive_name("Key", 1), "keys");
+        assert_eq!(StructField::derive_name("Entry", 1), "entries");
+        assert_eq!(StructField::derive_name("u8", 1), "u8s");
+    }
 }
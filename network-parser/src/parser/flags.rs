@@ -0,0 +1,441 @@
+use std::{collections::HashMap, fmt::Display, slice::Iter};
+
+use nom::{character::complete::u64, combinator::cut, multi::many0};
+
+use super::{
+    comment::Comment,
+    diagnostic::SourceSpan,
+    documentation::Documentation,
+    error::{expect, in_context, keyword, members, symbol, Expected, ParseResult, Span},
+    identifier::NetworkIdentifier,
+    interface::NetworkParser,
+    tag::Tag,
+};
+
+/// The highest bit position a flag can occupy. Flags are stored in
+/// at most 64 bits.
+pub const MAX_FLAG_POSITION: u64 = 64;
+
+/// The width of the smallest unsigned integer that can hold a flag
+/// or a set of flags.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub enum FlagsWidth {
+    U8,
+    U16,
+    U32,
+    U64,
+}
+
+impl FlagsWidth {
+    /// Returns the smallest width that can store the bit with
+    /// the given (zero based) index.
+    pub fn from_bit(bit: u64) -> Self {
+        match bit {
+            0..=7 => Self::U8,
+            8..=15 => Self::U16,
+            16..=31 => Self::U32,
+            _ => Self::U64,
+        }
+    }
+
+    /// The amount of bits in the integer.
+    pub fn bits(&self) -> u64 {
+        match self {
+            Self::U8 => 8,
+            Self::U16 => 16,
+            Self::U32 => 32,
+            Self::U64 => 64,
+        }
+    }
+
+    /// The name of the primitive type with this width.
+    pub fn primitive(&self) -> &'static str {
+        match self {
+            Self::U8 => "u8",
+            Self::U16 => "u16",
+            Self::U32 => "u32",
+            Self::U64 => "u64",
+        }
+    }
+}
+
+/// The reasons a flags declaration can be rejected.
+#[derive(Debug, PartialEq, Clone)]
+pub enum FlagsError {
+    /// Two members declared the same bit position.
+    DuplicatePosition {
+        position: u64,
+        first: String,
+        second: String,
+    },
+    /// A member declared a position outside of `1..=64`.
+    PositionOutOfRange { position: u64, member: String },
+}
+
+impl Display for FlagsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::DuplicatePosition {
+                position,
+                first,
+                second,
+            } => write!(
+                f,
+                "the flags `{first}` and `{second}` both occupy position {position}"
+            ),
+            Self::PositionOutOfRange { position, member } => write!(
+                f,
+                "the flag `{member}` occupies position {position}, expected 1 till {MAX_FLAG_POSITION}"
+            ),
+        }
+    }
+}
+
+/// A flags member is an entry in a flags declaration. It contains the
+/// member identifier and its' position, starting with 1 for the lowest
+/// bit.
+///
+/// #### Example
+///
+/// ```net
+/// Read = 1;
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+pub struct FlagsMember {
+    identity: String,
+    span: SourceSpan,
+    position: u64,
+    documentation: Option<Documentation>,
+    tags: Vec<Tag>,
+}
+
+impl FlagsMember {
+    pub fn name(&self) -> &str {
+        self.identity.as_str()
+    }
+
+    /// The location of the name in the network file.
+    pub fn span(&self) -> SourceSpan {
+        self.span
+    }
+
+    /// The position as written in the network file.
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// The zero based index of the bit this member sets. The invalid
+    /// position 0 is reported by [NetworkFlags::validate] and sets bit 0.
+    pub fn bit(&self) -> u64 {
+        self.position.saturating_sub(1)
+    }
+
+    /// The smallest integer width that can store this member.
+    pub fn width(&self) -> FlagsWidth {
+        FlagsWidth::from_bit(self.bit())
+    }
+
+    pub fn documentation(&self) -> Option<&Documentation> {
+        self.documentation.as_ref()
+    }
+
+    pub fn tags(&self) -> Iter<'_, Tag> {
+        self.tags.iter()
+    }
+
+    /// Reads the position of the member after the equal sign and the
+    /// closing semicolon.
+    fn definition(input: Span) -> ParseResult<u64> {
+        // read the position
+        let (input, _) = Comment::parse(input)?;
+        let (input, _) = symbol("=")(input)?;
+        let (input, _) = Comment::parse(input)?;
+        let (input, position) = expect(Expected::Integer, u64)(input)?;
+
+        // read the semicolon
+        let (input, _) = Comment::parse(input)?;
+        let (input, _) = symbol(";")(input)?;
+
+        ParseResult::Ok((input, position))
+    }
+}
+
+impl NetworkParser for FlagsMember {
+    fn parse(input: Span) -> ParseResult<Self> {
+        let (input, comment) = Comment::parse(input)?;
+
+        // read optionally several tags, including those in the documentation
+        let (input, tags) = many0(Tag::parse)(input)?;
+        let tags = [comment.read_tags(), tags].concat();
+
+        // read the member name, after which the declaration is expected
+        // to be complete
+        let (input, member_name) = NetworkIdentifier::parse(input)?;
+        let context = format!("member `{}`", member_name.identity);
+
+        let (input, position) = in_context(context, cut(Self::definition))(input)?;
+
+        ParseResult::Ok((
+            input,
+            FlagsMember {
+                identity: member_name.identity,
+                span: member_name.span,
+                position,
+                documentation: comment.read_documentation(),
+                tags,
+            },
+        ))
+    }
+}
+
+/// Definition for `flags` in a network file. Flags are a set of named
+/// bits, of which any combination can be sent. The declaration starts
+/// with the keyword 'flags', followed by an identifier declaring the
+/// name, followed by an array of members inside curly brackets.
+///
+/// #### Example
+///
+/// ```net
+/// flags Permission {
+///     Read  = 1; // 0001
+///     Write = 2; // 0010
+///     Admin = 4; // 1000
+/// }
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+pub struct NetworkFlags {
+    identity: String,
+    span: SourceSpan,
+    members: Vec<FlagsMember>,
+    documentation: Option<Documentation>,
+    tags: Vec<Tag>,
+}
+
+impl NetworkFlags {
+    pub fn name(&self) -> &str {
+        self.identity.as_str()
+    }
+
+    /// The location of the name in the network file.
+    pub fn span(&self) -> SourceSpan {
+        self.span
+    }
+
+    pub fn members(&self) -> Iter<'_, FlagsMember> {
+        self.members.iter()
+    }
+
+    /// Read a member from the flags.
+    pub fn member(&self, name: &str) -> Option<&FlagsMember> {
+        self.members.iter().find(|member| member.name() == name)
+    }
+
+    pub fn documentation(&self) -> Option<&Documentation> {
+        self.documentation.as_ref()
+    }
+
+    pub fn tags(&self) -> Iter<'_, Tag> {
+        self.tags.iter()
+    }
+
+    /// The smallest integer width that can store every member.
+    pub fn width(&self) -> FlagsWidth {
+        self.members
+            .iter()
+            .map(FlagsMember::width)
+            .max()
+            .unwrap_or(FlagsWidth::U8)
+    }
+
+    /// Checks that every member occupies a distinct position in the
+    /// range of `1..=64`. Returns the errors of all members, which do
+    /// not, together with the location of the member.
+    pub fn validate(&self) -> Result<(), Vec<(SourceSpan, FlagsError)>> {
+        let mut positions: HashMap<u64, &str> = HashMap::new();
+        let mut errors = Vec::new();
+
+        for member in self.members.iter() {
+            if member.position == 0 || member.position > MAX_FLAG_POSITION {
+                errors.push((
+                    member.span,
+                    FlagsError::PositionOutOfRange {
+                        position: member.position,
+                        member: member.identity.clone(),
+                    },
+                ));
+                continue;
+            }
+
+            match positions.get(&member.position) {
+                Some(first) => errors.push((
+                    member.span,
+                    FlagsError::DuplicatePosition {
+                        position: member.position,
+                        first: first.to_string(),
+                        second: member.identity.clone(),
+                    },
+                )),
+                None => {
+                    positions.insert(member.position, member.name());
+                }
+            }
+        }
+
+        match errors.is_empty() {
+            true => Ok(()),
+            false => Err(errors),
+        }
+    }
+
+    /// Reads the member declarations inside of curly brackets.
+    fn body(input: Span) -> ParseResult<Vec<FlagsMember>> {
+        // expect '{' symbol
+        let (input, _) = Comment::parse(input)?;
+        let (input, _) = symbol("{")(input)?;
+
+        // expect member declarations
+        let (input, members) = members(FlagsMember::parse)(input)?;
+
+        let (input, _) = Comment::parse(input)?;
+        let (input, _) = symbol("}")(input)?;
+
+        ParseResult::Ok((input, members))
+    }
+}
+
+impl NetworkParser for NetworkFlags {
+    fn parse(input: Span) -> ParseResult<Self> {
+        let (input, comment) = Comment::parse(input)?;
+
+        // read optionally several tags, including those in the documentation
+        let (input, tags) = many0(Tag::parse)(input)?;
+        let tags = [comment.read_tags(), tags].concat();
+
+        // read the 'flags' keyword
+        let (input, _) = Comment::parse(input)?;
+        let (input, _) = keyword("flags")(input)?;
+
+        // expect flags name
+        let (input, flags_name) = cut(NetworkIdentifier::parse)(input)?;
+        let context = format!("flags `{}`", flags_name.identity);
+
+        // expect member declarations inside of curly brackets
+        let (input, members) = in_context(context, cut(Self::body))(input)?;
+
+        // overlapping and out of range flags are reported by the resolver
+        Ok((
+            input,
+            Self {
+                identity: flags_name.identity,
+                span: flags_name.span,
+                members,
+                documentation: comment.read_documentation(),
+                tags,
+            },
+        ))
+    }
+}
+
+#[cfg(test)]
+mod flags_test {
+    use super::*;
+
+    /// Tests the flags declaration from `rights.net`.
+    #[test]
+    fn permission_flags() {
+        let (input, flags) = NetworkFlags::parse_str(
+            "flags Permission {\n\tRead = 1; // ooo1\n\tWrite = 2; // oo1o\n\tModeration = 3;\n\tAdmin = 4;\n}",
+        )
+        .unwrap();
+        assert_eq!(*input.fragment(), "");
+        assert_eq!(flags.name(), "Permission");
+        assert_eq!(flags.members().len(), 4);
+        assert_eq!(flags.member("Read").unwrap().bit(), 0);
+        assert_eq!(flags.member("Admin").unwrap().bit(), 3);
+        assert_eq!(flags.width(), FlagsWidth::U8);
+    }
+
+    #[test]
+    fn empty_flags() {
+        let (input, flags) = NetworkFlags::parse_str("flags Empty {}").unwrap();
+        assert_eq!(*input.fragment(), "");
+        assert_eq!(flags.width(), FlagsWidth::U8);
+    }
+
+    /// The width grows with the highest declared position.
+    #[test]
+    fn flags_width() {
+        let (_, flags) = NetworkFlags::parse_str("flags F { A = 1; B = 8; C = 9; }").unwrap();
+        assert_eq!(flags.member("B").unwrap().width(), FlagsWidth::U8);
+        assert_eq!(flags.member("C").unwrap().width(), FlagsWidth::U16);
+        assert_eq!(flags.width(), FlagsWidth::U16);
+
+        let (_, flags) = NetworkFlags::parse_str("flags F { A = 17; }").unwrap();
+        assert_eq!(flags.width(), FlagsWidth::U32);
+
+        let (_, flags) = NetworkFlags::parse_str("flags F { A = 64; }").unwrap();
+        assert_eq!(flags.width().primitive(), "u64");
+    }
+
+    #[test]
+    fn duplicate_position() {
+        let (_, flags) = NetworkFlags::parse_str("flags F { A = 1; B = 1; }").unwrap();
+        let errors = flags.validate().unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].0, flags.member("B").unwrap().span());
+    }
+
+    /// Every invalid member is reported, not only the first one.
+    #[test]
+    fn position_out_of_range() {
+        let (_, flags) =
+            NetworkFlags::parse_str("flags F { A = 0; B = 1; C = 65; D = 1; }").unwrap();
+        let errors = flags.validate().unwrap_err();
+        assert_eq!(
+            errors
+                .iter()
+                .map(|(span, _)| span.column())
+                .collect::<Vec<_>>(),
+            [11, 25, 33]
+        );
+        assert_eq!(
+            errors[1].1,
+            FlagsError::PositionOutOfRange {
+                position: 65,
+                member: "C".to_owned()
+            }
+        );
+        assert_eq!(flags.member("A").unwrap().bit(), 0);
+    }
+
+    #[test]
+    fn validation_error() {
+        let flags = NetworkFlags {
+            identity: "F".to_owned(),
+            span: SourceSpan::new(6, 1, 1, 7),
+            members: ["A", "B"]
+                .iter()
+                .map(|name| FlagsMember {
+                    identity: name.to_string(),
+                    span: SourceSpan::new(0, 1, 1, 1),
+                    position: 2,
+                    documentation: None,
+                    tags: vec![],
+                })
+                .collect(),
+            documentation: None,
+            tags: vec![],
+        };
+
+        assert_eq!(
+            flags.validate().unwrap_err()[0].1.to_string(),
+            "the flags `A` and `B` both occupy position 2"
+        );
+    }
+
+    /// Flags require an explicit position.
+    #[test]
+    fn missing_position() {
+        assert!(NetworkFlags::parse_str("flags F { A; }").is_err());
+    }
+}
@@ -1,11 +1,12 @@
-use super::{comment::Comment, interface::NetworkParser};
+use super::{
+    comment::Comment,
+    diagnostic::SourceSpan,
+    error::{expect, source_span, Expected, ParseResult, Span},
+    interface::NetworkParser,
+};
 use nom::{
-    bytes::complete::{is_a, take_while_m_n},
-    character::complete::alphanumeric1,
-    combinator::recognize,
-    multi::many0_count,
-    sequence::pair,
-    IResult,
+    bytes::complete::is_a, character::complete::alphanumeric1, combinator::recognize,
+    multi::many0_count, sequence::pair,
 };
 
 ///
@@ -14,14 +15,16 @@ use nom::{
 /// ```net
 /// Identifier label Text123 Hello_World
 /// ```
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct NetworkIdentifier {
     pub identity: String,
+    /// The location of the identifier in the network file.
+    pub span: SourceSpan,
 }
 
 impl NetworkIdentifier {
-    pub fn new(identity: String) -> Self {
-        Self { identity }
+    pub fn new(identity: String, span: SourceSpan) -> Self {
+        Self { identity, span }
     }
 }
 
@@ -32,15 +35,19 @@ impl NetworkParser for NetworkIdentifier {
     /// too.
     ///
     /// @reference https://stackoverflow.com/a/61329008/16002144
-    fn parse(input: &str) -> IResult<&str, Self> {
+    fn parse(input: Span) -> ParseResult<Self> {
         let (input, _comment) = Comment::parse(input)?;
 
-        let (input, identity) = recognize(pair(
-            is_a("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ_"),
-            many0_count(alphanumeric1),
-        ))(input)?;
+        let (input, identity) = expect(
+            Expected::Identifier,
+            recognize(pair(
+                is_a("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ_"),
+                many0_count(alphanumeric1),
+            )),
+        )(input)?;
 
-        IResult::Ok((input, Self::new(identity.to_string())))
+        let span = source_span(identity, identity.len());
+        ParseResult::Ok((input, Self::new(identity.to_string(), span)))
     }
 }
 
@@ -50,32 +57,33 @@ mod identifier_test {
 
     #[test]
     fn simple_identifier() {
-        let (_, field) = NetworkIdentifier::parse("Identifier").unwrap();
+        let (_, field) = NetworkIdentifier::parse_str("Identifier").unwrap();
         assert_eq!(field.identity, "Identifier");
     }
 
     #[test]
     fn invalid_identifier() {
-        let output = NetworkIdentifier::parse("012variable");
+        let output = NetworkIdentifier::parse_str("012variable");
         assert!(output.is_err());
     }
 
     #[test]
     #[ignore = "invalid test: returned output is NetworkIdentifier { identity: \"Id\" } and not an error."]
     fn invalid_character() {
-        let output = NetworkIdentifier::parse("Idäntitßi");
+        let output = NetworkIdentifier::parse_str("Idäntitßi");
         assert!(output.is_err());
     }
 
     #[test]
     fn trim_identifier() {
-        let (_, field) = NetworkIdentifier::parse("   spaces").unwrap();
+        let (_, field) = NetworkIdentifier::parse_str("   spaces").unwrap();
         assert_eq!(field.identity, "spaces");
+        assert_eq!(field.span, SourceSpan::new(3, 6, 1, 4));
     }
 
     #[test]
     fn middle_identifier() {
-        let (_, field) = NetworkIdentifier::parse("NoEof ").unwrap();
+        let (_, field) = NetworkIdentifier::parse_str("NoEof ").unwrap();
         assert_eq!(field.identity, "NoEof");
     }
 }
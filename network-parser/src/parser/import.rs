@@ -0,0 +1,126 @@
+use std::slice::Iter;
+
+use nom::{combinator::cut, multi::many0};
+
+use super::{
+    comment::Comment,
+    constant::ConstExpression,
+    diagnostic::SourceSpan,
+    documentation::Documentation,
+    error::{expect, in_context, keyword, source_span, symbol, Expected, ParseResult, Span},
+    interface::NetworkParser,
+    tag::Tag,
+};
+
+/// Definition for an `import` in a network file. An import starts with
+/// the keyword 'import', followed by the path of another network file in
+/// quotation marks. The declarations of the imported file share one
+/// namespace with the importing file, see
+/// [NetworkLoader](super::loader::NetworkLoader).
+///
+/// #### Example
+///
+/// ```net
+/// import "users.net";
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+pub struct NetworkImport {
+    path: String,
+    span: SourceSpan,
+    documentation: Option<Documentation>,
+    tags: Vec<Tag>,
+}
+
+impl NetworkImport {
+    /// The path as written in the network file.
+    pub fn path(&self) -> &str {
+        self.path.as_str()
+    }
+
+    /// The location of the path, including the quotation marks.
+    pub fn span(&self) -> SourceSpan {
+        self.span
+    }
+
+    pub fn documentation(&self) -> Option<&Documentation> {
+        self.documentation.as_ref()
+    }
+
+    pub fn tags(&self) -> Iter<'_, Tag> {
+        self.tags.iter()
+    }
+
+    /// Reads the path in quotation marks and the closing semicolon.
+    fn definition(input: Span) -> ParseResult<(String, SourceSpan)> {
+        // expect the path
+        let (input, _) = Comment::parse(input)?;
+        let start = input;
+        let (input, path) = expect(Expected::Symbol("\""), ConstExpression::string)(input)?;
+        let span = source_span(start, input.location_offset() - start.location_offset());
+
+        // read the semicolon
+        let (input, _) = Comment::parse(input)?;
+        let (input, _) = symbol(";")(input)?;
+
+        ParseResult::Ok((input, (path, span)))
+    }
+}
+
+impl NetworkParser for NetworkImport {
+    fn parse(input: Span) -> ParseResult<Self> {
+        let (input, comment) = Comment::parse(input)?;
+
+        // read optionally several tags, including those in the documentation
+        let (input, tags) = many0(Tag::parse)(input)?;
+        let tags = [comment.read_tags(), tags].concat();
+
+        // read the 'import' keyword
+        let (input, _) = Comment::parse(input)?;
+        let (input, _) = keyword("import")(input)?;
+
+        // expect the path and the semicolon
+        let (input, (path, span)) = in_context("import".to_owned(), cut(Self::definition))(input)?;
+
+        Ok((
+            input,
+            Self {
+                path,
+                span,
+                documentation: comment.read_documentation(),
+                tags,
+            },
+        ))
+    }
+}
+
+#[cfg(test)]
+mod import_test {
+    use super::*;
+
+    #[test]
+    fn simple_import() {
+        let (input, import) = NetworkImport::parse_str("import \"users.net\";").unwrap();
+        assert_eq!(*input.fragment(), "");
+        assert_eq!(import.path(), "users.net");
+        assert_eq!(import.span(), SourceSpan::new(7, 11, 1, 8));
+    }
+
+    #[test]
+    fn documented_import() {
+        let (input, import) =
+            NetworkImport::parse_str("/// The users.\nimport \"../shared/users.net\" ;").unwrap();
+        assert_eq!(*input.fragment(), "");
+        assert_eq!(import.path(), "../shared/users.net");
+        assert_eq!(
+            import.documentation().map(Documentation::description),
+            Some("The users.")
+        );
+    }
+
+    #[test]
+    fn invalid_import() {
+        assert!(NetworkImport::parse_str("import users;").is_err());
+        assert!(NetworkImport::parse_str("import \"users.net\"").is_err());
+        assert!(NetworkImport::parse_str("imports \"users.net\";").is_err());
+    }
+}
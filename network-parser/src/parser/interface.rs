@@ -1,21 +1,32 @@
-use nom::{error::{Error, ErrorKind}, Err, IResult};
+use nom::{
+    error::{ErrorKind, ParseError},
+    Err,
+};
+
+use super::error::{span, NetworkError, ParseResult, Span};
 
 /// This trait describes a common trait that all network
 /// file parser elements have.
 pub trait NetworkParser : Sized {
-    /// Parse the given element using a `&str` input and
+    /// Parse the given element using a `Span` input and
     /// `Self` output.
-    fn parse(input: &str) -> IResult<&str, Self>;
+    fn parse(input: Span) -> ParseResult<Self>;
 
-    /// Parse the given element using a `&str` input and
+    /// Parse the given element using a `Span` input and
     /// `Self` output, and expect to advance at least one
     /// symbol.
-    fn parse1(input: &str) -> IResult<&str, Self> {
+    fn parse1(input: Span) -> ParseResult<Self> {
         let (advanced_input, output) = Self::parse(input)?;
         if input == advanced_input {
-            IResult::Err(Err::Error(Error::new(input, ErrorKind::Many1)))
+            ParseResult::Err(Err::Error(NetworkError::from_error_kind(input, ErrorKind::Many1)))
         } else {
-            IResult::Ok((advanced_input, output))
+            ParseResult::Ok((advanced_input, output))
         }
     }
+
+    /// Parse the given element from the beginning of a source
+    /// text, see [NetworkParser::parse].
+    fn parse_str(source: &str) -> ParseResult<'_, Self> {
+        Self::parse(span(source))
+    }
 }
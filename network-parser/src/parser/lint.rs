@@ -0,0 +1,323 @@
+use std::{fmt::Display, slice::Iter};
+
+use util_cases::CaseStyles;
+
+use super::{
+    diagnostic::{Diagnostic, Severity, SourceSpan},
+    network::NetworkFileReader,
+    tag::{Tag, TagArgument},
+};
+
+/// The tag, which opts out of a lint for an item and its' members. With
+/// `@allow(case)` the case rules are not checked. Written in the `//!`
+/// documentation of the file, it applies to the entire file.
+pub const ALLOW_TAG: &str = "allow";
+
+/// The name of the case lint in the [ALLOW_TAG].
+pub const CASE_LINT: &str = "case";
+
+/// The case style an identifier has to follow, depending on its' role.
+///
+/// - Custom types and the members of enumerations and flags are written
+///   in `PascalCase`.
+/// - Fields and the named messages of protocols are written in `snake_case`.
+/// - Primitives are written in `flatcase`.
+/// - Constants are written in `CONSTANT_CASE`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum CaseRule {
+    Pascal,
+    Snake,
+    Flat,
+    Constant,
+}
+
+impl CaseRule {
+    /// Whether the identifier is written in this case style.
+    pub fn is_followed_by(&self, identifier: &str) -> bool {
+        match self {
+            Self::Pascal => identifier.is_strict_pascal_case(),
+            Self::Snake => identifier.is_strict_snake_case(),
+            Self::Flat => identifier.is_strict_flat_case(),
+            Self::Constant => identifier.is_strict_constant_case(),
+        }
+    }
+
+    /// Converts the identifier into this case style.
+    pub fn rewrite(&self, identifier: &str) -> String {
+        match self {
+            Self::Pascal => identifier.to_pascal_case(),
+            Self::Snake => identifier.to_snake_case(),
+            Self::Flat => identifier.to_flat_case(),
+            Self::Constant => identifier.to_constant_case(),
+        }
+    }
+}
+
+impl Display for CaseRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Pascal => write!(f, "PascalCase"),
+            Self::Snake => write!(f, "snake_case"),
+            Self::Flat => write!(f, "flatcase"),
+            Self::Constant => write!(f, "CONSTANT_CASE"),
+        }
+    }
+}
+
+/// The lint, which checks every declared name of a network file against
+/// the [CaseRule] of its' role and suggests a rewrite. Aliases named like a
+/// registered primitive or an alias of the prelude, like `type string = u8[];`,
+/// are primitives themselves.
+///
+/// ```
+/// use network_parser::parser::{lint::CaseLint, network::NetworkFileReader};
+///
+/// let source = "struct post_data {\n    authorId: u64;\n}\n\n@allow(case)\nconst max: u8 = 1;";
+/// let network_file = NetworkFileReader::from_source(source).unwrap();
+/// let diagnostics = CaseLint::default().check(&network_file);
+///
+/// assert_eq!(diagnostics.len(), 2);
+/// assert_eq!(diagnostics[0].message(), "struct `post_data` should have a PascalCase name");
+/// assert_eq!(diagnostics[0].help(), Some("rename it to `PostData`"));
+/// assert_eq!(diagnostics[1].help(), Some("rename it to `author_id`"));
+/// ```
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct CaseLint {
+    severity: Severity,
+}
+
+impl Default for CaseLint {
+    fn default() -> Self {
+        Self::new(Severity::Warning)
+    }
+}
+
+impl CaseLint {
+    /// A lint, which reports names with the severity.
+    pub fn new(severity: Severity) -> Self {
+        Self { severity }
+    }
+
+    /// Checks all names of the file, except for items which are allowed
+    /// to break the case rules.
+    pub fn check(&self, file: &NetworkFileReader) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        if allows_case(file.tags()) {
+            return diagnostics;
+        }
+
+        let mut check = |kind: &str, name: &str, span: SourceSpan, rule: CaseRule| {
+            if let Some(diagnostic) = self.check_name(kind, name, span, rule) {
+                diagnostics.push(diagnostic);
+            }
+        };
+
+        for structure in file.structures().filter(|item| !allows_case(item.tags())) {
+            check(
+                "struct",
+                structure.name(),
+                structure.span(),
+                CaseRule::Pascal,
+            );
+
+            for field in structure
+                .fields()
+                .filter(|field| !allows_case(field.tags()))
+            {
+                check("field", field.name(), field.span(), CaseRule::Snake);
+            }
+        }
+
+        for enumeration in file.enumerations().filter(|item| !allows_case(item.tags())) {
+            check(
+                "enum",
+                enumeration.name(),
+                enumeration.span(),
+                CaseRule::Pascal,
+            );
+
+            for member in enumeration
+                .members()
+                .filter(|item| !allows_case(item.tags()))
+            {
+                check("member", member.name(), member.span(), CaseRule::Pascal);
+            }
+        }
+
+        for flags in file.all_flags().filter(|item| !allows_case(item.tags())) {
+            check("flags", flags.name(), flags.span(), CaseRule::Pascal);
+
+            for member in flags.members().filter(|item| !allows_case(item.tags())) {
+                check("member", member.name(), member.span(), CaseRule::Pascal);
+            }
+        }
+
+        let primitives = file.primitives();
+        let prelude = file.prelude();
+
+        for alias in file.aliases().filter(|item| !allows_case(item.tags())) {
+            // aliases redeclaring a primitive of the prelude are primitives
+            let primitive = primitives.iter().any(|primitive| primitive == alias.name())
+                || prelude.is_some_and(|prelude| prelude.alias(alias.name()).is_some());
+
+            let rule = match primitive {
+                true => CaseRule::Flat,
+                false => CaseRule::Pascal,
+            };
+
+            check("type", alias.name(), alias.span(), rule);
+        }
+
+        for constant in file.consts().filter(|item| !allows_case(item.tags())) {
+            check(
+                "const",
+                constant.name(),
+                constant.span(),
+                CaseRule::Constant,
+            );
+        }
+
+        for protocol in file.protocols().filter(|item| !allows_case(item.tags())) {
+            check(
+                "protocol",
+                protocol.name(),
+                protocol.span(),
+                CaseRule::Pascal,
+            );
+
+            for entry in protocol.entries().filter(|item| !allows_case(item.tags())) {
+                // an entry common to both agents declares a single message
+                let server = entry
+                    .server()
+                    .filter(|server| Some(*server) != entry.client());
+
+                for message in entry.client().into_iter().chain(server) {
+                    if let (Some(name), Some(span)) = (message.name(), message.name_span()) {
+                        check("message", name, span, CaseRule::Snake);
+                    }
+                }
+            }
+        }
+
+        diagnostics.sort_by_key(|diagnostic| diagnostic.span().offset());
+        diagnostics
+    }
+
+    /// The diagnostic of a name, which does not follow its' case rule.
+    fn check_name(
+        &self,
+        kind: &str,
+        name: &str,
+        span: SourceSpan,
+        rule: CaseRule,
+    ) -> Option<Diagnostic> {
+        if rule.is_followed_by(name) {
+            return None;
+        }
+
+        let diagnostic = Diagnostic::new(
+            self.severity,
+            format!("{kind} `{name}` should have a {rule} name"),
+            span,
+        )
+        .with_label(format!("not in {rule}"));
+
+        // identifiers without letters can not be rewritten
+        match rule.rewrite(name) {
+            rewrite if rewrite.is_empty() => Some(diagnostic),
+            rewrite => Some(diagnostic.with_help(format!("rename it to `{rewrite}`"))),
+        }
+    }
+}
+
+/// Whether the tags contain `@allow(case)`.
+fn allows_case(mut tags: Iter<'_, Tag>) -> bool {
+    tags.any(|tag| {
+        tag.name() == ALLOW_TAG
+            && tag
+                .arguments()
+                .iter()
+                .any(|argument| *argument == TagArgument::Identifier(CASE_LINT.to_owned()))
+    })
+}
+
+#[cfg(test)]
+mod lint_test {
+    use super::*;
+    use crate::parser::interface::NetworkParser;
+
+    fn check(source: &str) -> Vec<Diagnostic> {
+        let (_, network_file) = NetworkFileReader::parse_str(source).unwrap();
+        CaseLint::default().check(&network_file)
+    }
+
+    #[test]
+    fn valid_names() {
+        let source = "struct PostData {\n\tauthor_id: u64;\n}\nenum Kind { Text; Image; }\nflags Permission { Read = 1; }\nconst MAX_LENGTH: u8 = 20;\nprotocol Connection {\n\t(request: PostData -> ()) = 1;\n}";
+        assert!(check(source).is_empty());
+    }
+
+    #[test]
+    fn rewrites() {
+        let diagnostics = check("enum kind_of_post { text_post; }\nconst maxLength: u8 = 1;\nprotocol Connection {\n\tpostData: Kind;\n}");
+        let helps = diagnostics
+            .iter()
+            .map(|diagnostic| diagnostic.help().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            helps,
+            vec![
+                "rename it to `KindOfPost`",
+                "rename it to `TextPost`",
+                "rename it to `MAX_LENGTH`",
+                "rename it to `post_data`",
+            ]
+        );
+        assert_eq!(diagnostics[0].severity(), Severity::Warning);
+        assert_eq!(diagnostics[0].label(), Some("not in PascalCase"));
+        assert_eq!(diagnostics[1].span().line(), 1);
+    }
+
+    /// Aliases of primitives are primitives and written in flatcase.
+    #[test]
+    fn primitive_aliases() {
+        let diagnostics =
+            check("type string = u8[];\ntype Bytes = u8[];\ntype Boolean_Value = u8;");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].message(),
+            "type `Boolean_Value` should have a PascalCase name"
+        );
+
+        let (_, mut network_file) = NetworkFileReader::parse_str("type Timestamp = u64;").unwrap();
+        network_file.register_primitive("Timestamp");
+        assert_eq!(
+            CaseLint::default().check(&network_file)[0].help(),
+            Some("rename it to `timestamp`")
+        );
+    }
+
+    #[test]
+    fn allowed_items() {
+        assert!(check("@allow(case)\nstruct ffi_data {\n\tCount: u8;\n}").is_empty());
+        assert_eq!(
+            check("struct A {\n\t@allow(case)\n\tCount: u8;\n\tTotal: u8;\n}").len(),
+            1
+        );
+        assert!(check("//! @allow(case)\n\nstruct ffi_data {}\nconst max: u8 = 1;").is_empty());
+        assert_eq!(check("@allow(other)\nconst max: u8 = 1;").len(), 1);
+    }
+
+    #[test]
+    fn denied_names() {
+        let (_, network_file) = NetworkFileReader::parse_str("struct post {}").unwrap();
+        let diagnostics = CaseLint::new(Severity::Error).check(&network_file);
+        assert_eq!(diagnostics[0].severity(), Severity::Error);
+    }
+
+    #[test]
+    fn stdlib() {
+        assert!(check(include_str!("../../../netz-stdlib/stdlib.net")).is_empty());
+    }
+}
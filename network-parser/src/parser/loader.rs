@@ -0,0 +1,595 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fs, io,
+    path::{Path, PathBuf},
+    slice::Iter,
+};
+
+use super::{
+    diagnostic::{Diagnostic, SourceSpan},
+    evaluate::{ConstError, ConstEvaluator, ConstValue},
+    import::NetworkImport,
+    network::NetworkFileReader,
+    prelude::Prelude,
+    resolver::{declarations, Resolver},
+};
+
+/// A network file, which was loaded as part of a schema.
+#[derive(Debug, PartialEq)]
+pub struct NetworkModule {
+    name: String,
+    path: PathBuf,
+    source: String,
+    file: NetworkFileReader,
+}
+
+impl NetworkModule {
+    /// The path of the file, as it is written in diagnostics.
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    /// The canonical path of the file.
+    pub fn path(&self) -> &Path {
+        self.path.as_path()
+    }
+
+    pub fn source(&self) -> &str {
+        self.source.as_str()
+    }
+
+    pub fn file(&self) -> &NetworkFileReader {
+        &self.file
+    }
+}
+
+/// A schema is a network file with all files it imports, directly or
+/// through other imports. The declarations of all files share a single
+/// namespace, so every file can use the types declared in any other file
+/// and in the prelude of the loader.
+///
+/// ```
+/// use network_parser::parser::loader::NetworkLoader;
+///
+/// let schema = NetworkLoader::new().load("../playground/net/imports/forum.net").unwrap();
+/// let names = schema.modules().map(|module| module.name()).collect::<Vec<_>>();
+///
+/// assert_eq!(
+///     names,
+///     [
+///         "../playground/net/imports/shared/users.net",
+///         "../playground/net/imports/forum.net",
+///     ]
+/// );
+/// assert!(schema.diagnostics().is_empty());
+/// ```
+#[derive(Debug, PartialEq)]
+pub struct NetworkSchema {
+    modules: Vec<NetworkModule>,
+    prelude: Prelude,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl NetworkSchema {
+    /// Get an iterator over all files of the schema. Imported files come
+    /// before the files importing them, the entry file last.
+    pub fn modules(&self) -> Iter<'_, NetworkModule> {
+        self.modules.iter()
+    }
+
+    /// Read a file of the schema by the name, that is used in diagnostics.
+    pub fn module(&self, name: &str) -> Option<&NetworkModule> {
+        self.modules.iter().find(|module| module.name() == name)
+    }
+
+    /// The declarations of all files merged into one network file. A name,
+    /// which is declared in several files, refers to its' first declaration.
+    pub fn namespace(&self) -> NetworkFileReader {
+        let entry = self.modules.last().map(NetworkModule::file);
+        let mut namespace =
+            NetworkFileReader::empty(entry.and_then(|file| file.documentation().cloned()));
+
+        namespace.set_prelude(self.prelude.clone());
+
+        let mut declared = HashSet::new();
+
+        for module in self.modules.iter() {
+            let file = &module.file;
+            let mut first = |name: &str| declared.insert(name.to_owned());
+
+            for structure in file.structures().filter(|item| first(item.name())) {
+                namespace.register_structure(structure.clone());
+            }
+            for enumeration in file.enumerations().filter(|item| first(item.name())) {
+                namespace.register_enumeration(enumeration.clone());
+            }
+            for flags in file.all_flags().filter(|item| first(item.name())) {
+                namespace.register_flags(flags.clone());
+            }
+            for alias in file.aliases().filter(|item| first(item.name())) {
+                namespace.register_alias(alias.clone());
+            }
+            for constant in file.consts().filter(|item| first(item.name())) {
+                namespace.register_const(constant.clone());
+            }
+            for protocol in file.protocols().filter(|item| first(item.name())) {
+                namespace.register_protocol(protocol.clone());
+            }
+        }
+
+        namespace
+    }
+
+    /// The errors of all files, which are the parser errors, unresolved
+    /// imports, import cycles, names declared in more than one file, the
+    /// diagnostics of the [Resolver] of every file and the constants, which
+    /// can not be evaluated. Every diagnostic names the file it refers to.
+    pub fn diagnostics(&self) -> Vec<Diagnostic> {
+        let mut diagnostics = self.diagnostics.clone();
+        let mut first_declarations = HashMap::new();
+
+        for module in self.modules.iter() {
+            for declaration in declarations(&module.file) {
+                let (Some(name), Some(span)) = (declaration.name(), declaration.span()) else {
+                    continue;
+                };
+
+                let Some((first_module, first)) = first_declarations.get(name) else {
+                    first_declarations.insert(name, (module.name(), span));
+                    continue;
+                };
+
+                // names declared twice in one file are reported by its' resolver
+                if *first_module == module.name() {
+                    continue;
+                }
+
+                diagnostics.push(
+                    Diagnostic::error(format!("the name `{name}` is defined multiple times"), span)
+                        .with_file(module.name())
+                        .with_label(format!("`{name}` redefined here"))
+                        .with_note(format!(
+                            "`{name}` is first defined in `{first_module}` on line {}",
+                            first.line()
+                        )),
+                );
+            }
+        }
+
+        for (index, module) in self.modules.iter().enumerate() {
+            let imports = self
+                .modules
+                .iter()
+                .enumerate()
+                .filter(|(other, _)| *other != index)
+                .map(|(_, other)| &other.file)
+                .collect::<Vec<_>>();
+
+            diagnostics.extend(
+                Resolver::with_imports(&module.file, &imports)
+                    .diagnostics()
+                    .iter()
+                    .map(|diagnostic| diagnostic.clone().with_file(module.name())),
+            );
+        }
+
+        // constants can reference the constants of other files, so they are
+        // evaluated in the namespace. `NETZ_SCHEMA` is only provided by the
+        // template generator, any string stands in for it.
+        let namespace = self.namespace();
+        let evaluator = ConstEvaluator::new(&namespace)
+            .with_variable("NETZ_SCHEMA", ConstValue::String(String::new()));
+
+        for error in evaluator.errors() {
+            let declared = self.modules.iter().find_map(|module| {
+                let constant = module.file.constant(error.constant())?;
+                Some((module.name(), constant.span()))
+            });
+
+            // constants of the prelude are not part of the schema
+            if let Some((name, span)) = declared {
+                diagnostics.push(const_diagnostic(&error, span).with_file(name));
+            }
+        }
+
+        diagnostics
+    }
+}
+
+/// The diagnostic of a constant, which can not be evaluated, at the name
+/// of the constant.
+fn const_diagnostic(error: &ConstError, span: SourceSpan) -> Diagnostic {
+    let label = match error {
+        ConstError::UnknownReference { reference, .. } => format!("`{reference}` is not declared"),
+        ConstError::CyclicReference { .. } => "cyclic reference".to_owned(),
+        ConstError::UnknownLimit { .. } => "no numeric limits".to_owned(),
+        ConstError::LimitOverflow { .. } => "maximum does not fit a constant".to_owned(),
+        ConstError::TypeMismatch { .. } => "the value does not fit into the type".to_owned(),
+    };
+
+    Diagnostic::error(error.to_string(), span).with_label(label)
+}
+
+/// The loader reads a network file and all files it imports into a
+/// [NetworkSchema]. The path of an import is resolved relative to the
+/// importing file first and to every search root after, in the order the
+/// roots were added.
+///
+/// ```net
+/// import "users.net";
+/// import "../shared/rights.net";
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+pub struct NetworkLoader {
+    roots: Vec<PathBuf>,
+    prelude: Prelude,
+}
+
+impl Default for NetworkLoader {
+    fn default() -> Self {
+        Self {
+            roots: Vec::new(),
+            prelude: Prelude::Stdlib,
+        }
+    }
+}
+
+impl NetworkLoader {
+    /// A loader without search roots, whose' files use the standard library
+    /// as prelude.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a directory, which is searched for imported files.
+    pub fn with_root(mut self, root: impl Into<PathBuf>) -> Self {
+        self.roots.push(root.into());
+        self
+    }
+
+    /// Sets the prelude of every loaded file.
+    pub fn with_prelude(mut self, prelude: Prelude) -> Self {
+        self.prelude = prelude;
+        self
+    }
+
+    /// Loads the entry file and all files it imports. Only the entry file
+    /// has to be readable, errors in any file are collected in the
+    /// diagnostics of the schema.
+    pub fn load(&self, entry: impl AsRef<Path>) -> io::Result<NetworkSchema> {
+        let entry = entry.as_ref();
+        let canonical = entry.canonicalize()?;
+        let source = fs::read_to_string(entry)?;
+
+        let mut loading = Loading {
+            modules: Vec::new(),
+            loaded: HashSet::new(),
+            stack: Vec::new(),
+            diagnostics: Vec::new(),
+        };
+
+        self.visit(&mut loading, entry.display().to_string(), canonical, source);
+
+        Ok(NetworkSchema {
+            modules: loading.modules,
+            prelude: self.prelude.clone(),
+            diagnostics: loading.diagnostics,
+        })
+    }
+
+    /// Parses a file and loads its' imports, before the file itself is
+    /// added to the schema.
+    fn visit(&self, loading: &mut Loading, name: String, path: PathBuf, source: String) {
+        let (mut file, diagnostics) = NetworkFileReader::parse_recovering(&source);
+        file.set_prelude(self.prelude.clone());
+        loading.stack.push((path.clone(), name.clone()));
+
+        for import in file.imports() {
+            let Some((import_name, import_path)) = self.locate(&path, &name, import) else {
+                loading.diagnostics.push(
+                    Diagnostic::error(
+                        format!("cannot find the file `{}`", import.path()),
+                        import.span(),
+                    )
+                    .with_file(&name)
+                    .with_label("not found")
+                    .with_note(self.searched(&path, import)),
+                );
+                continue;
+            };
+
+            if let Some(start) = loading
+                .stack
+                .iter()
+                .position(|(path, _)| *path == import_path)
+            {
+                let cycle = loading.stack[start..]
+                    .iter()
+                    .map(|(_, name)| format!("`{name}`"))
+                    .chain([format!("`{}`", loading.stack[start].1)])
+                    .collect::<Vec<_>>();
+
+                loading.diagnostics.push(
+                    Diagnostic::error(
+                        format!("the import of `{}` is cyclic", import.path()),
+                        import.span(),
+                    )
+                    .with_file(&name)
+                    .with_label("cyclic import")
+                    .with_note(format!("the import cycle is {}", cycle.join(" -> "))),
+                );
+                continue;
+            }
+
+            if loading.loaded.contains(&import_path) {
+                continue;
+            }
+
+            match fs::read_to_string(&import_path) {
+                Ok(source) => self.visit(loading, import_name, import_path, source),
+                Err(error) => loading.diagnostics.push(
+                    Diagnostic::error(
+                        format!("could not read the file `{}`", import.path()),
+                        import.span(),
+                    )
+                    .with_file(&name)
+                    .with_label(error.to_string()),
+                ),
+            }
+        }
+
+        loading.stack.pop();
+        loading.loaded.insert(path.clone());
+        loading.add(
+            NetworkModule {
+                name,
+                path,
+                source,
+                file,
+            },
+            diagnostics,
+        );
+    }
+
+    /// The directories an import is searched in, the directory of the
+    /// importing file first.
+    fn directories(&self, path: &Path) -> Vec<PathBuf> {
+        let parent = path.parent().map(Path::to_path_buf);
+        parent
+            .into_iter()
+            .chain(self.roots.iter().cloned())
+            .collect()
+    }
+
+    /// Resolves an import to the name used in diagnostics and the canonical
+    /// path. The name is the path relative to the name of the importing
+    /// file or to the search root.
+    fn locate(&self, path: &Path, name: &str, import: &NetworkImport) -> Option<(String, PathBuf)> {
+        let relative = Path::new(name)
+            .parent()
+            .map(|parent| parent.join(import.path()));
+        let roots = self.roots.iter().map(|root| root.join(import.path()));
+        let names = relative.into_iter().chain(roots);
+
+        self.directories(path)
+            .iter()
+            .map(|directory| directory.join(import.path()))
+            .zip(names)
+            .find(|(candidate, _)| candidate.is_file())
+            .and_then(|(candidate, name)| {
+                let canonical = candidate.canonicalize().ok()?;
+                Some((name.display().to_string(), canonical))
+            })
+    }
+
+    /// The note of an unresolved import, listing the searched directories.
+    fn searched(&self, path: &Path, import: &NetworkImport) -> String {
+        let directories = self
+            .directories(path)
+            .iter()
+            .map(|directory| format!("`{}`", directory.display()))
+            .collect::<Vec<_>>();
+
+        format!(
+            "`{}` was searched in {}",
+            import.path(),
+            directories.join(", ")
+        )
+    }
+}
+
+/// The state of the loader while the files of a schema are read.
+struct Loading {
+    modules: Vec<NetworkModule>,
+    /// The canonical paths of the files, which were added to the modules.
+    loaded: HashSet<PathBuf>,
+    /// The canonical paths and names of the files, whose' imports are read.
+    stack: Vec<(PathBuf, String)>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Loading {
+    /// Adds a module with the parser errors of its' file.
+    fn add(&mut self, module: NetworkModule, diagnostics: Vec<Diagnostic>) {
+        self.diagnostics.extend(
+            diagnostics
+                .into_iter()
+                .map(|diagnostic| diagnostic.with_file(module.name())),
+        );
+        self.modules.push(module);
+    }
+}
+
+#[cfg(test)]
+mod loader_test {
+    use super::*;
+
+    const IMPORTS: &str = "../playground/net/imports";
+
+    fn load(file: &str) -> NetworkSchema {
+        NetworkLoader::new()
+            .load(Path::new(IMPORTS).join(file))
+            .unwrap()
+    }
+
+    #[test]
+    fn relative_imports() {
+        let schema = load("forum.net");
+        assert!(schema.diagnostics().is_empty());
+        assert_eq!(schema.modules().count(), 2);
+
+        let namespace = schema.namespace();
+        assert!(namespace.structure("Post").is_some());
+        assert!(namespace.structure("User").is_some());
+    }
+
+    /// `string`, `bool` and `bit` are declared in the prelude and resolve
+    /// to the wire types.
+    #[test]
+    fn prelude_types() {
+        let schema = load("forum.net");
+        let entry = schema.modules().last().unwrap().file();
+        assert!(!entry.primitives().contains(&"string".to_owned()));
+
+        let namespace = schema.namespace();
+        assert_eq!(
+            namespace.resolve_primitive("string").map(|t| t.to_string()),
+            Some("u8[]".to_owned())
+        );
+        assert_eq!(
+            namespace.resolve_primitive("bit").map(|t| t.to_string()),
+            Some("u(1)".to_owned())
+        );
+
+        let schema = NetworkLoader::new()
+            .with_prelude(Prelude::Disabled)
+            .load(Path::new(IMPORTS).join("forum.net"))
+            .unwrap();
+        let diagnostics = schema.diagnostics();
+        assert_eq!(diagnostics.len(), 3);
+        assert_eq!(
+            diagnostics[0].message(),
+            "cannot find type `string` in this file"
+        );
+    }
+
+    /// A file imported twice through different files is loaded once.
+    #[test]
+    fn diamond_imports() {
+        let schema = load("diamond.net");
+        assert!(schema.diagnostics().is_empty());
+        let users = schema
+            .modules()
+            .filter(|module| module.name().ends_with("users.net"))
+            .count();
+        assert_eq!(users, 1);
+    }
+
+    #[test]
+    fn search_roots() {
+        let schema = load("rooted.net");
+        assert_eq!(
+            schema.diagnostics()[0].message(),
+            "cannot find the file `users.net`"
+        );
+
+        let schema = NetworkLoader::new()
+            .with_root(Path::new(IMPORTS).join("shared"))
+            .load(Path::new(IMPORTS).join("rooted.net"))
+            .unwrap();
+        assert!(schema.diagnostics().is_empty());
+        assert!(schema
+            .module("../playground/net/imports/shared/users.net")
+            .is_some());
+    }
+
+    #[test]
+    fn import_cycle() {
+        let schema = load("cycle_a.net");
+        let diagnostics = schema.diagnostics();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].message(),
+            "the import of `cycle_a.net` is cyclic"
+        );
+        assert_eq!(
+            diagnostics[0].file(),
+            Some("../playground/net/imports/cycle_b.net")
+        );
+        assert_eq!(
+            diagnostics[0].notes(),
+            ["the import cycle is `../playground/net/imports/cycle_a.net` -> `../playground/net/imports/cycle_b.net` -> `../playground/net/imports/cycle_a.net`"]
+        );
+
+        // both files are loaded and share their' declarations
+        assert!(schema.namespace().structure("A").is_some());
+        assert!(schema.namespace().structure("B").is_some());
+    }
+
+    /// Diagnostics name the file they refer to, names declared in several
+    /// files refer to the file of the first declaration.
+    #[test]
+    fn file_qualified_diagnostics() {
+        let schema = load("broken.net");
+        let diagnostics = schema.diagnostics();
+        let messages = diagnostics
+            .iter()
+            .map(|diagnostic| (diagnostic.file().unwrap(), diagnostic.message()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            messages,
+            [
+                (
+                    "../playground/net/imports/broken.net",
+                    "the name `User` is defined multiple times"
+                ),
+                (
+                    "../playground/net/imports/broken.net",
+                    "cannot find type `Usr` in this file"
+                ),
+            ]
+        );
+        assert_eq!(
+            diagnostics[0].notes(),
+            ["`User` is first defined in `../playground/net/imports/shared/users.net` on line 3"]
+        );
+        assert_eq!(diagnostics[1].help(), Some("did you mean `User`?"));
+    }
+
+    /// Constants are evaluated across files, and every constant, which
+    /// can not be evaluated, is reported.
+    #[test]
+    fn const_diagnostics() {
+        let schema = load("consts.net");
+        let diagnostics = schema.diagnostics();
+        let messages = diagnostics
+            .iter()
+            .map(|diagnostic| (diagnostic.span().line(), diagnostic.message()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            messages,
+            [
+                (
+                    4,
+                    "the constant `MAX_LIKES` of type `u8` can not hold the value `300`"
+                ),
+                (
+                    5,
+                    "the constant `MAX_TAGS` references the unknown `MAX_TOPICS`"
+                ),
+            ]
+        );
+        assert_eq!(
+            diagnostics[0].file(),
+            Some("../playground/net/imports/consts.net")
+        );
+        assert_eq!(diagnostics[1].label(), Some("`MAX_TOPICS` is not declared"));
+    }
+
+    #[test]
+    fn unreadable_entry() {
+        assert!(NetworkLoader::new().load("missing.net").is_err());
+    }
+}
@@ -1,17 +1,71 @@
+pub mod alias;
 pub mod comment;
+pub mod constant;
+pub mod diagnostic;
+pub mod documentation;
+pub mod enumeration;
+pub mod error;
+pub mod evaluate;
+pub mod expression;
 pub mod field;
+pub mod flags;
 pub mod identifier;
+pub mod import;
 pub mod interface;
+pub mod lint;
+pub mod loader;
 pub mod network;
+pub mod prelude;
+pub mod protocol;
+pub mod resolver;
 pub mod structure;
 pub mod tag;
 
-/// The primitive types that the network file accepts per default.
-/// This includes integers, a boolean and string value.
-static PRIMITIVE_TYPES: &'static [&'static str] = &[
-    "u8", "i8", "u16", "i16", "u32", "i32", "u64", "i64", "bool", "string",
+/// The primitive types that the network file accepts per default. These
+/// are the types encoded on the wire directly, every other type such as
+/// `string`, `bool` and `bit` is declared in the [prelude](prelude::Prelude).
+static PRIMITIVE_TYPES: &[&str] = &[
+    "u8", "i8", "u16", "i16", "u32", "i32", "u64", "i64", "f32", "f64",
 ];
 
+/// The floating point primitives, which are encoded as IEEE 754 numbers
+/// in little endian order. They can not be compared for equality, so
+/// they are no map keys.
+static FLOAT_TYPES: &[&str] = &["f32", "f64"];
+
+/// The integer primitives, which are encoded as 7-bit varints by default,
+/// signed integers after the zigzag encoding. The `@fixed` tag encodes them
+/// in fixed width and little endian order instead. Integers of 8 bits are
+/// always written as one byte.
+pub static VARINT_TYPES: &[&str] = &["u16", "i16", "u32", "i32", "u64", "i64"];
+
+/// The sign and the amount of bits of an integer primitive, such as `u16`.
+pub(crate) fn integer_primitive(name: &str) -> Option<(bool, u32)> {
+    match name {
+        "u8" => Some((false, 8)),
+        "i8" => Some((true, 8)),
+        "u16" => Some((false, 16)),
+        "i16" => Some((true, 16)),
+        "u32" => Some((false, 32)),
+        "i32" => Some((true, 32)),
+        "u64" => Some((false, 64)),
+        "i64" => Some((true, 64)),
+        _ => None,
+    }
+}
+
+/// The smallest and the largest value of an integer with the amount of
+/// bits. The largest value of `u(128)` does not fit into an `i128`, so it
+/// is capped at `i128::MAX`. The constant evaluator rejects `.MAX` of such
+/// a type instead of reading the capped value.
+pub(crate) fn integer_range(signed: bool, bits: u32) -> (i128, i128) {
+    match (signed, bits.min(128)) {
+        (_, 0) => (0, 0),
+        (true, bits) => (i128::MIN >> (128 - bits), i128::MAX >> (128 - bits)),
+        (false, bits) => (0, i128::MAX >> (127 - bits.min(127))),
+    }
+}
+
 // /// The reserved identifiers are custom keywords that cannot be
 // /// chosen for field, type and name identifiers. 
 // static RESERVED_IDENTIFIERS: &'static [&'static str] = &[
@@ -1,18 +1,73 @@
-use super::{interface::NetworkParser, structure::NetworkStruct, PRIMITIVE_TYPES};
-use crate::parser::comment::Comment;
-use nom::{branch::Alt, combinator::eof, multi::many0, sequence::pair, IResult};
+use std::{cell::RefCell, collections::HashSet, slice::Iter};
+
+use super::{
+    alias::NetworkType,
+    constant::NetworkConst,
+    enumeration::NetworkEnum,
+    diagnostic::Diagnostic,
+    error::{skip_declaration, NetworkError, ParseResult, Recovery, Span},
+    expression::{TypeExpression, TypeKind},
+    flags::NetworkFlags,
+    import::NetworkImport,
+    interface::NetworkParser,
+    prelude::Prelude,
+    protocol::NetworkProtocol,
+    resolver::Resolver,
+    structure::NetworkStruct,
+    tag::Tag,
+    PRIMITIVE_TYPES,
+};
+use crate::parser::{comment::Comment, documentation::Documentation};
+use nom::{
+    branch::alt,
+    character::complete::multispace0,
+    combinator::eof,
+    multi::many0,
+    sequence::{pair, preceded},
+    Err, Parser,
+};
 
 /// The `NetworkFileReader` defines a grammar state for a network file.
 #[derive(Debug, PartialEq)]
 pub struct NetworkFileReader {
+    documentation: Option<Documentation>,
+    tags: Vec<Tag>,
     primitive_types: Vec<String>,
+    prelude: Prelude,
+    imports: Vec<NetworkImport>,
     structures: Vec<NetworkStruct>,
+    enumerations: Vec<NetworkEnum>,
+    flags: Vec<NetworkFlags>,
+    aliases: Vec<NetworkType>,
+    consts: Vec<NetworkConst>,
+    protocols: Vec<NetworkProtocol>,
 }
 
-// #[derive(Debug, PartialEq)]
-// pub enum NetworkElement {
-//     NStruct(NetworkStruct),
-// }
+/// A network element is any top-level definition of a network file.
+#[derive(Debug, PartialEq)]
+pub enum NetworkElement {
+    NImport(NetworkImport),
+    NStruct(NetworkStruct),
+    NEnum(NetworkEnum),
+    NFlags(NetworkFlags),
+    NType(NetworkType),
+    NConst(NetworkConst),
+    NProtocol(NetworkProtocol),
+}
+
+impl NetworkParser for NetworkElement {
+    fn parse(input: Span) -> ParseResult<Self> {
+        alt((
+            NetworkImport::parse.map(Self::NImport),
+            NetworkStruct::parse.map(Self::NStruct),
+            NetworkEnum::parse.map(Self::NEnum),
+            NetworkFlags::parse.map(Self::NFlags),
+            NetworkType::parse.map(Self::NType),
+            NetworkConst::parse.map(Self::NConst),
+            NetworkProtocol::parse.map(Self::NProtocol),
+        ))(input)
+    }
+}
 
 impl NetworkFileReader {
     // pub fn new() -> Self {
@@ -22,6 +77,92 @@ impl NetworkFileReader {
     //     }
     // }
 
+    /// A file without declarations.
+    pub(crate) fn empty(documentation: Option<Documentation>) -> Self {
+        Self {
+            documentation,
+            tags: vec![],
+            primitive_types: Self::get_default_primitives(),
+            prelude: Prelude::default(),
+            imports: vec![],
+            structures: vec![],
+            enumerations: vec![],
+            flags: vec![],
+            aliases: vec![],
+            consts: vec![],
+            protocols: vec![],
+        }
+    }
+
+    /// The documentation of the file itself, read from the `//!` inner
+    /// documentation comments at the beginning of the file.
+    pub fn documentation(&self) -> Option<&Documentation> {
+        self.documentation.as_ref()
+    }
+
+    /// The tags of the file itself, read from the `//!` inner documentation
+    /// comments at the beginning of the file.
+    pub fn tags(&self) -> Iter<'_, Tag> {
+        self.tags.iter()
+    }
+
+    /// Parses a complete network file. If the file is invalid, all of its'
+    /// errors are returned as diagnostics, which can be rendered with the
+    /// source.
+    ///
+    /// ```
+    /// use network_parser::parser::network::NetworkFileReader;
+    ///
+    /// let diagnostics = NetworkFileReader::from_source("struct Post {\n    author User;\n}").unwrap_err();
+    /// assert_eq!(diagnostics[0].message(), "expected `:`, found `User`");
+    /// assert_eq!(diagnostics[0].notes(), &["in field `author` of struct `Post`"]);
+    /// ```
+    pub fn from_source(source: &str) -> Result<Self, Vec<Diagnostic>> {
+        match Self::parse_recovering(source) {
+            (reader, diagnostics) if diagnostics.is_empty() => Ok(reader),
+            (_, diagnostics) => Err(diagnostics),
+        }
+    }
+
+    /// Parses a complete network file and recovers from errors. Invalid
+    /// members are skipped to the next `;` or `}` and invalid declarations
+    /// to the next line starting with a declaration keyword. Returns the
+    /// declarations, which could be read, and the diagnostics of all errors
+    /// ordered by their' location.
+    ///
+    /// ```
+    /// use network_parser::parser::network::NetworkFileReader;
+    ///
+    /// let source = "struct Post {\n    author User;\n    id: u64;\n}\n\nstrcut Author {}\n";
+    /// let (reader, diagnostics) = NetworkFileReader::parse_recovering(source);
+    ///
+    /// assert_eq!(reader.structure("Post").unwrap().fields().len(), 1);
+    /// assert_eq!(diagnostics.len(), 2);
+    /// ```
+    pub fn parse_recovering(source: &str) -> (Self, Vec<Diagnostic>) {
+        let errors = RefCell::new(Vec::new());
+        let input = Span::new_extra(source, Recovery::new(&errors));
+
+        let reader = match Self::parse(input) {
+            Ok((_, reader)) => reader,
+            Err(Err::Error(error) | Err::Failure(error)) => {
+                // only errors outside of declarations are left
+                input.extra.report(error);
+                Self::empty(None)
+            }
+            Err(Err::Incomplete(_)) => unreachable!("the network parsers read complete input"),
+        };
+
+        let mut diagnostics = errors
+            .borrow()
+            .iter()
+            .map(NetworkError::to_diagnostic)
+            .collect::<Vec<_>>();
+
+        diagnostics.sort_by_key(|diagnostic| diagnostic.span().offset());
+        (reader, diagnostics)
+    }
+
     pub fn get_default_primitives() -> Vec<String> {
         PRIMITIVE_TYPES.iter().map(|str| str.to_string()).collect()
     }
@@ -39,6 +180,28 @@ impl NetworkFileReader {
         self.primitive_types.clone()
     }
 
+    /// Replace the prelude, whose' declarations the file can use without
+    /// declaring them. The standard library is the default prelude.
+    pub fn set_prelude(&mut self, prelude: Prelude) {
+        self.prelude = prelude;
+    }
+
+    /// The declarations of the prelude, `None` if it is disabled.
+    pub fn prelude(&self) -> Option<&NetworkFileReader> {
+        self.prelude.file()
+    }
+
+    /// Add a new import
+    pub fn register_import(&mut self, value: NetworkImport) {
+        self.imports.push(value);
+    }
+
+    /// Get an iterator over all imports of the file, in the order they
+    /// were declared.
+    pub fn imports(&self) -> Iter<'_, NetworkImport> {
+        self.imports.iter()
+    }
+
     /// Add a new structure
     pub fn register_structure(&mut self, value: NetworkStruct) {
         self.structures.push(value);
@@ -51,21 +214,209 @@ impl NetworkFileReader {
             .into_iter()
             .find(|structure| structure.name() == name)
     }
+
+    /// Get an iterator over all declared structures.
+    pub fn structures(&self) -> Iter<'_, NetworkStruct> {
+        self.structures.iter()
+    }
+
+    /// Add a new enumeration
+    pub fn register_enumeration(&mut self, value: NetworkEnum) {
+        self.enumerations.push(value);
+    }
+
+    /// Read an enumeration from the parser contents.
+    pub fn enumeration(&self, name: &str) -> Option<NetworkEnum> {
+        self.enumerations
+            .iter()
+            .find(|enumeration| enumeration.name() == name)
+            .cloned()
+    }
+
+    /// Get an iterator over all declared enumerations.
+    pub fn enumerations(&self) -> Iter<'_, NetworkEnum> {
+        self.enumerations.iter()
+    }
+
+    /// Add new flags
+    pub fn register_flags(&mut self, value: NetworkFlags) {
+        self.flags.push(value);
+    }
+
+    /// Read flags from the parser contents.
+    pub fn flags(&self, name: &str) -> Option<NetworkFlags> {
+        self.flags.iter().find(|flags| flags.name() == name).cloned()
+    }
+
+    /// Get an iterator over all declared flags.
+    pub fn all_flags(&self) -> Iter<'_, NetworkFlags> {
+        self.flags.iter()
+    }
+
+    /// Add a new type alias
+    pub fn register_alias(&mut self, value: NetworkType) {
+        self.aliases.push(value);
+    }
+
+    /// Read a type alias from the parser contents.
+    pub fn alias(&self, name: &str) -> Option<NetworkType> {
+        self.aliases.iter().find(|alias| alias.name() == name).cloned()
+    }
+
+    /// Get an iterator over all declared type aliases.
+    pub fn aliases(&self) -> Iter<'_, NetworkType> {
+        self.aliases.iter()
+    }
+
+    /// Read a type alias of the file or, if the file does not declare it,
+    /// of the prelude.
+    pub(crate) fn visible_alias(&self, name: &str) -> Option<NetworkType> {
+        self.alias(name)
+            .or_else(|| self.prelude().and_then(|prelude| prelude.alias(name)))
+    }
+
+    /// Follow a chain of type aliases until the aliased element type is
+    /// no alias itself. The array dimensions along the chain add up, so
+    /// `Names` in `type Name = Tag; type Names = Name[];` resolves to
+    /// `Tag[]`. Aliases of the prelude are followed as well, so `string`
+    /// resolves to `u8[]`. Returns `None` if `name` is no alias or the
+    /// aliases are cyclic.
+    pub fn resolve_alias(&self, name: &str) -> Option<TypeExpression> {
+        let mut visited = HashSet::new();
+        let mut resolved = self.visible_alias(name)?.aliased().clone();
+        visited.insert(name.to_owned());
+
+        while let Some(alias) = resolved
+            .element()
+            .name()
+            .and_then(|name| self.visible_alias(name))
+        {
+            if !visited.insert(alias.name().to_owned()) {
+                return None;
+            }
+
+            resolved = alias
+                .aliased()
+                .clone()
+                .with_array_dimension(resolved.array_dimension());
+        }
+
+        Some(resolved)
+    }
+
+    /// Add a new constant
+    pub fn register_const(&mut self, value: NetworkConst) {
+        self.consts.push(value);
+    }
+
+    /// Read a constant from the parser contents.
+    pub fn constant(&self, name: &str) -> Option<NetworkConst> {
+        self.consts
+            .iter()
+            .find(|constant| constant.name() == name)
+            .cloned()
+    }
+
+    /// Get an iterator over all declared constants.
+    pub fn consts(&self) -> Iter<'_, NetworkConst> {
+        self.consts.iter()
+    }
+
+    /// Add a new protocol
+    pub fn register_protocol(&mut self, value: NetworkProtocol) {
+        self.protocols.push(value);
+    }
+
+    /// Read a protocol from the parser contents.
+    pub fn protocol(&self, name: &str) -> Option<NetworkProtocol> {
+        self.protocols
+            .iter()
+            .find(|protocol| protocol.name() == name)
+            .cloned()
+    }
+
+    /// Get an iterator over all declared protocols.
+    pub fn protocols(&self) -> Iter<'_, NetworkProtocol> {
+        self.protocols.iter()
+    }
+
+    /// Links the type references of the file to their' declarations and
+    /// reports unknown types and duplicate names, see [Resolver].
+    pub fn resolve(&self) -> Resolver<'_> {
+        Resolver::new(self)
+    }
+
+    /// Resolve a type alias down to its' underlying primitive. Returns
+    /// `None` if the alias does not end in a registered primitive or a
+    /// sized integer, such as `u(1)`.
+    pub fn resolve_primitive(&self, name: &str) -> Option<TypeExpression> {
+        self.resolve_alias(name).filter(|resolved| {
+            matches!(resolved.element().kind(), TypeKind::Integer { .. })
+                || self
+                    .primitive_types
+                    .iter()
+                    .any(|p| Some(p.as_str()) == resolved.element().name())
+        })
+    }
 }
 
 impl NetworkParser for NetworkFileReader {
-    fn parse(input: &str) -> IResult<&str, Self> {
-        // read multiple structures
-        // TODO add other definition types
-        let (input, structures) = many0(NetworkStruct::parse)(input)?;
+    fn parse(input: Span) -> ParseResult<Self> {
+        // read the inner documentation of the file
+        let (input, inner_docs) =
+            many0(preceded(multispace0, Comment::c_comment_inner_docs))(input)?;
 
-        // parse and ignore any trailing whitespace
-        let (input, _) = pair(Comment::parse, eof)(input)?;
+        let documentation = inner_docs
+            .iter()
+            .filter_map(Comment::read_comment)
+            .collect::<Vec<_>>()
+            .join("\n");
 
-        IResult::Ok((input, NetworkFileReader {
-            primitive_types: Self::get_default_primitives(),
-            structures,
-        }))
+        // read definitions until only whitespace and comments are left,
+        // so the error of an invalid definition is not discarded
+        let mut input = input;
+        let mut elements = Vec::new();
+
+        loop {
+            if let Ok((tmp_input, _)) = pair(Comment::parse, eof)(input) {
+                input = tmp_input;
+                break;
+            }
+
+            match NetworkElement::parse(input) {
+                Ok((tmp_input, element)) => {
+                    input = tmp_input;
+                    elements.push(element);
+                }
+                Err(Err::Error(error) | Err::Failure(error)) if input.extra.is_recovering() => {
+                    let (begin, _) = Comment::parse(input)?;
+                    let error_input = error.input();
+                    input.extra.report(error);
+                    input = skip_declaration(begin, error_input);
+                }
+                Err(error) => return Err(error),
+            }
+        }
+
+        let mut reader = NetworkFileReader::empty(
+            Some(Documentation::new(&documentation))
+                .filter(|documentation| !documentation.is_empty()),
+        );
+        reader.tags = Tag::from_documentation(&documentation);
+
+        for element in elements {
+            match element {
+                NetworkElement::NImport(import) => reader.register_import(import),
+                NetworkElement::NStruct(structure) => reader.register_structure(structure),
+                NetworkElement::NEnum(enumeration) => reader.register_enumeration(enumeration),
+                NetworkElement::NFlags(flags) => reader.register_flags(flags),
+                NetworkElement::NType(alias) => reader.register_alias(alias),
+                NetworkElement::NConst(constant) => reader.register_const(constant),
+                NetworkElement::NProtocol(protocol) => reader.register_protocol(protocol),
+            }
+        }
+
+        ParseResult::Ok((input, reader))
     }
 }
 
@@ -75,11 +426,11 @@ impl NetworkParser for NetworkFileReader {
 /// ### Contributing Tests
 ///
 /// - At the beginning of the test parse a string with `NetworkFileReader`
-/// and unwrap it or assert an error. Longer strings should be defined as
-/// a variable.
+///   and unwrap it or assert an error. Longer strings should be defined as
+///   a variable.
 /// - Since all files have to be consumed, it's expected for
-/// every test to include `assert_eq!(input, "");` to make sure,
-/// that the entire input was read.
+///   every test to include `assert_eq!(input, "");` to make sure,
+///   that the entire input was read.
 #[cfg(test)]
 mod network_test {
     use super::*;
@@ -88,8 +439,8 @@ mod network_test {
     #[test]
     fn simple_struct() {
         let (input, network_struct) =
-            NetworkFileReader::parse("\nstruct Struct {\n\tid: u8;\n}\n").unwrap();
-        assert_eq!(input, "");
+            NetworkFileReader::parse_str("\nstruct Struct {\n\tid: u8;\n}\n").unwrap();
+        assert_eq!(*input.fragment(), "");
         assert_eq!(network_struct.structure("NonExistingStructure"), None);
         assert_eq!(network_struct.structure("Struct").unwrap().name(), "Struct");
         assert_eq!(network_struct.structure("Struct").unwrap().fields().len(), 1);
@@ -100,8 +451,8 @@ mod network_test {
     #[test]
     fn two_structs() {
         let (input, network_struct) =
-            NetworkFileReader::parse("\nstruct Abc {\n\tabc: u8;\n}\n\nstruct Def {\n\tdef: u16;\n}\n").unwrap();
-        assert_eq!(input, "");
+            NetworkFileReader::parse_str("\nstruct Abc {\n\tabc: u8;\n}\n\nstruct Def {\n\tdef: u16;\n}\n").unwrap();
+        assert_eq!(*input.fragment(), "");
         assert_eq!(network_struct.structure("NonExistingStructure"), None);
         assert!(network_struct.structure("Abc").is_some());
         assert!(network_struct.structure("Def").is_some());
@@ -110,15 +461,15 @@ mod network_test {
     /// The empty file should not return in an error.
     #[test]
     fn empty_file() {
-        let (input, network_struct) = NetworkFileReader::parse("").unwrap();
-        assert_eq!(input, "");
+        let (input, _network_struct) = NetworkFileReader::parse_str("").unwrap();
+        assert_eq!(*input.fragment(), "");
     }
 
     /// A file filled with spaces should not return in an error.
     #[test]
     fn spacious_file() {
-        let (input, network_struct) = NetworkFileReader::parse(" \r \t \n ").unwrap();
-        assert_eq!(input, "");
+        let (input, _network_struct) = NetworkFileReader::parse_str(" \r \t \n ").unwrap();
+        assert_eq!(*input.fragment(), "");
     }
 
     /// A structure with a comment should have the message as a part
@@ -126,8 +477,374 @@ mod network_test {
     #[test]
     fn commented_struct() {
         let (input, network_struct) =
-            NetworkFileReader::parse("/// This is a very simple structure.\nstruct Struct {}\n")
+            NetworkFileReader::parse_str("/// This is a very simple structure.\nstruct Struct {}\n")
                 .unwrap();
-        assert_eq!(input, "");
+        assert_eq!(*input.fragment(), "");
+        assert_eq!(
+            network_struct
+                .structure("Struct")
+                .unwrap()
+                .documentation()
+                .map(Documentation::description),
+            Some("This is a very simple structure.")
+        );
+    }
+
+    /// A file can document itself with inner documentation comments at
+    /// the beginning of the file.
+    #[test]
+    fn documented_file() {
+        let source = "\n//! A forum.\n//!\n//! @note Work in progress.\n\n// TODO\nstruct Post {\n\t/// @description The author.\n\tauthor: User;\n}\n";
+        let (input, network_file) = NetworkFileReader::parse_str(source).unwrap();
+        assert_eq!(*input.fragment(), "");
+
+        let documentation = network_file.documentation().unwrap();
+        assert_eq!(documentation.description(), "A forum.");
+        assert_eq!(
+            documentation.doc_tag("note").unwrap().content(),
+            "Work in progress."
+        );
+
+        let post = network_file.structure("Post").unwrap();
+        assert_eq!(post.documentation(), None);
+        assert_eq!(
+            post.fields()
+                .next()
+                .unwrap()
+                .documentation()
+                .map(Documentation::description),
+            Some("The author.")
+        );
+    }
+
+    /// An undocumented file has no documentation.
+    #[test]
+    fn undocumented_file() {
+        let (_, network_file) = NetworkFileReader::parse_str("// A comment.\nstruct Post {}").unwrap();
+        assert_eq!(network_file.documentation(), None);
+    }
+
+    /// A file defining a structure and an enumeration should register
+    /// both of them.
+    #[test]
+    fn struct_and_enum() {
+        let (input, network_file) = NetworkFileReader::parse_str(
+            "\nstruct Foo {\n\tsmall: u8;\n}\n\nenum Bar {\n\tSmall(u8) = 1;\n\tLarge(u32) = 3;\n}\n",
+        )
+        .unwrap();
+        assert_eq!(*input.fragment(), "");
+        assert!(network_file.structure("Foo").is_some());
+        assert_eq!(network_file.enumeration("Foo"), None);
+        assert_eq!(network_file.enumeration("Bar").unwrap().members().len(), 2);
+    }
+
+    /// The recursive `Any` enumeration from the README should parse.
+    #[test]
+    fn recursive_any_enum() {
+        let file = r#"
+enum Any {
+    Null = 0;
+    Bool(bool) = 1;
+    Number(i(64)) = 2;
+    String(string) = 3;
+    Array(Any[]) = 4;
+    Object(map(string -> Any)) = 5;
+}
+"#;
+        let (input, network_file) = NetworkFileReader::parse_str(file).unwrap();
+        assert_eq!(*input.fragment(), "");
+
+        let any = network_file.enumeration("Any").unwrap();
+        assert_eq!(any.members().len(), 6);
+
+        let payload = |name| any.member(name).unwrap().payload().next().unwrap().clone();
+        assert_eq!(payload("Number"), TypeExpression::integer(true, 64));
+        assert_eq!(payload("Array").array_dimension(), 1);
+        assert_eq!(payload("Object").to_string(), "map(string -> Any)");
+    }
+
+    /// Flags with overlapping positions are read, and reported by the
+    /// resolver.
+    #[test]
+    fn overlapping_flags() {
+        let (_, network_file) =
+            NetworkFileReader::parse_str("flags F {\n\tA = 1;\n\tB = 1;\n}\n").unwrap();
+        assert_eq!(network_file.resolve().diagnostics().len(), 1);
+
+        let (input, network_file) =
+            NetworkFileReader::parse_str("flags F {\n\tA = 1;\n\tB = 2;\n}\n").unwrap();
+        assert_eq!(*input.fragment(), "");
+        assert_eq!(network_file.flags("F").unwrap().members().len(), 2);
+    }
+
+    /// Aliases resolve through other aliases down to a primitive.
+    #[test]
+    fn resolve_aliases() {
+        let (input, network_file) = NetworkFileReader::parse_str(
+            "type Name = string;\ntype Names = Name[];\ntype Author = User;\n",
+        )
+        .unwrap();
+        assert_eq!(*input.fragment(), "");
+        assert_eq!(
+            network_file.alias("Name").unwrap().aliased().name(),
+            Some("string")
+        );
+        // `string` is an alias of the prelude
+        assert_eq!(
+            network_file.resolve_alias("Names"),
+            Some(TypeExpression::named("u8").with_array_dimension(2))
+        );
+        assert_eq!(
+            network_file.resolve_primitive("Names").unwrap().element().name(),
+            Some("u8")
+        );
+        assert_eq!(
+            network_file.resolve_alias("Author").unwrap().name(),
+            Some("User")
+        );
+        assert_eq!(network_file.resolve_primitive("Author"), None);
+        assert_eq!(network_file.resolve_alias("Unknown"), None);
+    }
+
+    /// Cyclic aliases can not be resolved.
+    #[test]
+    fn cyclic_aliases() {
+        let (_, network_file) =
+            NetworkFileReader::parse_str("type A = B;\ntype B = C[];\ntype C = A;\n").unwrap();
+        assert_eq!(network_file.resolve_alias("A"), None);
+        assert_eq!(network_file.resolve_alias("C"), None);
+    }
+
+    /// The type aliases of the standard library resolve to the `u8`
+    /// wire primitive.
+    #[test]
+    fn stdlib_aliases() {
+        let file = r#"//! This is the standard library for Netz files.
+
+/// @description The primitive string type, gets encoded as a
+/// byte array and converted to its' ascii representation upon
+/// decoding.
+type string = u8[];
+
+/// @description The boolean data type, inherits from [u8].
+type bool = u8;
+"#;
+        let (input, network_file) = NetworkFileReader::parse_str(file).unwrap();
+        assert_eq!(*input.fragment(), "");
+        assert_eq!(
+            network_file.resolve_primitive("string"),
+            Some(TypeExpression::array(TypeExpression::named("u8")))
+        );
+        assert_eq!(
+            network_file.resolve_primitive("bool"),
+            Some(TypeExpression::named("u8"))
+        );
+    }
+
+    /// The standard library should parse entirely.
+    #[test]
+    fn stdlib() {
+        let (input, network_file) =
+            NetworkFileReader::parse_str(include_str!("../../../netz-stdlib/stdlib.net")).unwrap();
+        assert_eq!(*input.fragment(), "");
+        assert!(network_file.alias("string").is_some());
+        assert!(network_file.constant("true").is_some());
+        assert!(network_file.constant("false").is_some());
+        assert_eq!(
+            network_file.documentation().map(Documentation::description),
+            Some("This is the standard library for Netz files.")
+        );
+
+        let string = network_file.alias("string").unwrap();
+        assert!(string
+            .documentation()
+            .unwrap()
+            .description()
+            .starts_with("The primitive string type"));
+        assert_eq!(string.tags().next().unwrap().name(), "string_convert");
+    }
+
+    /// A protocol references structures declared in the same file.
+    #[test]
+    fn protocol_and_structs() {
+        let file = "struct Ping {}\nstruct Message {\n\tcontent: string;\n}\n\nprotocol Connection {\n\tPing = 0;\n\t(Message -> ()) = 1;\n}\n";
+        let (input, network_file) = NetworkFileReader::parse_str(file).unwrap();
+        assert_eq!(*input.fragment(), "");
+        assert_eq!(network_file.protocol("Connection").unwrap().entries().len(), 2);
+        assert_eq!(network_file.protocol("Ping"), None);
+    }
+
+    /// A misspelled keyword lists all declarations, which were expected.
+    #[test]
+    fn misspelled_keyword() {
+        let diagnostic = NetworkFileReader::from_source("struct A {}\n\nstrcut B {}\n").unwrap_err().remove(0);
+        assert_eq!(
+            diagnostic.message(),
+            "expected `import`, `struct`, `enum`, `flags`, `type`, `const` or `protocol`, found `strcut`"
+        );
+        assert_eq!(diagnostic.span().line(), 3);
+        assert_eq!(diagnostic.span().column(), 1);
+    }
+
+    /// An error inside of a declaration is reported where it occurred,
+    /// with the declarations it occurred in.
+    #[test]
+    fn field_error_snippet() {
+        let source = "struct Post {\n\tauthor: User;\n\tcontent Content;\n}\n";
+        let diagnostic = NetworkFileReader::from_source(source).unwrap_err().remove(0);
+        assert_eq!(
+            diagnostic.render("forum.net", source),
+            "error: expected `:`, found `Content`
+ --> forum.net:3:10
+  |
+3 |     content Content;
+  |             ^^^^^^^ unexpected `Content`
+  |
+  = note: in field `content` of struct `Post`
+"
+        );
+    }
+
+    /// A missing semicolon is reported at the next declaration.
+    #[test]
+    fn missing_semicolon() {
+        let diagnostic =
+            NetworkFileReader::from_source("const A: u8 = 1\nconst B: u8 = 2;").unwrap_err().remove(0);
+        assert_eq!(diagnostic.message(), "expected `;`, found `const`");
+        assert_eq!(diagnostic.span().line(), 2);
+        assert_eq!(diagnostic.notes(), &["in const `A`"]);
+    }
+
+    /// An unclosed structure is reported at the end of the file.
+    #[test]
+    fn unclosed_struct() {
+        let diagnostic = NetworkFileReader::from_source("struct Post {\n\tid: u8;\n").unwrap_err().remove(0);
+        assert_eq!(diagnostic.message(), "expected `}`, found end of file");
+        assert_eq!(diagnostic.label(), Some("unexpected end of file"));
+    }
+
+    /// The errors after the type of a shorthand field are not reported as
+    /// a missing colon.
+    #[test]
+    fn invalid_shorthand() {
+        let diagnostic = NetworkFileReader::from_source("struct S {\n\tField = ;\n}")
+            .unwrap_err()
+            .remove(0);
+        assert_eq!(diagnostic.message(), "expected integer, found `;`");
+
+        let diagnostic = NetworkFileReader::from_source("struct S {\n\tField[ ;\n}")
+            .unwrap_err()
+            .remove(0);
+        assert!(!diagnostic.message().contains("`:`"));
+    }
+
+    /// Validation errors keep their' message and point at the member.
+    #[test]
+    fn overlapping_flags_diagnostic() {
+        let network_file = NetworkFileReader::from_source("flags F {\n\tA = 1;\n\tB = 1;\n}").unwrap();
+        let resolver = network_file.resolve();
+        let diagnostic = &resolver.diagnostics()[0];
+        assert_eq!(diagnostic.span().line(), 3);
+        assert!(diagnostic.message().contains("position 1"));
+    }
+
+    /// A recovering parse reports every error of the file and keeps the
+    /// declarations and members, which could be read.
+    #[test]
+    fn recover_all_errors() {
+        let source = "struct Post {\n\tauthor User;\n\tid: u64;\n\tcontent: ;\n}\n\nstrcut Author {\n\tname: string;\n}\n\nconst A: u8 = 1\nconst B: u8 = 2;\n\nflags Kind {\n\tText = ;\n\tImage = 1;\n}\n";
+        let (network_file, diagnostics) = NetworkFileReader::parse_recovering(source);
+
+        let messages = diagnostics.iter().map(Diagnostic::message).collect::<Vec<_>>();
+        assert_eq!(
+            messages,
+            vec![
+                "expected `:`, found `User`",
+                "expected type, found `;`",
+                "expected `import`, `struct`, `enum`, `flags`, `type`, `const` or `protocol`, found `strcut`",
+                "expected `;`, found `const`",
+                "expected integer, found `;`",
+            ]
+        );
+        assert_eq!(diagnostics[0].span().line(), 2);
+        assert_eq!(diagnostics[2].span().line(), 7);
+
+        let post = network_file.structure("Post").unwrap();
+        assert_eq!(post.fields().len(), 1);
+        assert_eq!(post.fields().next().unwrap().name(), "id");
+        assert_eq!(network_file.structure("Author"), None);
+        assert_eq!(network_file.constant("A"), None);
+        assert!(network_file.constant("B").is_some());
+        assert_eq!(network_file.flags("Kind").unwrap().members().len(), 1);
+    }
+
+    /// An unclosed declaration is skipped to the next declaration.
+    #[test]
+    fn recover_unclosed_struct() {
+        let (network_file, diagnostics) =
+            NetworkFileReader::parse_recovering("struct A {\n\tid: u8\n\nstruct B {}\n");
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].message(), "expected `;`, found `struct`");
+        assert_eq!(diagnostics[1].message(), "expected `}`, found `struct`");
+        assert!(network_file.structure("B").is_some());
+    }
+
+    /// A valid file has no diagnostics.
+    #[test]
+    fn recover_valid_file() {
+        let (network_file, diagnostics) =
+            NetworkFileReader::parse_recovering(include_str!("../../../netz-stdlib/stdlib.net"));
+        assert!(diagnostics.is_empty());
+        assert!(network_file.alias("string").is_some());
+    }
+
+    /// Fields declared by their' type only derive their' names.
+    #[test]
+    fn shorthand_fields() {
+        let (input, network_file) =
+            NetworkFileReader::parse_str(include_str!("../../../playground/net/complex.net")).unwrap();
+        assert_eq!(*input.fragment(), "");
+
+        let names = network_file
+            .structure("FooBar")
+            .unwrap()
+            .fields()
+            .map(|field| field.name().to_owned())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["foos", "bar"]);
+        assert!(network_file.resolve().diagnostics().is_empty());
+    }
+
+    /// Imports are collected, the imported files are read by the loader.
+    #[test]
+    fn imports() {
+        let file = "import \"users.net\";\nimport \"shared/rights.net\" ;\n\nstruct Post {\n\tauthor: User;\n}\n";
+        let (input, network_file) = NetworkFileReader::parse_str(file).unwrap();
+        assert_eq!(*input.fragment(), "");
+
+        let paths = network_file.imports().map(NetworkImport::path).collect::<Vec<_>>();
+        assert_eq!(paths, vec!["users.net", "shared/rights.net"]);
+        assert!(network_file.structure("Post").is_some());
+    }
+
+    /// The prelude can be disabled or replaced by another network file.
+    #[test]
+    fn custom_prelude() {
+        let (_, mut network_file) =
+            NetworkFileReader::parse_str("struct A {\n\tname: string;\n\tat: Instant;\n}").unwrap();
+        assert_eq!(network_file.resolve().diagnostics().len(), 1);
+        assert!(network_file.prelude().unwrap().alias("bool").is_some());
+
+        network_file.set_prelude(Prelude::Disabled);
+        assert_eq!(network_file.prelude(), None);
+        assert_eq!(network_file.resolve().diagnostics().len(), 2);
+
+        let prelude = Prelude::from_source("type string = u8[];\ntype Instant = u64;").unwrap();
+        network_file.set_prelude(prelude);
+        assert!(network_file.resolve().diagnostics().is_empty());
+        assert_eq!(
+            network_file.resolve_primitive("Instant"),
+            Some(TypeExpression::named("u64"))
+        );
     }
 }
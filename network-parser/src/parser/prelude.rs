@@ -0,0 +1,101 @@
+use std::sync::{Arc, OnceLock};
+
+use super::{diagnostic::Diagnostic, network::NetworkFileReader};
+
+/// The source of the standard library, which is embedded at build time.
+pub const STDLIB_SOURCE: &str = include_str!("../../../netz-stdlib/stdlib.net");
+
+/// The declarations, which every network file can use without declaring
+/// or importing them. Declarations of the file itself shadow the prelude.
+///
+/// ```
+/// use network_parser::parser::{network::NetworkFileReader, prelude::Prelude};
+///
+/// let source = "struct User {\n    name: string;\n}";
+/// let mut network_file = NetworkFileReader::from_source(source).unwrap();
+/// assert!(network_file.resolve().diagnostics().is_empty());
+///
+/// network_file.set_prelude(Prelude::Disabled);
+/// assert_eq!(
+///     network_file.resolve().diagnostics()[0].message(),
+///     "cannot find type `string` in this file"
+/// );
+/// ```
+#[derive(Debug, PartialEq, Clone, Default)]
+pub enum Prelude {
+    /// The standard library `netz-stdlib/stdlib.net`, which declares
+    /// `string`, `bool`, `bit` and the constants of `bool`.
+    ///
+    /// The source is embedded at build time, but only parsed on first use
+    /// by [stdlib]. A broken `stdlib.net` does not fail the build, it
+    /// panics in every consumer of the prelude. The tests of this module
+    /// parse and resolve it, so they are the gate for changes of it.
+    #[default]
+    Stdlib,
+    /// No prelude, only the wire primitives are built in.
+    Disabled,
+    /// A custom network file, which replaces the standard library.
+    Custom(Arc<NetworkFileReader>),
+}
+
+impl Prelude {
+    /// A prelude of the declarations in the source. Returns the parser
+    /// errors, if the source is invalid.
+    ///
+    /// ```
+    /// use network_parser::parser::{network::NetworkFileReader, prelude::Prelude};
+    ///
+    /// let prelude = Prelude::from_source("type Timestamp = u64;").unwrap();
+    /// let mut network_file = NetworkFileReader::from_source("struct A {\n    Timestamp;\n}").unwrap();
+    /// network_file.set_prelude(prelude);
+    ///
+    /// assert!(network_file.resolve().diagnostics().is_empty());
+    /// ```
+    pub fn from_source(source: &str) -> Result<Self, Vec<Diagnostic>> {
+        let mut file = NetworkFileReader::from_source(source)?;
+        file.set_prelude(Prelude::Disabled);
+        Ok(Self::Custom(Arc::new(file)))
+    }
+
+    /// The declarations of the prelude, `None` if it is disabled.
+    pub fn file(&self) -> Option<&NetworkFileReader> {
+        match self {
+            Self::Stdlib => Some(stdlib()),
+            Self::Disabled => None,
+            Self::Custom(file) => Some(file),
+        }
+    }
+}
+
+/// The standard library, which is parsed once on first use. It does not
+/// have a prelude itself.
+///
+/// # Panics
+///
+/// Panics if the embedded standard library is invalid.
+pub fn stdlib() -> &'static NetworkFileReader {
+    static STDLIB: OnceLock<NetworkFileReader> = OnceLock::new();
+
+    STDLIB.get_or_init(|| {
+        let mut file =
+            NetworkFileReader::from_source(STDLIB_SOURCE).expect("the standard library is valid");
+        file.set_prelude(Prelude::Disabled);
+        file
+    })
+}
+
+#[cfg(test)]
+mod prelude_test {
+    use super::*;
+    use crate::parser::evaluate::ConstEvaluator;
+
+    /// The embedded standard library parses, resolves and evaluates
+    /// without errors.
+    #[test]
+    fn valid_stdlib() {
+        let file = Prelude::Stdlib.file().unwrap();
+        assert!(file.resolve().diagnostics().is_empty());
+        assert!(ConstEvaluator::new(file).evaluate().is_ok());
+        assert!(file.alias("bit").is_some());
+    }
+}
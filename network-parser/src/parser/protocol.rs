@@ -0,0 +1,491 @@
+use std::slice::Iter;
+
+use nom::{
+    branch::alt,
+    character::complete::u64,
+    combinator::{cut, opt, value},
+    multi::many0,
+    sequence::{delimited, pair, preceded, terminated, tuple},
+    Parser,
+};
+
+use super::{
+    comment::Comment,
+    diagnostic::SourceSpan,
+    documentation::Documentation,
+    error::{
+        expect, in_context, keyword, members, source_span, symbol, Expected, ParseResult, Span,
+    },
+    expression::TypeExpression,
+    identifier::NetworkIdentifier,
+    interface::NetworkParser,
+    tag::Tag,
+};
+
+/// The two agents of a protocol. The client initiates the connection
+/// and sends requests, the server responds.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ProtocolSide {
+    Client,
+    Server,
+}
+
+impl ProtocolSide {
+    /// The agent on the other end of the connection.
+    pub fn opposite(&self) -> Self {
+        match self {
+            Self::Client => Self::Server,
+            Self::Server => Self::Client,
+        }
+    }
+}
+
+/// A protocol message is the type that one agent sends for a
+/// discriminator, with an optional field name.
+///
+/// #### Example
+///
+/// ```net
+/// request: HTTPRequest
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+pub struct ProtocolMessage {
+    field_name: Option<NetworkIdentifier>,
+    message_type: TypeExpression,
+}
+
+impl ProtocolMessage {
+    /// The declared field name, if any.
+    pub fn name(&self) -> Option<&str> {
+        self.field_name
+            .as_ref()
+            .map(|identifier| identifier.identity.as_str())
+    }
+
+    /// The location of the declared field name, if any.
+    pub fn name_span(&self) -> Option<SourceSpan> {
+        self.field_name.as_ref().map(|identifier| identifier.span)
+    }
+
+    pub fn message_type(&self) -> &TypeExpression {
+        &self.message_type
+    }
+
+    /// Reads one side of a protocol entry. The unit type `()` declares,
+    /// that the agent does not send anything.
+    fn side(input: Span) -> ParseResult<Option<Self>> {
+        let (input, _) = Comment::parse(input)?;
+
+        alt((
+            value(None, pair(symbol("("), pair(Comment::parse, symbol(")")))),
+            pair(
+                opt(terminated(
+                    NetworkIdentifier::parse,
+                    pair(Comment::parse, symbol(":")),
+                )),
+                TypeExpression::parse,
+            )
+            .map(|(field_name, message_type)| {
+                Some(Self {
+                    field_name,
+                    message_type,
+                })
+            }),
+        ))(input)
+    }
+}
+
+/// A protocol entry assigns the message of the client and the message
+/// of the server to a discriminator. A message is `None` if the agent
+/// does not send for this discriminator.
+///
+/// #### Example
+///
+/// ```net
+/// Ping = 0;
+/// (request: HTTPRequest -> response: HTTPResponse) = 1;
+/// (Message -> ()) = 2;
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+pub struct ProtocolEntry {
+    span: SourceSpan,
+    client: Option<ProtocolMessage>,
+    server: Option<ProtocolMessage>,
+    discriminator: Option<u64>,
+    documentation: Option<Documentation>,
+    tags: Vec<Tag>,
+}
+
+impl ProtocolEntry {
+    /// The location of the messages in the network file.
+    pub fn span(&self) -> SourceSpan {
+        self.span
+    }
+
+    /// The message the client sends.
+    pub fn client(&self) -> Option<&ProtocolMessage> {
+        self.client.as_ref()
+    }
+
+    /// The message the server sends.
+    pub fn server(&self) -> Option<&ProtocolMessage> {
+        self.server.as_ref()
+    }
+
+    /// The message the agent sends.
+    pub fn sent_by(&self, side: ProtocolSide) -> Option<&ProtocolMessage> {
+        match side {
+            ProtocolSide::Client => self.client(),
+            ProtocolSide::Server => self.server(),
+        }
+    }
+
+    /// The discriminator as written in the network file, `None` if it
+    /// was omitted. See [NetworkProtocol::discriminators] for the resolved
+    /// values.
+    pub fn discriminator(&self) -> Option<u64> {
+        self.discriminator
+    }
+
+    pub fn documentation(&self) -> Option<&Documentation> {
+        self.documentation.as_ref()
+    }
+
+    pub fn tags(&self) -> Iter<'_, Tag> {
+        self.tags.iter()
+    }
+
+    /// Reads the optional discriminator after the equal sign and the
+    /// closing semicolon.
+    fn discriminator_definition(input: Span) -> ParseResult<Option<u64>> {
+        // read the optional discriminator
+        let (input, _) = Comment::parse(input)?;
+        let (input, discriminator) = opt(preceded(
+            pair(symbol("="), Comment::parse),
+            expect(Expected::Integer, u64),
+        ))(input)?;
+
+        // read the semicolon
+        let (input, _) = Comment::parse(input)?;
+        let (input, _) = symbol(";")(input)?;
+
+        ParseResult::Ok((input, discriminator))
+    }
+}
+
+impl NetworkParser for ProtocolEntry {
+    fn parse(input: Span) -> ParseResult<Self> {
+        let (input, comment) = Comment::parse(input)?;
+
+        // read optionally several tags, including those in the documentation
+        let (input, tags) = many0(Tag::parse)(input)?;
+        let tags = [comment.read_tags(), tags].concat();
+
+        // read the directed messages, or one message common to both agents
+        let arrow = || pair(Comment::parse, symbol("->"));
+        let (start, _) = Comment::parse(input)?;
+        let (input, (client, server)) = alt((
+            delimited(
+                symbol("("),
+                tuple((ProtocolMessage::side, arrow(), ProtocolMessage::side))
+                    .map(|(client, _, server)| (client, server)),
+                pair(Comment::parse, symbol(")")),
+            ),
+            tuple((ProtocolMessage::side, arrow(), ProtocolMessage::side))
+                .map(|(client, _, server)| (client, server)),
+            ProtocolMessage::side.map(|message| (message.clone(), message)),
+        ))(start)?;
+        let span = source_span(start, input.location_offset() - start.location_offset());
+
+        // read the optional discriminator and the semicolon, after the
+        // messages the declaration is expected to be complete
+        let (input, discriminator) =
+            in_context("entry".to_owned(), cut(Self::discriminator_definition))(input)?;
+
+        ParseResult::Ok((
+            input,
+            ProtocolEntry {
+                span,
+                client,
+                server,
+                discriminator,
+                documentation: comment.read_documentation(),
+                tags,
+            },
+        ))
+    }
+}
+
+/// Definition for a `protocol` in a network file. A protocol is the
+/// entry type to encode and decode from a connection. It starts with
+/// the keyword 'protocol', followed by an identifier declaring the
+/// protocols' name, an optional discriminator type after a colon,
+/// followed by an array of entries inside curly brackets.
+///
+/// #### Example
+///
+/// ```net
+/// protocol Connection {
+///     Ping = 0;
+///     (request: HTTPRequest -> response: HTTPResponse) = 1;
+///     (Message -> ()) = 2;
+/// }
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+pub struct NetworkProtocol {
+    identity: String,
+    span: SourceSpan,
+    discriminator_type: Option<TypeExpression>,
+    entries: Vec<ProtocolEntry>,
+    documentation: Option<Documentation>,
+    tags: Vec<Tag>,
+}
+
+impl NetworkProtocol {
+    pub fn name(&self) -> &str {
+        self.identity.as_str()
+    }
+
+    /// The location of the name in the network file.
+    pub fn span(&self) -> SourceSpan {
+        self.span
+    }
+
+    /// The type of the discriminator, if it was declared explicitly.
+    pub fn discriminator_type(&self) -> Option<&TypeExpression> {
+        self.discriminator_type.as_ref()
+    }
+
+    pub fn entries(&self) -> Iter<'_, ProtocolEntry> {
+        self.entries.iter()
+    }
+
+    pub fn documentation(&self) -> Option<&Documentation> {
+        self.documentation.as_ref()
+    }
+
+    pub fn tags(&self) -> Iter<'_, Tag> {
+        self.tags.iter()
+    }
+
+    /// Resolves the discriminator of every entry. An entry without an
+    /// explicit discriminator continues counting from its' predecessor,
+    /// the first entry starts at zero. The list ends before the first
+    /// entry, whose' implicit discriminator would overflow, which is
+    /// reported by the [Resolver](super::resolver::Resolver).
+    pub fn discriminators(&self) -> Vec<(u64, &ProtocolEntry)> {
+        let mut next = Some(0);
+
+        self.entries
+            .iter()
+            .map_while(|entry| {
+                let discriminator = entry.discriminator.or(next)?;
+                next = discriminator.checked_add(1);
+                Some((discriminator, entry))
+            })
+            .collect()
+    }
+
+    /// Lists the messages the agent can send, with their discriminators.
+    pub fn sends(&self, side: ProtocolSide) -> Vec<(u64, &ProtocolMessage)> {
+        self.discriminators()
+            .into_iter()
+            .filter_map(|(discriminator, entry)| {
+                entry.sent_by(side).map(|message| (discriminator, message))
+            })
+            .collect()
+    }
+
+    /// Lists the messages the agent can receive, with their discriminators.
+    /// These are the messages the opposite agent sends.
+    pub fn receives(&self, side: ProtocolSide) -> Vec<(u64, &ProtocolMessage)> {
+        self.sends(side.opposite())
+    }
+
+    /// Reads the optional discriminator type and the entry declarations
+    /// inside of curly brackets.
+    fn body(input: Span) -> ParseResult<(Option<TypeExpression>, Vec<ProtocolEntry>)> {
+        // read the optional discriminator type
+        let (input, _) = Comment::parse(input)?;
+        let (input, discriminator_type) =
+            opt(preceded(symbol(":"), NetworkIdentifier::parse))(input)?;
+
+        // expect '{' symbol
+        let (input, _) = Comment::parse(input)?;
+        let (input, _) = symbol("{")(input)?;
+
+        // expect entry declarations
+        let (input, entries) = members(ProtocolEntry::parse)(input)?;
+
+        let (input, _) = Comment::parse(input)?;
+        let (input, _) = symbol("}")(input)?;
+
+        ParseResult::Ok((
+            input,
+            (
+                discriminator_type.map(TypeExpression::from),
+                entries,
+            ),
+        ))
+    }
+}
+
+impl NetworkParser for NetworkProtocol {
+    fn parse(input: Span) -> ParseResult<Self> {
+        let (input, comment) = Comment::parse(input)?;
+
+        // read optionally several tags, including those in the documentation
+        let (input, tags) = many0(Tag::parse)(input)?;
+        let tags = [comment.read_tags(), tags].concat();
+
+        // read the 'protocol' keyword
+        let (input, _) = Comment::parse(input)?;
+        let (input, _) = keyword("protocol")(input)?;
+
+        // expect protocol name
+        let (input, protocol_name) = cut(NetworkIdentifier::parse)(input)?;
+        let context = format!("protocol `{}`", protocol_name.identity);
+
+        // expect the optional discriminator type and the entry
+        // declarations inside of curly brackets
+        let (input, (discriminator_type, entries)) = in_context(context, cut(Self::body))(input)?;
+
+        Ok((
+            input,
+            Self {
+                identity: protocol_name.identity,
+                span: protocol_name.span,
+                discriminator_type,
+                entries,
+                documentation: comment.read_documentation(),
+                tags,
+            },
+        ))
+    }
+}
+
+#[cfg(test)]
+mod protocol_test {
+    use super::*;
+
+    /// The protocol from the README example.
+    const CONNECTION: &str = "protocol Connection {
+    Ping = 0;
+    (request: HTTPRequest -> response: HTTPResponse) = 1;
+    (Message -> ()) = 2;
+}";
+
+    #[test]
+    fn simple_protocol() {
+        let (input, protocol) = NetworkProtocol::parse_str("protocol Empty {}").unwrap();
+        assert_eq!(*input.fragment(), "");
+        assert_eq!(protocol.name(), "Empty");
+        assert_eq!(protocol.entries().len(), 0);
+    }
+
+    #[test]
+    fn connection_protocol() {
+        let (input, protocol) = NetworkProtocol::parse_str(CONNECTION).unwrap();
+        assert_eq!(*input.fragment(), "");
+        assert_eq!(protocol.entries().len(), 3);
+
+        let entries = protocol.discriminators();
+
+        // `Ping` is common to both agents.
+        let (discriminator, ping) = entries[0];
+        assert_eq!(discriminator, 0);
+        assert_eq!(ping.client().unwrap().message_type().name(), Some("Ping"));
+        assert_eq!(ping.server().unwrap().message_type().name(), Some("Ping"));
+        assert_eq!(ping.client().unwrap().name(), None);
+
+        // Requests are answered with responses.
+        let (discriminator, request) = entries[1];
+        assert_eq!(discriminator, 1);
+        assert_eq!(request.client().unwrap().name(), Some("request"));
+        assert_eq!(
+            request.client().unwrap().message_type().name(),
+            Some("HTTPRequest")
+        );
+        assert_eq!(request.server().unwrap().name(), Some("response"));
+        assert_eq!(
+            request.server().unwrap().message_type().name(),
+            Some("HTTPResponse")
+        );
+
+        // Only the client sends messages.
+        let (discriminator, message) = entries[2];
+        assert_eq!(discriminator, 2);
+        assert_eq!(message.client().unwrap().message_type().name(), Some("Message"));
+        assert_eq!(message.server(), None);
+    }
+
+    #[test]
+    fn protocol_views() {
+        let (_, protocol) = NetworkProtocol::parse_str(CONNECTION).unwrap();
+
+        let client_sends = protocol.sends(ProtocolSide::Client);
+        assert_eq!(
+            client_sends
+                .iter()
+                .map(|(d, m)| (*d, m.message_type().name().unwrap()))
+                .collect::<Vec<_>>(),
+            vec![(0, "Ping"), (1, "HTTPRequest"), (2, "Message")]
+        );
+
+        let client_receives = protocol.receives(ProtocolSide::Client);
+        assert_eq!(
+            client_receives
+                .iter()
+                .map(|(d, m)| (*d, m.message_type().name().unwrap()))
+                .collect::<Vec<_>>(),
+            vec![(0, "Ping"), (1, "HTTPResponse")]
+        );
+
+        assert_eq!(protocol.receives(ProtocolSide::Server), client_sends);
+    }
+
+    /// Named messages can be declared without round brackets.
+    #[test]
+    fn unbracketed_entries() {
+        let (input, protocol) = NetworkProtocol::parse_str(
+            "protocol P: u16 { different: Custom = 4; from: From -> to: To; () -> Event; }",
+        )
+        .unwrap();
+        assert_eq!(*input.fragment(), "");
+        assert_eq!(
+            protocol.discriminator_type().and_then(TypeExpression::name),
+            Some("u16")
+        );
+
+        let entries = protocol.discriminators();
+        assert_eq!(entries[0].0, 4);
+        assert_eq!(entries[0].1.server().unwrap().name(), Some("different"));
+        assert_eq!(entries[1].0, 5);
+        assert_eq!(entries[1].1.server().unwrap().name(), Some("to"));
+        assert_eq!(entries[1].1.span(), SourceSpan::new(41, 20, 1, 42));
+        assert_eq!(entries[2].0, 6);
+        assert_eq!(entries[2].1.client(), None);
+        assert_eq!(
+            entries[2].1.server().unwrap().message_type().name(),
+            Some("Event")
+        );
+    }
+
+    /// The entries end before an implicit discriminator, which would
+    /// overflow.
+    #[test]
+    fn overflowing_entries() {
+        let (_, protocol) =
+            NetworkProtocol::parse_str("protocol P { A = 18446744073709551615; B; }").unwrap();
+        let entries = protocol.discriminators();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].0, u64::MAX);
+    }
+
+    #[test]
+    fn invalid_entries() {
+        assert!(NetworkProtocol::parse_str("protocol P { (A -> B = 1; }").is_err());
+        assert!(NetworkProtocol::parse_str("protocol P { A -> = 1; }").is_err());
+        assert!(NetworkProtocol::parse_str("protocol P { A = 1 }").is_err());
+    }
+}
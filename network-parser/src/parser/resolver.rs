@@ -0,0 +1,1135 @@
+use std::{
+    collections::{HashMap, HashSet},
+    slice::Iter,
+};
+
+use super::{
+    alias::NetworkType,
+    constant::NetworkConst,
+    diagnostic::{Diagnostic, SourceSpan},
+    enumeration::NetworkEnum,
+    expression::{TypeExpression, TypeKind},
+    flags::{FlagsError, NetworkFlags, MAX_FLAG_POSITION},
+    integer_primitive, integer_range,
+    network::NetworkFileReader,
+    protocol::{NetworkProtocol, ProtocolEntry, ProtocolMessage},
+    structure::{NetworkStruct, StructError, MAX_FIELD_NUMBER},
+    tag::Tag,
+    FLOAT_TYPES, VARINT_TYPES,
+};
+
+/// The tag of a type alias, which is converted to a string of the target
+/// language, such as `string` in the standard library.
+pub const STRING_CONVERT_TAG: &str = "string_convert";
+
+/// The tag of a field or type alias, whose' integers are encoded in fixed
+/// width instead of 7-bit varints.
+pub const FIXED_TAG: &str = "fixed";
+
+/// The declaration a name refers to.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Declaration<'a> {
+    /// A built-in or registered primitive type.
+    Primitive,
+    Struct(&'a NetworkStruct),
+    Enum(&'a NetworkEnum),
+    Flags(&'a NetworkFlags),
+    Alias(&'a NetworkType),
+    Const(&'a NetworkConst),
+    Protocol(&'a NetworkProtocol),
+}
+
+impl<'a> Declaration<'a> {
+    /// Whether the declaration can be used as a type.
+    pub fn is_type(&self) -> bool {
+        !matches!(self, Self::Const(_) | Self::Protocol(_))
+    }
+
+    /// The declared name, `None` for built-in types.
+    pub fn name(&self) -> Option<&'a str> {
+        match self {
+            Self::Primitive => None,
+            Self::Struct(declaration) => Some(declaration.name()),
+            Self::Enum(declaration) => Some(declaration.name()),
+            Self::Flags(declaration) => Some(declaration.name()),
+            Self::Alias(declaration) => Some(declaration.name()),
+            Self::Const(declaration) => Some(declaration.name()),
+            Self::Protocol(declaration) => Some(declaration.name()),
+        }
+    }
+
+    /// The location of the declared name, `None` for built-in types.
+    pub fn span(&self) -> Option<SourceSpan> {
+        match self {
+            Self::Primitive => None,
+            Self::Struct(declaration) => Some(declaration.span()),
+            Self::Enum(declaration) => Some(declaration.span()),
+            Self::Flags(declaration) => Some(declaration.span()),
+            Self::Alias(declaration) => Some(declaration.span()),
+            Self::Const(declaration) => Some(declaration.span()),
+            Self::Protocol(declaration) => Some(declaration.span()),
+        }
+    }
+
+    /// The kind of declaration, as written in diagnostics.
+    fn kind(&self) -> &'static str {
+        match self {
+            Self::Primitive => "primitive",
+            Self::Struct(_) => "struct",
+            Self::Enum(_) => "enum",
+            Self::Flags(_) => "flags",
+            Self::Alias(_) => "type",
+            Self::Const(_) => "const",
+            Self::Protocol(_) => "protocol",
+        }
+    }
+}
+
+/// A type name in the network file, which was resolved to its' declaration.
+#[derive(Debug, PartialEq, Clone)]
+pub struct TypeLink<'a> {
+    name: String,
+    span: SourceSpan,
+    declaration: Declaration<'a>,
+}
+
+impl<'a> TypeLink<'a> {
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    /// The location of the type name.
+    pub fn span(&self) -> SourceSpan {
+        self.span
+    }
+
+    pub fn declaration(&self) -> Declaration<'a> {
+        self.declaration
+    }
+}
+
+/// The semantic analysis of a network file. The resolver links every type
+/// name in the file to its' declaration or a primitive, and reports
+///
+/// - unknown types, with a suggestion for misspelled names,
+/// - map keys, which are no scalar types,
+/// - `@fixed` tags on types, which are no varints,
+/// - top-level names, which are declared more than once,
+/// - fields, which are declared more than once in a structure,
+/// - field numbers, which are missing, unexpected, shared or outside of
+///   `1..=255`,
+/// - members of enumerations and flags, which shadow each other by their'
+///   name or discriminator, and protocol entries sharing a discriminator,
+/// - flags, which share a position or lie outside of `1..=64`,
+/// - discriminators, which overflow or do not fit into the discriminator
+///   type.
+///
+/// ```
+/// use network_parser::parser::network::NetworkFileReader;
+///
+/// let source = "struct User {}\nstruct Post {\n    author: Usr;\n}";
+/// let network_file = NetworkFileReader::from_source(source).unwrap();
+/// let resolver = network_file.resolve();
+///
+/// let diagnostic = &resolver.diagnostics()[0];
+/// assert_eq!(diagnostic.message(), "cannot find type `Usr` in this file");
+/// assert_eq!(diagnostic.help(), Some("did you mean `User`?"));
+/// ```
+#[derive(Debug)]
+pub struct Resolver<'a> {
+    declarations: HashMap<&'a str, Declaration<'a>>,
+    imported: HashMap<&'a str, Declaration<'a>>,
+    primitives: Vec<String>,
+    links: Vec<TypeLink<'a>>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl<'a> Resolver<'a> {
+    /// Resolves all type names of the file and collects the diagnostics.
+    pub fn new(file: &'a NetworkFileReader) -> Self {
+        Self::with_imports(file, &[])
+    }
+
+    /// Resolves all type names of the file, which may also refer to the
+    /// declarations of the imported files and of the prelude. Names declared
+    /// in more than one file are reported by the
+    /// [NetworkSchema](super::loader::NetworkSchema), so the first imported
+    /// declaration of a name is used silently.
+    pub fn with_imports(file: &'a NetworkFileReader, imports: &[&'a NetworkFileReader]) -> Self {
+        let mut resolver = Self {
+            declarations: HashMap::new(),
+            imported: HashMap::new(),
+            primitives: file.primitives(),
+            links: Vec::new(),
+            diagnostics: Vec::new(),
+        };
+
+        let imports = imports.iter().copied().chain(file.prelude());
+
+        for declaration in imports.flat_map(declarations) {
+            if let Some(name) = declaration.name() {
+                resolver.imported.entry(name).or_insert(declaration);
+            }
+        }
+
+        resolver.declare_all(file);
+
+        for structure in file.structures() {
+            resolver.check_duplicates(
+                "field",
+                &format!("struct `{}`", structure.name()),
+                structure
+                    .fields()
+                    .map(|field| {
+                        // the name of a shorthand field can only be changed
+                        // by declaring it explicitly
+                        let help = field.is_shorthand().then(|| {
+                            format!(
+                                "`{}` is derived from the type `{}`, declare the field with an explicit name",
+                                field.name(),
+                                field.field_type(),
+                            )
+                        });
+
+                        (field.name().to_owned(), field.span(), help)
+                    })
+                    .collect(),
+            );
+
+            if let Err(errors) = structure.validate() {
+                resolver.diagnostics.extend(
+                    errors
+                        .into_iter()
+                        .map(|(span, error)| struct_diagnostic(&error, span)),
+                );
+            }
+
+            for field in structure.fields() {
+                resolver.link_expression(field.field_type());
+                resolver.check_fixed(field.tags(), field.field_type(), field.span());
+            }
+        }
+
+        for enumeration in file.enumerations() {
+            let context = format!("enum `{}`", enumeration.name());
+            let members = enumeration
+                .members()
+                .map(|member| (member.name().to_owned(), member.span(), None))
+                .collect::<Vec<_>>();
+
+            let mut discriminators = enumeration
+                .discriminators()
+                .into_iter()
+                .zip(members.iter())
+                .map(|((name, discriminator), (_, span, _))| {
+                    (name.to_owned(), discriminator, *span)
+                })
+                .collect::<Vec<_>>();
+
+            // the discriminators end before a member, whose' implicit
+            // discriminator overflows
+            if let Some((name, span, _)) = members.get(discriminators.len()) {
+                resolver
+                    .diagnostics
+                    .push(overflow("member", name, &context, *span));
+            }
+
+            if let Some(discriminator_type) = enumeration.discriminator_type() {
+                resolver.link_expression(discriminator_type);
+                resolver.check_range("member", &context, discriminator_type, &discriminators);
+            }
+
+            // members, which are declared twice, are reported as duplicates
+            let mut names = HashSet::new();
+            discriminators.retain(|(name, _, _)| names.insert(name.clone()));
+
+            resolver.check_duplicates("member", &context, members);
+            resolver.check_discriminators("member", &context, discriminators);
+
+            for member in enumeration.members() {
+                member
+                    .payload()
+                    .for_each(|payload| resolver.link_expression(payload));
+            }
+        }
+
+        for flags in file.all_flags() {
+            resolver.check_duplicates(
+                "member",
+                &format!("flags `{}`", flags.name()),
+                flags
+                    .members()
+                    .map(|member| (member.name().to_owned(), member.span(), None))
+                    .collect(),
+            );
+
+            if let Err(errors) = flags.validate() {
+                resolver.diagnostics.extend(
+                    errors
+                        .into_iter()
+                        .map(|(span, error)| flags_diagnostic(&error, span)),
+                );
+            }
+        }
+
+        for alias in file.aliases() {
+            resolver.link_expression(alias.aliased());
+            resolver.check_fixed(alias.tags(), alias.aliased(), alias.span());
+        }
+
+        for constant in file.consts() {
+            resolver.link_expression(constant.const_type());
+        }
+
+        for protocol in file.protocols() {
+            let context = format!("protocol `{}`", protocol.name());
+            let entries = protocol
+                .entries()
+                .map(|entry| (entry_name(entry), entry.span()))
+                .collect::<Vec<_>>();
+
+            let discriminators = protocol
+                .discriminators()
+                .into_iter()
+                .zip(entries.iter())
+                .map(|((discriminator, _), (name, span))| (name.clone(), discriminator, *span))
+                .collect::<Vec<_>>();
+
+            // the discriminators end before an entry, whose' implicit
+            // discriminator overflows
+            if let Some((name, span)) = entries.get(discriminators.len()) {
+                resolver
+                    .diagnostics
+                    .push(overflow("entry", name, &context, *span));
+            }
+
+            if let Some(discriminator_type) = protocol.discriminator_type() {
+                resolver.link_expression(discriminator_type);
+                resolver.check_range("entry", &context, discriminator_type, &discriminators);
+            }
+
+            resolver.check_discriminators("entry", &context, discriminators);
+
+            for entry in protocol.entries() {
+                // an entry common to both agents declares a single message
+                let server = entry
+                    .server()
+                    .filter(|server| Some(*server) != entry.client());
+
+                for message in entry.client().into_iter().chain(server) {
+                    resolver.link_expression(message.message_type());
+                }
+            }
+        }
+
+        resolver
+            .diagnostics
+            .sort_by_key(|diagnostic| diagnostic.span().offset());
+
+        resolver
+    }
+
+    /// The declaration of a name. Declarations in the file take precedence
+    /// over the imported declarations, which take precedence over the
+    /// primitives.
+    pub fn declaration(&self, name: &str) -> Option<Declaration<'a>> {
+        let declared = self
+            .declarations
+            .get(name)
+            .or_else(|| self.imported.get(name));
+
+        declared.copied().or_else(|| {
+            self.primitives
+                .iter()
+                .any(|primitive| primitive == name)
+                .then_some(Declaration::Primitive)
+        })
+    }
+
+    /// Get an iterator over all resolved type names, in the order they
+    /// were checked.
+    pub fn links(&self) -> Iter<'_, TypeLink<'a>> {
+        self.links.iter()
+    }
+
+    /// The resolved type name at the byte offset in the network file, for
+    /// example to go to the declaration from an editor.
+    pub fn link_at(&self, offset: usize) -> Option<&TypeLink<'a>> {
+        self.links.iter().find(|link| {
+            (link.span.offset()..link.span.offset() + link.span.length()).contains(&offset)
+        })
+    }
+
+    /// The errors found in the file, ordered by their' location.
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        self.diagnostics.as_slice()
+    }
+
+    /// Registers the top-level declarations and reports names, which are
+    /// declared more than once. The first declaration is kept.
+    fn declare_all(&mut self, file: &'a NetworkFileReader) {
+        for declaration in declarations(file) {
+            let (Some(name), Some(span)) = (declaration.name(), declaration.span()) else {
+                continue;
+            };
+
+            match self.declarations.get(name).and_then(Declaration::span) {
+                Some(first) => self.diagnostics.push(
+                    Diagnostic::error(format!("the name `{name}` is defined multiple times"), span)
+                        .with_label(format!("`{name}` redefined here"))
+                        .with_note(format!(
+                            "`{name}` is first defined on line {}",
+                            first.line()
+                        )),
+                ),
+                None => {
+                    self.declarations.insert(name, declaration);
+                }
+            }
+        }
+    }
+
+    /// Reports names, which are declared more than once in a declaration.
+    /// The help of a redeclared name is added to its' diagnostic.
+    fn check_duplicates(
+        &mut self,
+        kind: &str,
+        context: &str,
+        names: Vec<(String, SourceSpan, Option<String>)>,
+    ) {
+        let mut first_spans: HashMap<&str, SourceSpan> = HashMap::new();
+
+        for (name, span, help) in names.iter() {
+            let Some(first) = first_spans.get(name.as_str()) else {
+                first_spans.insert(name, *span);
+                continue;
+            };
+
+            let diagnostic = Diagnostic::error(
+                format!("{kind} `{name}` is already declared in {context}"),
+                *span,
+            )
+            .with_label(format!("`{name}` redeclared here"))
+            .with_note(format!(
+                "`{name}` is first declared on line {}",
+                first.line()
+            ));
+
+            self.diagnostics.push(match help {
+                Some(help) => diagnostic.with_help(help),
+                None => diagnostic,
+            });
+        }
+    }
+
+    /// Reports members or entries, which resolve to the discriminator of
+    /// a previous one, so they can not be told apart on the wire.
+    fn check_discriminators(
+        &mut self,
+        kind: &str,
+        context: &str,
+        members: Vec<(String, u64, SourceSpan)>,
+    ) {
+        let mut first_members: HashMap<u64, (String, SourceSpan)> = HashMap::new();
+
+        for (name, discriminator, span) in members {
+            match first_members.get(&discriminator) {
+                Some((first, first_span)) => self.diagnostics.push(
+                    Diagnostic::error(
+                        format!("{kind} `{name}` shadows `{first}` in {context}"),
+                        span,
+                    )
+                    .with_label(format!("discriminator {discriminator} is already used"))
+                    .with_note(format!(
+                        "`{first}` on line {} has the discriminator {discriminator}",
+                        first_span.line()
+                    )),
+                ),
+                None => {
+                    first_members.insert(discriminator, (name, span));
+                }
+            }
+        }
+    }
+
+    /// Reports discriminators, which do not fit into the integer type of
+    /// the discriminators. Types, which are no integers, are not checked.
+    fn check_range(
+        &mut self,
+        kind: &str,
+        context: &str,
+        discriminator_type: &TypeExpression,
+        members: &[(String, u64, SourceSpan)],
+    ) {
+        let Some((signed, bits)) = self.integer_type(discriminator_type, &mut Vec::new()) else {
+            return;
+        };
+
+        let (min, max) = integer_range(signed, bits);
+
+        for (name, discriminator, span) in members {
+            if (*discriminator as i128) <= max {
+                continue;
+            }
+
+            self.diagnostics.push(
+                Diagnostic::error(
+                    format!("the discriminator of {kind} `{name}` does not fit into the type of {context}"),
+                    *span,
+                )
+                .with_label(format!("discriminator {discriminator} is out of range"))
+                .with_note(format!(
+                    "`{discriminator_type}` holds the values {min} to {max}"
+                )),
+            );
+        }
+    }
+
+    /// Links the type names of an expression, which was read from the
+    /// file, and checks the keys of its' maps.
+    fn link_expression(&mut self, expression: &TypeExpression) {
+        match expression.kind() {
+            TypeKind::Named(name) => {
+                if let Some(span) = expression.span() {
+                    self.link(name, span);
+                }
+            }
+            TypeKind::Array(inner) | TypeKind::Option(inner) => self.link_expression(inner),
+            TypeKind::Map(key, value) => {
+                self.link_expression(key);
+                self.link_expression(value);
+                self.check_key(key);
+            }
+            TypeKind::Integer { .. } | TypeKind::Unit => (),
+        }
+    }
+
+    /// Links a type name at `span` to its' declaration.
+    fn link(&mut self, name: &str, span: SourceSpan) {
+        match self.declaration(name) {
+            Some(declaration) if declaration.is_type() => self.links.push(TypeLink {
+                name: name.to_owned(),
+                span,
+                declaration,
+            }),
+            Some(declaration) => self.diagnostics.push(
+                Diagnostic::error(
+                    format!("expected type, found {} `{name}`", declaration.kind()),
+                    span,
+                )
+                .with_label("not a type"),
+            ),
+            None => self.diagnostics.push(self.unknown(name, span)),
+        }
+    }
+
+    /// The diagnostic of an unknown type, which suggests the most similar
+    /// known name.
+    fn unknown(&self, name: &str, span: SourceSpan) -> Diagnostic {
+        let mut candidates = self
+            .declarations
+            .iter()
+            .chain(self.imported.iter())
+            .filter(|(_, declaration)| declaration.is_type())
+            .map(|(name, _)| name.to_string())
+            .chain(self.primitives.iter().cloned())
+            .collect::<Vec<_>>();
+
+        candidates.sort();
+        candidates.dedup();
+
+        let diagnostic = Diagnostic::error(format!("cannot find type `{name}` in this file"), span)
+            .with_label("not found in this file");
+
+        match suggest(name, &candidates) {
+            Some(suggestion) => diagnostic.with_help(format!("did you mean `{suggestion}`?")),
+            None => diagnostic,
+        }
+    }
+
+    /// Reports a map key, which is no scalar type. Keys are compared for
+    /// equality, so only integers, strings, booleans, flags and enums
+    /// without payload are hashable, floats are not.
+    fn check_key(&mut self, key: &TypeExpression) {
+        let Some(span) = key.span() else {
+            return;
+        };
+
+        if !self.is_scalar(key, &mut Vec::new()) {
+            self.diagnostics.push(
+                Diagnostic::error(
+                    format!("the type `{key}` can not be used as a map key"),
+                    span,
+                )
+                .with_label("not a scalar type")
+                .with_note(
+                    "map keys are integers, strings, booleans, flags or enums without payload",
+                ),
+            );
+        }
+    }
+
+    /// Warns about a `@fixed` tag on a type, which is not encoded as a
+    /// varint, so the tag has no effect.
+    fn check_fixed(
+        &mut self,
+        mut tags: Iter<'_, Tag>,
+        expression: &TypeExpression,
+        span: SourceSpan,
+    ) {
+        if !tags.any(|tag| tag.name() == FIXED_TAG) || self.is_varint(expression, &mut Vec::new()) {
+            return;
+        }
+
+        self.diagnostics.push(
+            Diagnostic::warning(
+                format!("the tag `@{FIXED_TAG}` has no effect on the type `{expression}`"),
+                span,
+            )
+            .with_label("not a varint")
+            .with_note("only integers wider than 8 bits are encoded as varints"),
+        );
+    }
+
+    /// Whether the type contains integers, which are encoded as varints.
+    /// Arrays and options of integers are encoded element by element.
+    /// Aliases are followed, unknown names are reported elsewhere and
+    /// count as varints.
+    fn is_varint(&self, expression: &TypeExpression, visited: &mut Vec<String>) -> bool {
+        let name = match expression.kind() {
+            TypeKind::Integer { bits, .. } => return *bits > 8,
+            TypeKind::Array(inner) | TypeKind::Option(inner) => {
+                return self.is_varint(inner, visited)
+            }
+            TypeKind::Named(name) => name.as_str(),
+            _ => return false,
+        };
+
+        match self.declaration(name) {
+            Some(Declaration::Primitive) => VARINT_TYPES.contains(&name),
+            Some(Declaration::Alias(alias))
+                if !alias.tags().any(|tag| tag.name() == STRING_CONVERT_TAG)
+                    && !visited.iter().any(|seen| seen == name) =>
+            {
+                visited.push(name.to_owned());
+                self.is_varint(alias.aliased(), visited)
+            }
+            None => true,
+            _ => false,
+        }
+    }
+
+    /// The sign and the amount of bits of an integer type. Aliases are
+    /// followed, other types are no integers.
+    fn integer_type(
+        &self,
+        expression: &TypeExpression,
+        visited: &mut Vec<String>,
+    ) -> Option<(bool, u32)> {
+        let name = match expression.kind() {
+            TypeKind::Integer { signed, bits } => return Some((*signed, *bits)),
+            TypeKind::Named(name) => name.as_str(),
+            _ => return None,
+        };
+
+        match self.declaration(name)? {
+            Declaration::Primitive => integer_primitive(name),
+            Declaration::Alias(alias) if !visited.iter().any(|seen| seen == name) => {
+                visited.push(name.to_owned());
+                self.integer_type(alias.aliased(), visited)
+            }
+            _ => None,
+        }
+    }
+
+    /// Whether the type is a hashable scalar type. Aliases are followed,
+    /// except for aliases converted to strings. Unknown names are reported
+    /// elsewhere and count as scalar.
+    fn is_scalar(&self, expression: &TypeExpression, visited: &mut Vec<String>) -> bool {
+        let name = match expression.kind() {
+            TypeKind::Integer { .. } => return true,
+            TypeKind::Named(name) => name.as_str(),
+            _ => return false,
+        };
+
+        match self.declaration(name) {
+            Some(Declaration::Alias(alias))
+                if alias.tags().any(|tag| tag.name() == STRING_CONVERT_TAG) =>
+            {
+                true
+            }
+            Some(Declaration::Alias(alias)) if !visited.iter().any(|seen| seen == name) => {
+                visited.push(name.to_owned());
+                self.is_scalar(alias.aliased(), visited)
+            }
+            Some(Declaration::Enum(enumeration)) => enumeration
+                .members()
+                .all(|member| member.payload().len() == 0),
+            Some(Declaration::Primitive) => !FLOAT_TYPES.contains(&name),
+            Some(Declaration::Flags(_)) | None => true,
+            _ => false,
+        }
+    }
+}
+
+/// The messages of a protocol entry, as written in diagnostics.
+fn entry_name(entry: &ProtocolEntry) -> String {
+    let message = |message: Option<&ProtocolMessage>| {
+        message.map_or("()".to_owned(), |message| {
+            message.message_type().to_string()
+        })
+    };
+
+    match (entry.client(), entry.server()) {
+        (client, server) if client == server => message(client),
+        (client, server) => format!("{} -> {}", message(client), message(server)),
+    }
+}
+
+/// The diagnostic of a field, whose' number is missing, unexpected or
+/// invalid.
+fn struct_diagnostic(error: &StructError, span: SourceSpan) -> Diagnostic {
+    let label = match error {
+        StructError::MissingNumber { .. } => "expected a field number".to_owned(),
+        StructError::UnexpectedNumber { .. } => "unexpected field number".to_owned(),
+        StructError::NumberOutOfRange { .. } => {
+            format!("expected a number of 1 till {MAX_FIELD_NUMBER}")
+        }
+        StructError::DuplicateNumber { number, .. } => {
+            format!("number {number} is already taken")
+        }
+    };
+
+    Diagnostic::error(error.to_string(), span).with_label(label)
+}
+
+/// The diagnostic of a flags member, which occupies an invalid position.
+fn flags_diagnostic(error: &FlagsError, span: SourceSpan) -> Diagnostic {
+    let label = match error {
+        FlagsError::DuplicatePosition { position, .. } => {
+            format!("position {position} is already occupied")
+        }
+        FlagsError::PositionOutOfRange { .. } => {
+            format!("expected a position of 1 till {MAX_FLAG_POSITION}")
+        }
+    };
+
+    Diagnostic::error(error.to_string(), span).with_label(label)
+}
+
+/// The diagnostic of a member or entry, whose' implicit discriminator
+/// would follow the largest possible discriminator.
+fn overflow(kind: &str, name: &str, context: &str, span: SourceSpan) -> Diagnostic {
+    Diagnostic::error(
+        format!("the discriminator of {kind} `{name}` overflows in {context}"),
+        span,
+    )
+    .with_label("implicit discriminator overflows")
+    .with_note(format!(
+        "the previous discriminator is {}, the largest possible one",
+        u64::MAX
+    ))
+    .with_help("declare the discriminator explicitly")
+}
+
+/// The top-level declarations of a file, ordered by their' location.
+pub(crate) fn declarations(file: &NetworkFileReader) -> Vec<Declaration<'_>> {
+    let mut declarations = file
+        .structures()
+        .map(Declaration::Struct)
+        .chain(file.enumerations().map(Declaration::Enum))
+        .chain(file.all_flags().map(Declaration::Flags))
+        .chain(file.aliases().map(Declaration::Alias))
+        .chain(file.consts().map(Declaration::Const))
+        .chain(file.protocols().map(Declaration::Protocol))
+        .collect::<Vec<_>>();
+
+    declarations.sort_by_key(|declaration| declaration.span().map(|span| span.offset()));
+    declarations
+}
+
+/// The candidate most similar to `name`, if it is close enough to be a
+/// misspelling. Names that only differ in case are always suggested.
+fn suggest<'b>(name: &str, candidates: &'b [String]) -> Option<&'b str> {
+    let limit = (name.chars().count() / 3).max(1);
+
+    candidates
+        .iter()
+        .filter_map(|candidate| {
+            let distance = match candidate.eq_ignore_ascii_case(name) {
+                true => 0,
+                false => edit_distance(name, candidate),
+            };
+
+            (distance <= limit).then_some((distance, candidate.as_str()))
+        })
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// The Levenshtein distance between two strings, counted in characters.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut row = (0..=b.len()).collect::<Vec<_>>();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != *cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+
+    row[b.len()]
+}
+
+#[cfg(test)]
+mod resolver_test {
+    use super::*;
+    use crate::parser::{diagnostic::Severity, interface::NetworkParser};
+
+    fn resolve(source: &str) -> Vec<Diagnostic> {
+        let (_, network_file) = NetworkFileReader::parse_str(source).unwrap();
+        network_file.resolve().diagnostics().to_vec()
+    }
+
+    #[test]
+    fn linked_types() {
+        let source = "struct User {}\nenum Kind: u8 { Post(User[]) = 1; }\ntype Users = User[];\nstruct Post {\n\tauthor: User;\n\tkind: Kind;\n}";
+        let (_, network_file) = NetworkFileReader::parse_str(source).unwrap();
+        let resolver = network_file.resolve();
+        assert!(resolver.diagnostics().is_empty());
+
+        let author = resolver.link_at(source.find("User;").unwrap()).unwrap();
+        assert_eq!(author.name(), "User");
+        assert_eq!(author.span().line(), 5);
+        assert_eq!(author.span().column(), 10);
+        assert_eq!(
+            author.declaration(),
+            Declaration::Struct(network_file.structures().next().unwrap())
+        );
+        assert_eq!(
+            author.declaration().span().unwrap().offset(),
+            source.find("User").unwrap()
+        );
+
+        assert_eq!(resolver.declaration("u8"), Some(Declaration::Primitive));
+        assert_eq!(resolver.links().len(), 5);
+    }
+
+    #[test]
+    fn unknown_type() {
+        let diagnostics = resolve("struct Post {\n\tauthor: strin;\n\tid: Identifier;\n}");
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(
+            diagnostics[0].message(),
+            "cannot find type `strin` in this file"
+        );
+        assert_eq!(diagnostics[0].help(), Some("did you mean `string`?"));
+        assert_eq!(diagnostics[0].span(), SourceSpan::new(23, 5, 2, 10));
+        assert_eq!(diagnostics[1].help(), None);
+    }
+
+    #[test]
+    fn suggested_declaration() {
+        let diagnostics = resolve("struct User {}\ntype Author = user;\nconst A: U16 = 1;");
+        assert_eq!(diagnostics[0].help(), Some("did you mean `User`?"));
+        assert_eq!(diagnostics[1].help(), Some("did you mean `u16`?"));
+    }
+
+    #[test]
+    fn constructor_arguments() {
+        let diagnostics = resolve(
+            "enum Any {\n\tNumber(i(64));\n\tObject(map(string -> Anny));\n\tList(option(Any)[]);\n}",
+        );
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].message(),
+            "cannot find type `Anny` in this file"
+        );
+        assert_eq!(diagnostics[0].span().column(), 23);
+        assert_eq!(diagnostics[0].help(), Some("did you mean `Any`?"));
+    }
+
+    #[test]
+    fn map_keys() {
+        let diagnostics = resolve(
+            "enum Kind { A; B; }\nenum Any { Text(string); }\nflags F { A = 1; }\ntype Id = u(128);\nstruct S {\n\ta: map(Kind -> u8);\n\tb: map(Id -> F);\n\tc: map(F -> string);\n\td: map(Any -> u8);\n\te: map(string[] -> u8);\n\tf: map(option(u8) -> S);\n}",
+        );
+        assert_eq!(diagnostics.len(), 3);
+        assert_eq!(
+            diagnostics[0].message(),
+            "the type `Any` can not be used as a map key"
+        );
+        assert_eq!(diagnostics[0].span().line(), 9);
+        assert_eq!(diagnostics[0].label(), Some("not a scalar type"));
+        assert_eq!(
+            diagnostics[1].message(),
+            "the type `string[]` can not be used as a map key"
+        );
+        assert_eq!(diagnostics[1].span().length(), 8);
+        assert_eq!(
+            diagnostics[2].message(),
+            "the type `option(u8)` can not be used as a map key"
+        );
+
+        // floats are not compared for equality
+        let diagnostics = resolve(
+            "type Real = f64;\nstruct S {\n\ta: map(f32 -> u8);\n\tb: map(Real -> f64);\n}",
+        );
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(
+            diagnostics[1].message(),
+            "the type `Real` can not be used as a map key"
+        );
+    }
+
+    #[test]
+    fn fixed_integers() {
+        let diagnostics = resolve(
+            "@fixed\ntype Hash = u64;\ntype Id = u32;\nstruct S {\n\t@fixed a: Id[];\n\t@fixed b: option(i(24));\n\t@fixed c: u8;\n\t@fixed d: string;\n\t@fixed e: f64;\n}",
+        );
+        let messages = diagnostics
+            .iter()
+            .map(|diagnostic| (diagnostic.severity(), diagnostic.message()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            messages,
+            [
+                (
+                    Severity::Warning,
+                    "the tag `@fixed` has no effect on the type `u8`"
+                ),
+                (
+                    Severity::Warning,
+                    "the tag `@fixed` has no effect on the type `string`"
+                ),
+                (
+                    Severity::Warning,
+                    "the tag `@fixed` has no effect on the type `f64`"
+                ),
+            ]
+        );
+        assert_eq!(diagnostics[0].span().line(), 7);
+    }
+
+    #[test]
+    fn not_a_type() {
+        let diagnostics = resolve("const A: u8 = 1;\nstruct B {\n\ta: A;\n}");
+        assert_eq!(diagnostics[0].message(), "expected type, found const `A`");
+    }
+
+    #[test]
+    fn duplicate_declarations() {
+        let diagnostics = resolve("struct A {}\nenum B {}\n\ntype A = u8;\nconst B: u8 = 1;");
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(
+            diagnostics[0].message(),
+            "the name `A` is defined multiple times"
+        );
+        assert_eq!(diagnostics[0].span().line(), 4);
+        assert_eq!(diagnostics[0].notes(), &["`A` is first defined on line 1"]);
+        assert_eq!(
+            diagnostics[1].message(),
+            "the name `B` is defined multiple times"
+        );
+    }
+
+    #[test]
+    fn duplicate_fields() {
+        let diagnostics = resolve("struct A {\n\tid: u8;\n\tname: string;\n\tid: u16;\n}");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].message(),
+            "field `id` is already declared in struct `A`"
+        );
+        assert_eq!(diagnostics[0].span().line(), 4);
+    }
+
+    #[test]
+    fn shadowing_members() {
+        let diagnostics = resolve("enum E {\n\tA = 1;\n\tB;\n\tC = 2;\n\tA = 5;\n}");
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(
+            diagnostics[0].message(),
+            "member `C` shadows `B` in enum `E`"
+        );
+        assert_eq!(
+            diagnostics[0].label(),
+            Some("discriminator 2 is already used")
+        );
+        assert_eq!(
+            diagnostics[1].message(),
+            "member `A` is already declared in enum `E`"
+        );
+
+        let diagnostics = resolve("flags F {\n\tA = 1;\n\tA = 2;\n}");
+        assert_eq!(
+            diagnostics[0].message(),
+            "member `A` is already declared in flags `F`"
+        );
+    }
+
+    #[test]
+    fn overflowing_members() {
+        let diagnostics = resolve("enum E {\n\tA = 18446744073709551615;\n\tB;\n\tC = 1;\n}");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].message(),
+            "the discriminator of member `B` overflows in enum `E`"
+        );
+        assert_eq!(
+            diagnostics[0].label(),
+            Some("implicit discriminator overflows")
+        );
+        assert_eq!(diagnostics[0].span().line(), 3);
+    }
+
+    #[test]
+    fn discriminator_range() {
+        let diagnostics = resolve(
+            "type Small = i(4);\ntype Wide = u(128);\nenum A: u8 { B = 255; C; }\nenum D: Small { E = 7; F; }\nenum G: Wide { H = 18446744073709551615; }",
+        );
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(
+            diagnostics[0].message(),
+            "the discriminator of member `C` does not fit into the type of enum `A`"
+        );
+        assert_eq!(
+            diagnostics[0].label(),
+            Some("discriminator 256 is out of range")
+        );
+        assert_eq!(
+            diagnostics[0].notes(),
+            ["`u8` holds the values 0 to 255".to_owned()]
+        );
+        assert_eq!(
+            diagnostics[1].notes(),
+            ["`Small` holds the values -8 to 7".to_owned()]
+        );
+        assert_eq!(diagnostics[1].span().line(), 4);
+    }
+
+    #[test]
+    fn protocol_discriminators() {
+        let diagnostics = resolve(
+            "struct M {}\nprotocol C: u8 {\n\t(M -> ()) = 2;\n\t(M -> ()) = 2;\n\tM = 300;\n}\nprotocol D: u64 {\n\tM = 18446744073709551615;\n\t() -> M;\n}",
+        );
+        assert_eq!(diagnostics.len(), 3);
+        assert_eq!(
+            diagnostics[0].message(),
+            "entry `M -> ()` shadows `M -> ()` in protocol `C`"
+        );
+        assert_eq!(diagnostics[0].span().line(), 4);
+        assert_eq!(
+            diagnostics[0].notes(),
+            ["`M -> ()` on line 3 has the discriminator 2".to_owned()]
+        );
+        assert_eq!(
+            diagnostics[1].message(),
+            "the discriminator of entry `M` does not fit into the type of protocol `C`"
+        );
+        assert_eq!(
+            diagnostics[2].message(),
+            "the discriminator of entry `() -> M` overflows in protocol `D`"
+        );
+    }
+
+    /// Every invalid flag is reported at the member.
+    #[test]
+    fn invalid_flags() {
+        let diagnostics = resolve("flags P {\n\tA = 1;\n\tB = 1;\n\tC = 65;\n\tD = 0;\n}");
+        let messages = diagnostics
+            .iter()
+            .map(|diagnostic| (diagnostic.span().line(), diagnostic.message()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            messages,
+            [
+                (3, "the flags `A` and `B` both occupy position 1"),
+                (4, "the flag `C` occupies position 65, expected 1 till 64"),
+                (5, "the flag `D` occupies position 0, expected 1 till 64"),
+            ]
+        );
+        assert_eq!(
+            diagnostics[0].label(),
+            Some("position 1 is already occupied")
+        );
+    }
+
+    /// Every invalid field number is reported at the field.
+    #[test]
+    fn invalid_field_numbers() {
+        let diagnostics = resolve(
+            "option struct M {\n\ta: u8 = 0;\n\tb: u8 = 256;\n\tc: u8 = 1;\n\td: u8 = 1;\n\te: u8;\n}\nstruct S {\n\tf: u8 = 1;\n}",
+        );
+        let messages = diagnostics
+            .iter()
+            .map(|diagnostic| (diagnostic.span().line(), diagnostic.message()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            messages,
+            [
+                (2, "the field `a` has the number 0, expected 1 till 255"),
+                (3, "the field `b` has the number 256, expected 1 till 255"),
+                (5, "the fields `c` and `d` both have the number 1"),
+                (6, "the field `e` of an option struct requires a number"),
+                (
+                    9,
+                    "the field `f` is numbered, but only fields of an option struct are numbered"
+                ),
+            ]
+        );
+        assert_eq!(diagnostics[2].label(), Some("number 1 is already taken"));
+    }
+
+    #[test]
+    fn registered_primitive() {
+        let (_, mut network_file) =
+            NetworkFileReader::parse_str("struct A {\n\tat: Instant;\n}").unwrap();
+        assert_eq!(network_file.resolve().diagnostics().len(), 1);
+
+        network_file.register_primitive("Instant");
+        assert!(network_file.resolve().diagnostics().is_empty());
+    }
+
+    #[test]
+    fn stdlib() {
+        let diagnostics = resolve(include_str!("../../../netz-stdlib/stdlib.net"));
+        assert!(diagnostics.is_empty());
+    }
+
+    #[test]
+    fn distance() {
+        assert_eq!(edit_distance("User", "Usr"), 1);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("", "abc"), 3);
+    }
+
+    /// Shorthand fields of the same type derive the same name.
+    #[test]
+    fn shorthand_collision() {
+        let diagnostics = resolve(
+            "struct Author {}
+struct Message {
+	Author;
+	Author;
+	Author[];
+}",
+        );
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].message(),
+            "field `author` is already declared in struct `Message`"
+        );
+        assert_eq!(diagnostics[0].span().line(), 4);
+        assert_eq!(
+            diagnostics[0].help(),
+            Some("`author` is derived from the type `Author`, declare the field with an explicit name")
+        );
+    }
+}
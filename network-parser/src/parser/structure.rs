@@ -1,17 +1,80 @@
-use std::array::IntoIter;
+use std::{collections::HashMap, fmt::Display, slice::Iter};
 
-use nom::{bytes::complete::tag, multi::many0, IResult};
+use nom::{
+    combinator::{cut, opt},
+    multi::many0,
+    sequence::terminated,
+};
 
 use super::{
-    comment::Comment, field::StructField, identifier::NetworkIdentifier, interface::NetworkParser,
+    comment::Comment,
+    diagnostic::SourceSpan,
+    documentation::Documentation,
+    error::{in_context, keyword, members, symbol, ParseResult, Span},
+    field::StructField,
+    identifier::NetworkIdentifier,
+    interface::NetworkParser,
     tag::Tag,
 };
 
+/// The highest number a field of an `option struct` can have. The
+/// number 0 marks the end of a message on the wire.
+pub const MAX_FIELD_NUMBER: u64 = 255;
+
+/// The reasons a structure declaration can be rejected.
+#[derive(Debug, PartialEq, Clone)]
+pub enum StructError {
+    /// A field of an `option struct` was declared without a number.
+    MissingNumber { field: String },
+    /// A field of a plain `struct` was declared with a number.
+    UnexpectedNumber { field: String },
+    /// A field declared a number outside of `1..=255`.
+    NumberOutOfRange { number: u64, field: String },
+    /// Two fields declared the same number.
+    DuplicateNumber {
+        number: u64,
+        first: String,
+        second: String,
+    },
+}
+
+impl Display for StructError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingNumber { field } => write!(
+                f,
+                "the field `{field}` of an option struct requires a number"
+            ),
+            Self::UnexpectedNumber { field } => write!(
+                f,
+                "the field `{field}` is numbered, but only fields of an option struct are numbered"
+            ),
+            Self::NumberOutOfRange { number, field } => write!(
+                f,
+                "the field `{field}` has the number {number}, expected 1 till {MAX_FIELD_NUMBER}"
+            ),
+            Self::DuplicateNumber {
+                number,
+                first,
+                second,
+            } => write!(
+                f,
+                "the fields `{first}` and `{second}` both have the number {number}"
+            ),
+        }
+    }
+}
+
 /// Definition for a `struct` in a network file. Such a structure
 /// starts with the keyword 'struct', followed by an identifier
 /// declaring the structures' name, followed by an array of fields
 /// inside curly brackets.
 ///
+/// With the keyword 'option' before 'struct' the structure is a
+/// message: every field is optional and has a number, which
+/// identifies it on the wire. Readers skip numbers they do not know,
+/// so messages stay readable when fields are added.
+///
 /// #### Example
 ///
 /// ```net
@@ -19,11 +82,19 @@ use super::{
 ///     u8 foo;
 ///     string[] bar;
 /// }
+///
+/// option struct Message {
+///     content: string = 1;
+///     Author = 2;
+/// }
 /// ```
 #[derive(Debug, PartialEq, Clone)]
 pub struct NetworkStruct {
     identity: String,
+    span: SourceSpan,
+    message: bool,
     fields: Vec<StructField>,
+    documentation: Option<Documentation>,
     tags: Vec<Tag>,
 }
 
@@ -36,43 +107,127 @@ impl NetworkStruct {
     // }
 
     pub fn name(&self) -> &str {
-        &self.identity.as_str()
+        self.identity.as_str()
+    }
+
+    /// The location of the name in the network file.
+    pub fn span(&self) -> SourceSpan {
+        self.span
+    }
+
+    /// Whether the structure was declared with `option struct`, so every
+    /// field is optional and numbered.
+    pub fn is_message(&self) -> bool {
+        self.message
     }
 
     pub fn fields(&self) -> <Vec<StructField> as IntoIterator>::IntoIter {
         self.fields.clone().into_iter()
     }
-}
 
-impl NetworkParser for NetworkStruct {
-    fn parse(input: &str) -> IResult<&str, Self> {
-        let (input, _comment) = Comment::parse(input)?;
+    pub fn documentation(&self) -> Option<&Documentation> {
+        self.documentation.as_ref()
+    }
 
-        // read optionally several tags
-        let (input, tags) = many0(Tag::parse)(input)?;
+    pub fn tags(&self) -> Iter<'_, Tag> {
+        self.tags.iter()
+    }
 
-        // read the 'struct' keyword
-        let (input, _) = Comment::parse(input)?;
-        let (input, _) = tag("struct")(input)?;
+    /// Checks that the fields of a message have distinct numbers in the
+    /// range of `1..=255`, and that the fields of a plain structure are
+    /// not numbered. Returns the errors of all fields, which do not,
+    /// together with the location of the field.
+    pub fn validate(&self) -> Result<(), Vec<(SourceSpan, StructError)>> {
+        let mut numbers: HashMap<u64, &str> = HashMap::new();
+        let mut errors = Vec::new();
 
-        // expect structure name
-        let (input, struct_name) = NetworkIdentifier::parse(input)?;
+        for field in self.fields.iter() {
+            let error = match (self.message, field.number()) {
+                (false, None) => continue,
+                (true, None) => StructError::MissingNumber {
+                    field: field.name().to_owned(),
+                },
+                (false, Some(_)) => StructError::UnexpectedNumber {
+                    field: field.name().to_owned(),
+                },
+                (true, Some(number)) if number == 0 || number > MAX_FIELD_NUMBER => {
+                    StructError::NumberOutOfRange {
+                        number,
+                        field: field.name().to_owned(),
+                    }
+                }
+                (true, Some(number)) => match numbers.get(&number) {
+                    Some(first) => StructError::DuplicateNumber {
+                        number,
+                        first: first.to_string(),
+                        second: field.name().to_owned(),
+                    },
+                    None => {
+                        numbers.insert(number, field.name());
+                        continue;
+                    }
+                },
+            };
+
+            errors.push((field.span(), error));
+        }
+
+        match errors.is_empty() {
+            true => Ok(()),
+            false => Err(errors),
+        }
+    }
 
+    /// Reads the field declarations inside of curly brackets.
+    fn body(input: Span) -> ParseResult<Vec<StructField>> {
         // expect '{' symbol
         let (input, _) = Comment::parse(input)?;
-        let (input, _) = tag("{")(input)?;
+        let (input, _) = symbol("{")(input)?;
 
         // expect field declarations
-        let (input, fields) = many0(StructField::parse)(input)?;
+        let (input, fields) = members(StructField::parse)(input)?;
+
+        let (input, _) = Comment::parse(input)?;
+        let (input, _) = symbol("}")(input)?;
 
+        ParseResult::Ok((input, fields))
+    }
+}
+
+impl NetworkParser for NetworkStruct {
+    fn parse(input: Span) -> ParseResult<Self> {
+        let (input, comment) = Comment::parse(input)?;
+
+        // read optionally several tags, including those in the documentation
+        let (input, tags) = many0(Tag::parse)(input)?;
+        let tags = [comment.read_tags(), tags].concat();
+
+        // read the 'struct' keyword, which is preceded by 'option' for
+        // a message
         let (input, _) = Comment::parse(input)?;
-        let (input, _) = tag("}")(input)?;
+        let (input, message) = opt(terminated(keyword("option"), Comment::parse))(input)?;
+        let (input, _) = match message {
+            Some(_) => cut(keyword("struct"))(input)?,
+            None => keyword("struct")(input)?,
+        };
 
+        // expect structure name
+        let (input, struct_name) = cut(NetworkIdentifier::parse)(input)?;
+        let context = format!("struct `{}`", struct_name.identity);
+
+        // expect field declarations inside of curly brackets
+        let (input, fields) = in_context(context, cut(Self::body))(input)?;
+
+        // missing, unexpected and overlapping field numbers are reported
+        // by the resolver
         Ok((
             input,
             Self {
                 identity: struct_name.identity,
+                span: struct_name.span,
+                message: message.is_some(),
                 fields,
+                documentation: comment.read_documentation(),
                 tags,
             },
         ))
@@ -86,14 +241,14 @@ mod struct_test {
     /// Tests a simple structure without declared fields.
     #[test]
     fn simple_struct() {
-        let (input, network_struct) = NetworkStruct::parse("struct Struct {}").unwrap();
+        let (_input, network_struct) = NetworkStruct::parse_str("struct Struct {}").unwrap();
         assert_eq!(network_struct.identity, "Struct");
     }
 
     /// Tests a simple structure without declared fields.
     #[test]
     fn nameless_struct() {
-        let result = NetworkStruct::parse("struct {}");
+        let result = NetworkStruct::parse_str("struct {}");
         assert!(result.is_err());
     }
 
@@ -102,14 +257,14 @@ mod struct_test {
     #[test]
     fn foobar_struct() {
         let (input, network_struct) =
-            NetworkStruct::parse("struct FooBar { foo: Foo; bar: Bar; }").unwrap();
-        assert_eq!(input, "");
+            NetworkStruct::parse_str("struct FooBar { foo: Foo; bar: Bar; }").unwrap();
+        assert_eq!(*input.fragment(), "");
         assert_eq!(network_struct.identity, "FooBar");
         assert_eq!(network_struct.fields.len(), 2);
         assert_eq!(network_struct.fields.first().unwrap().name(), "foo");
-        assert_eq!(network_struct.fields.first().unwrap().field_type(), "Foo");
+        assert_eq!(network_struct.fields.first().unwrap().field_type().name(), Some("Foo"));
         assert_eq!(network_struct.fields.get(1).unwrap().name(), "bar");
-        assert_eq!(network_struct.fields.get(1).unwrap().field_type(), "Bar");
+        assert_eq!(network_struct.fields.get(1).unwrap().field_type().name(), Some("Bar"));
     }
 
     /// Tests a simple structure with two declared fields of types
@@ -117,9 +272,9 @@ mod struct_test {
     #[test]
     fn typed_struct() {
         let (input, network_struct) =
-            NetworkStruct::parse("\nstruct Simple {\n\tbyte: u8;\n\tvalue: u16;\n\tnext: u8;\n}")
+            NetworkStruct::parse_str("\nstruct Simple {\n\tbyte: u8;\n\tvalue: u16;\n\tnext: u8;\n}")
                 .unwrap();
-        assert_eq!(input, "");
+        assert_eq!(*input.fragment(), "");
         assert_eq!(network_struct.identity, "Simple");
         assert_eq!(network_struct.fields.len(), 3);
     }
@@ -128,13 +283,13 @@ mod struct_test {
     #[test]
     fn annotated_struct() {
         let (input, network_struct) =
-            NetworkStruct::parse("@special @annotation struct FooBar { @deprecated foo: Foo; }")
+            NetworkStruct::parse_str("@special @annotation struct FooBar { @deprecated foo: Foo; }")
                 .unwrap();
-        assert_eq!(input, "");
+        assert_eq!(*input.fragment(), "");
 
         assert_eq!(network_struct.fields.len(), 1);
         assert_eq!(network_struct.fields.first().unwrap().name(), "foo");
-        assert_eq!(network_struct.fields.first().unwrap().field_type(), "Foo");
+        assert_eq!(network_struct.fields.first().unwrap().field_type().name(), Some("Foo"));
         assert_eq!(
             network_struct
                 .fields
@@ -150,4 +305,80 @@ mod struct_test {
         assert_eq!(network_struct.tags.len(), 2);
         assert_eq!(network_struct.tags.first().unwrap().name(), "special");
     }
+
+    /// Tests the message from the README, where every field is numbered.
+    #[test]
+    fn message_struct() {
+        let (input, network_struct) = NetworkStruct::parse_str(
+            "option struct Struct {\n\tField = 1;\n\tedited: option(string) = 3;\n}",
+        )
+        .unwrap();
+        assert_eq!(*input.fragment(), "");
+        assert!(network_struct.is_message());
+        assert_eq!(
+            network_struct
+                .fields()
+                .map(|field| (field.name().to_owned(), field.number()))
+                .collect::<Vec<_>>(),
+            vec![("field".to_owned(), Some(1)), ("edited".to_owned(), Some(3))]
+        );
+        assert!(!NetworkStruct::parse_str("struct A {}").unwrap().1.is_message());
+    }
+
+    /// Every missing, unexpected and invalid field number is returned
+    /// with the location of the field.
+    #[test]
+    fn invalid_numbers() {
+        let errors = |source| {
+            let (_, network_struct) = NetworkStruct::parse_str(source).unwrap();
+            network_struct
+                .validate()
+                .unwrap_err()
+                .into_iter()
+                .map(|(span, error)| (span.offset(), error.to_string()))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            errors("option struct A { a: u8 = 1; b: u8; }"),
+            [(
+                29,
+                "the field `b` of an option struct requires a number".to_owned()
+            )]
+        );
+        assert_eq!(
+            errors("struct A { a: u8 = 1; }"),
+            [(
+                11,
+                "the field `a` is numbered, but only fields of an option struct are numbered"
+                    .to_owned()
+            )]
+        );
+        assert_eq!(
+            errors("option struct A { a: u8 = 0; b: u8 = 256; c: u8 = 2; d: u8 = 2; }"),
+            [
+                (
+                    18,
+                    "the field `a` has the number 0, expected 1 till 255".to_owned()
+                ),
+                (
+                    29,
+                    "the field `b` has the number 256, expected 1 till 255".to_owned()
+                ),
+                (
+                    53,
+                    "the fields `c` and `d` both have the number 2".to_owned()
+                ),
+            ]
+        );
+        assert!(NetworkStruct::parse_str("option struct A { a: u8 = 1; }")
+            .unwrap()
+            .1
+            .validate()
+            .is_ok());
+        assert!(matches!(
+            NetworkStruct::parse_str("option A {}"),
+            Err(nom::Err::Failure(_))
+        ));
+    }
 }
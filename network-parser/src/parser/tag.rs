@@ -1,47 +1,211 @@
 use super::{
     comment::Comment,
+    constant::ConstExpression,
+    documentation::Documentation,
+    error::{symbol, NetworkError, ParseResult, Span},
     identifier::NetworkIdentifier,
     interface::NetworkParser,
 };
-use nom::{bytes::complete::tag, IResult};
+use nom::{
+    branch::alt,
+    character::complete::{char, digit1},
+    combinator::{cut, map_res, opt, recognize, value},
+    error::{ErrorKind, ParseError},
+    multi::separated_list0,
+    sequence::{pair, preceded, terminated},
+    Err, Parser,
+};
+
+/// An argument of a parameterized tag.
+///
+/// #### Example
+///
+/// ```net
+/// "lowercase"  42  -1  20B  4KB  html  Socket.user.permission
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+pub enum TagArgument {
+    String(String),
+    Integer(i128),
+    /// A byte size literal, stored in bytes. The units `B`, `KB`, `MB`
+    /// and `GB` are powers of 1024.
+    ByteSize(u64),
+    Identifier(String),
+    /// A dotted path of at least two identifiers.
+    Path(Vec<String>),
+}
+
+impl TagArgument {
+    /// Reads a byte size literal, an unsigned decimal immediately followed
+    /// by its' unit.
+    fn byte_size(input: Span) -> ParseResult<u64> {
+        let (input, size) = map_res(digit1, |s: Span| s.fragment().parse::<u64>())(input)?;
+        let (input, factor) = alt((
+            value(1 << 30, symbol("GB")),
+            value(1 << 20, symbol("MB")),
+            value(1 << 10, symbol("KB")),
+            value(1, symbol("B")),
+        ))(input)?;
+
+        // a unit directly followed by an identifier character is not a size
+        if input
+            .fragment()
+            .starts_with(|c: char| c.is_alphanumeric() || c == '_')
+        {
+            return Err(Err::Error(NetworkError::from_error_kind(
+                input,
+                ErrorKind::Verify,
+            )));
+        }
+
+        match size.checked_mul(factor) {
+            Some(bytes) => ParseResult::Ok((input, bytes)),
+            None => Err(Err::Failure(NetworkError::invalid(
+                input,
+                format!("the byte size {size} is too large"),
+            ))),
+        }
+    }
+
+    /// Reads a decimal integer, which can be negative.
+    fn integer(input: Span) -> ParseResult<i128> {
+        map_res(recognize(pair(opt(char('-')), digit1)), |s: Span| {
+            s.fragment().parse::<i128>()
+        })(input)
+    }
+
+    /// Reads an identifier or a dotted path of identifiers.
+    fn path(input: Span) -> ParseResult<Self> {
+        let (input, first) = NetworkIdentifier::parse(input)?;
+        let mut segments = vec![first.identity];
+        let mut input = input;
+
+        while let Ok((tmp_input, segment)) = preceded(char('.'), NetworkIdentifier::parse)(input) {
+            segments.push(segment.identity);
+            input = tmp_input;
+        }
+
+        if segments.len() == 1 {
+            ParseResult::Ok((input, Self::Identifier(segments.remove(0))))
+        } else {
+            ParseResult::Ok((input, Self::Path(segments)))
+        }
+    }
+}
+
+impl NetworkParser for TagArgument {
+    fn parse(input: Span) -> ParseResult<Self> {
+        let (input, _) = Comment::parse(input)?;
+
+        alt((
+            ConstExpression::string.map(Self::String),
+            Self::byte_size.map(Self::ByteSize),
+            Self::integer.map(Self::Integer),
+            Self::path,
+        ))(input)
+    }
+}
 
 /// A tag is a marker that can extend a functionality of a
-/// network definition file.
+/// network definition file. Tags can take a list of arguments in
+/// round brackets. An exclamation mark after the '@' symbol marks
+/// the tag as a required extension, which a generator must not
+/// ignore if it does not know the tag.
+///
+/// Tags can be written in front of a definition or on their' own
+/// line inside of its' documentation comment.
 ///
 /// #### Example
 ///
 /// ```net
 /// @ExampleTag
+/// @!limit(20B)
+/// @privilege(Socket.user.permission)
 /// ```
 #[derive(Debug, PartialEq, Clone)]
 pub struct Tag {
     tag_name: String,
+    required: bool,
+    arguments: Vec<TagArgument>,
 }
 
 impl Tag {
     pub fn new(tag_name: String) -> Self {
         Self {
             tag_name,
+            required: false,
+            arguments: Vec::new(),
         }
     }
 
     pub fn name(&self) -> &str {
-        &self.tag_name.as_str()
+        self.tag_name.as_str()
+    }
+
+    /// Whether the tag was marked as a required extension with `@!`.
+    pub fn is_required(&self) -> bool {
+        self.required
+    }
+
+    pub fn arguments(&self) -> &[TagArgument] {
+        self.arguments.as_slice()
+    }
+
+    /// Reads the tags from a documentation comment. Every line starting
+    /// with the '@' symbol is read as a tag, the rest of the line is
+    /// ignored. Documentation tags like `@note` are not extensions and
+    /// are left to the [Documentation].
+    ///
+    /// ```
+    /// use network_parser::parser::tag::{Tag, TagArgument};
+    ///
+    /// let tags = Tag::from_documentation("A name.\n@!limit(20B)\n@case(\"lower\")");
+    /// assert_eq!(tags.len(), 2);
+    /// assert!(tags[0].is_required());
+    /// assert_eq!(tags[0].arguments(), &[TagArgument::ByteSize(20)]);
+    /// ```
+    pub fn from_documentation(documentation: &str) -> Vec<Self> {
+        documentation
+            .lines()
+            .map(str::trim_start)
+            .filter(|line| line.starts_with('@'))
+            .filter_map(|line| Self::parse_str(line).ok())
+            .map(|(_, tag)| tag)
+            .filter(|tag| !Documentation::is_documentation_tag(tag))
+            .collect()
     }
 }
 
 impl NetworkParser for Tag {
     /// A tag consists of two required elements, the '@' symbol and
-    /// a custom identifier afterwards.
-    fn parse(input: &str) -> IResult<&str, Self> {
+    /// a custom identifier afterwards, followed by optional arguments.
+    fn parse(input: Span) -> ParseResult<Self> {
         // expect the '@' tag annotation
         let (input, _) = Comment::parse(input)?;
-        let (input, _) = tag("@")(input)?;
+        let (input, _) = symbol("@")(input)?;
+        let (input, required) = opt(char('!'))(input)?;
 
         // read the tag name
         let (input, tag_name) = NetworkIdentifier::parse(input)?;
 
-        IResult::Ok((input, Tag::new(tag_name.identity)))
+        // read the optional arguments, which have to follow immediately,
+        // an opened argument list has to be closed
+        let (input, arguments) = opt(preceded(
+            char('('),
+            cut(terminated(
+                separated_list0(pair(Comment::parse, char(',')), TagArgument::parse),
+                pair(Comment::parse, char(')')),
+            )),
+        ))(input)?;
+
+        ParseResult::Ok((
+            input,
+            Tag {
+                tag_name: tag_name.identity,
+                required: required.is_some(),
+                arguments: arguments.unwrap_or_default(),
+            },
+        ))
     }
 }
 
@@ -51,13 +215,71 @@ mod tag_test {
 
     #[test]
     fn simple_tag() {
-        let (_, tag) = Tag::parse("@HelloWorld").unwrap();
+        let (_, tag) = Tag::parse_str("@HelloWorld").unwrap();
         assert_eq!(tag.tag_name, "HelloWorld");
+        assert!(!tag.is_required());
+        assert!(tag.arguments().is_empty());
     }
 
     #[test]
     fn documented_tag() {
-        let (_, tag) = Tag::parse("/** A tag. */ @Doc").unwrap();
+        let (_, tag) = Tag::parse_str("/** A tag. */ @Doc").unwrap();
         assert_eq!(tag.tag_name, "Doc");
     }
+
+    #[test]
+    fn required_tag() {
+        let (input, tag) = Tag::parse_str("@!limit(20B) name").unwrap();
+        assert_eq!(*input.fragment(), " name");
+        assert_eq!(tag.name(), "limit");
+        assert!(tag.is_required());
+        assert_eq!(tag.arguments(), &[TagArgument::ByteSize(20)]);
+    }
+
+    #[test]
+    fn tag_arguments() {
+        let (input, tag) =
+            Tag::parse_str("@test(\"a\\.b\", -3, 2KB, html, Socket.user.permission)").unwrap();
+        assert_eq!(*input.fragment(), "");
+        assert_eq!(
+            tag.arguments(),
+            &[
+                TagArgument::String("a\\.b".to_owned()),
+                TagArgument::Integer(-3),
+                TagArgument::ByteSize(2048),
+                TagArgument::Identifier("html".to_owned()),
+                TagArgument::Path(vec![
+                    "Socket".to_owned(),
+                    "user".to_owned(),
+                    "permission".to_owned()
+                ]),
+            ]
+        );
+    }
+
+    #[test]
+    fn empty_arguments() {
+        let (input, tag) = Tag::parse_str("@empty( )").unwrap();
+        assert_eq!(*input.fragment(), "");
+        assert!(tag.arguments().is_empty());
+    }
+
+    #[test]
+    fn unclosed_arguments() {
+        assert!(Tag::parse_str("@limit(20B").is_err());
+        assert!(Tag::parse_str("@limit(20B,)").is_err());
+    }
+
+    #[test]
+    fn documentation_tags() {
+        let tags = Tag::from_documentation(
+            "@!limit(70B)\n@!regex(\"[a-z]+(\\.[a-z]+)*@example\\.com\")\n@note The `@!` marker.",
+        );
+        assert_eq!(tags.len(), 2);
+        assert_eq!(tags[0].name(), "limit");
+        assert_eq!(
+            tags[1].arguments(),
+            &[TagArgument::String("[a-z]+(\\.[a-z]+)*@example\\.com".to_owned())]
+        );
+    }
 }
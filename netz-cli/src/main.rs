@@ -1,3 +1,69 @@
-fn main() {
-    println!("Hello, world!");
+use std::{env, process::ExitCode};
+
+use network_parser::parser::{diagnostic::Severity, lint::CaseLint, loader::NetworkLoader};
+
+fn main() -> ExitCode {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+
+    match args.split_first() {
+        Some((command, files)) if command == "check" && !files.is_empty() => check(files),
+        _ => {
+            eprintln!("usage: netz check <file>...");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Loads every network file with its' imports, resolves and lints them
+/// and prints all of their' diagnostics. Only errors fail the check.
+fn check(files: &[String]) -> ExitCode {
+    let loader = NetworkLoader::new();
+    let mut failed = false;
+
+    for file in files {
+        let schema = match loader.load(file) {
+            Ok(schema) => schema,
+            Err(error) => {
+                eprintln!("error: could not read `{file}`: {error}");
+                failed = true;
+                continue;
+            }
+        };
+
+        let mut diagnostics = schema.diagnostics();
+
+        for module in schema.modules() {
+            diagnostics.extend(
+                CaseLint::default()
+                    .check(module.file())
+                    .into_iter()
+                    .map(|diagnostic| diagnostic.with_file(module.name())),
+            );
+        }
+
+        diagnostics.sort_by_key(|diagnostic| {
+            (
+                diagnostic.file().map(str::to_owned),
+                diagnostic.span().offset(),
+            )
+        });
+
+        for diagnostic in diagnostics.iter() {
+            let module = diagnostic.file().and_then(|name| schema.module(name));
+            let (name, source) = module.map_or((file.as_str(), ""), |module| {
+                (module.name(), module.source())
+            });
+            eprintln!("{}", diagnostic.render(name, source));
+        }
+
+        failed |= diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic.severity() == Severity::Error);
+    }
+
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
 }
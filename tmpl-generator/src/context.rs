@@ -0,0 +1,562 @@
+//! Converts the declarations of a network file into the JSON context of
+//! templates. The context follows a versioned schema, so templates can be
+//! written against it without touching Rust. The current version is
+//! [SCHEMA_VERSION].
+//!
+//! ## Schema `v1`
+//!
+//! Every declaration is an object. Optional values are `null`, lists are
+//! arrays in declaration order.
+//!
+//! - **name**: An identifier in several cases, `value` is the declared
+//!   spelling. The keys are `value`, `lower_flat_case`, `lower_dash_case`,
+//!   `lower_camel_case`, `upper_camel_case`, `lower_snake_case` and
+//!   `upper_snake_case`.
+//! - **documentation**: `description` and the documentation `tags`, which
+//!   have a `name` and a `content`.
+//! - **tag**: `name`, `required` and `arguments`. An argument has a `kind`
+//!   (`string`, `integer`, `byte_size`, `identifier` or `path`) and a
+//!   `value`, which is an array of segments for paths.
+//! - **type**: `kind` is `named`, `array`, `option`, `map`, `integer` or
+//!   `unit` and `value` is the type as written. Named types have the keys
+//!   of a name, arrays an `element`, options an `inner`, maps a `key` and
+//!   an `item` type, integers `signed` and `bits`.
+//!
+//! Declarations with documentation and tags have the keys `documentation`,
+//! `tags` and `tagged`, an object mapping the name of every tag to `true`.
+//!
+//! - **struct**: `name`, `message` and `fields`. A field has an
+//!   `identifier`, a `type`, its' `number` in messages and `shorthand`.
+//! - **enum**: `name`, `discriminator_type`, `unit` if no member has a
+//!   payload, and `members`. A member has a `name`, a `payload` list of
+//!   types and its' resolved `discriminator`.
+//! - **flags**: `name`, `bits`, `primitive` and `members`. A member has a
+//!   `name`, a `position` and its' `bit`.
+//! - **alias**: `name` and the `aliased` type.
+//! - **const**: `name`, `type` and the evaluated `value`, see [file].
+//! - **import**: `path`.
+//! - **protocol**: `name`, `discriminator_type` and `entries`. An entry
+//!   has a resolved `discriminator` and the `client` and `server`
+//!   messages, which have an optional `name` and a `type`.
+//! - **file**: `version`, `documentation`, `tags`, `tagged`, `imports`,
+//!   `types`, `flags`, `enums`, `structs`, `consts` and `protocols`.
+//!
+//! ```
+//! use network_parser::parser::network::NetworkFileReader;
+//! use tmpl_generator::context;
+//!
+//! let file = NetworkFileReader::from_source("struct UserData {\n    user_name: string;\n}").unwrap();
+//! let value = context::file(&file);
+//!
+//! assert_eq!(value["version"], "v1");
+//! assert_eq!(value["structs"][0]["name"]["lower_dash_case"], "user-data");
+//! assert_eq!(value["structs"][0]["fields"][0]["type"]["kind"], "named");
+//! ```
+
+use network_parser::parser::{
+    alias::NetworkType,
+    constant::NetworkConst,
+    documentation::Documentation,
+    enumeration::{EnumMember, NetworkEnum},
+    evaluate::{ConstEvaluator, ConstValue},
+    expression::{TypeExpression, TypeKind},
+    field::StructField,
+    flags::{FlagsMember, NetworkFlags},
+    import::NetworkImport,
+    network::NetworkFileReader,
+    protocol::{NetworkProtocol, ProtocolEntry, ProtocolMessage},
+    structure::NetworkStruct,
+    tag::{Tag, TagArgument},
+};
+use serde_json::{json, Map, Number, Value};
+use util_cases::CaseStyles;
+
+/// The version of the context schema. Templates can check the version
+/// with the `meta` arguments, see [crate::Template::override_arguments].
+pub const SCHEMA_VERSION: &str = "v1";
+
+/// Converts an AST node into its' JSON context.
+pub trait ToContext {
+    fn to_context(&self) -> Value;
+}
+
+/// The context of an identifier in all supported cases.
+pub fn name(identifier: &str) -> Value {
+    json!({
+        "value": identifier,
+        "lower_flat_case": identifier.to_flat_case(),
+        "lower_dash_case": identifier.to_kebab_case(),
+        "lower_camel_case": identifier.to_camel_case(),
+        "upper_camel_case": identifier.to_pascal_case(),
+        "lower_snake_case": identifier.to_snake_case(),
+        "upper_snake_case": identifier.to_constant_case(),
+    })
+}
+
+/// An integer as JSON number. Integers beyond 64 bits do not fit into a
+/// JSON number and are written as string.
+fn integer(value: i128) -> Value {
+    if let Ok(value) = i64::try_from(value) {
+        return Value::from(value);
+    }
+
+    match u64::try_from(value) {
+        Ok(value) => Value::from(value),
+        Err(_) => Value::String(value.to_string()),
+    }
+}
+
+fn optional<T: ToContext>(value: Option<&T>) -> Value {
+    value.map_or(Value::Null, T::to_context)
+}
+
+fn list<'a, T: ToContext + 'a>(values: impl IntoIterator<Item = &'a T>) -> Value {
+    values.into_iter().map(T::to_context).collect()
+}
+
+/// Adds the documentation and tags of a declaration to its' context.
+fn annotated<'a>(
+    mut value: Value,
+    documentation: Option<&Documentation>,
+    tags: impl Iterator<Item = &'a Tag> + Clone,
+) -> Value {
+    let tagged: Map<String, Value> = tags
+        .clone()
+        .map(|tag| (tag.name().to_owned(), Value::Bool(true)))
+        .collect();
+
+    value["documentation"] = optional(documentation);
+    value["tags"] = list(tags);
+    value["tagged"] = Value::Object(tagged);
+    value
+}
+
+impl ToContext for Documentation {
+    fn to_context(&self) -> Value {
+        let tags: Vec<Value> = self
+            .doc_tags()
+            .map(|tag| json!({ "name": tag.name(), "content": tag.content() }))
+            .collect();
+
+        json!({ "description": self.description(), "tags": tags })
+    }
+}
+
+impl ToContext for TagArgument {
+    fn to_context(&self) -> Value {
+        let (kind, value) = match self {
+            TagArgument::String(value) => ("string", json!(value)),
+            TagArgument::Integer(value) => ("integer", integer(*value)),
+            TagArgument::ByteSize(value) => ("byte_size", json!(value)),
+            TagArgument::Identifier(value) => ("identifier", json!(value)),
+            TagArgument::Path(segments) => ("path", json!(segments)),
+        };
+
+        json!({ "kind": kind, "value": value })
+    }
+}
+
+impl ToContext for Tag {
+    fn to_context(&self) -> Value {
+        json!({
+            "name": self.name(),
+            "required": self.is_required(),
+            "arguments": list(self.arguments()),
+        })
+    }
+}
+
+impl ToContext for TypeExpression {
+    fn to_context(&self) -> Value {
+        let mut value = match self.kind() {
+            TypeKind::Named(identifier) => {
+                let mut value = name(identifier);
+                value["kind"] = json!("named");
+                value
+            }
+            TypeKind::Array(element) => {
+                json!({ "kind": "array", "element": element.to_context() })
+            }
+            TypeKind::Option(inner) => json!({ "kind": "option", "inner": inner.to_context() }),
+            TypeKind::Map(key, item) => json!({
+                "kind": "map",
+                "key": key.to_context(),
+                "item": item.to_context(),
+            }),
+            TypeKind::Integer { signed, bits } => {
+                json!({ "kind": "integer", "signed": signed, "bits": bits })
+            }
+            TypeKind::Unit => json!({ "kind": "unit" }),
+        };
+
+        value["value"] = json!(self.to_string());
+        value
+    }
+}
+
+impl ToContext for StructField {
+    fn to_context(&self) -> Value {
+        let value = json!({
+            "identifier": name(self.name()),
+            "type": self.field_type().to_context(),
+            "number": self.number(),
+            "shorthand": self.is_shorthand(),
+        });
+
+        annotated(value, self.documentation(), self.tags())
+    }
+}
+
+impl ToContext for NetworkStruct {
+    fn to_context(&self) -> Value {
+        let value = json!({
+            "name": name(self.name()),
+            "message": self.is_message(),
+            "fields": self.fields().map(|field| field.to_context()).collect::<Vec<_>>(),
+        });
+
+        annotated(value, self.documentation(), self.tags())
+    }
+}
+
+/// The context of an enum member with its' resolved discriminator.
+fn member(member: &EnumMember, discriminator: Option<u64>) -> Value {
+    let value = json!({
+        "name": name(member.name()),
+        "payload": list(member.payload()),
+        "discriminator": discriminator,
+    });
+
+    annotated(value, member.documentation(), member.tags())
+}
+
+impl ToContext for EnumMember {
+    /// The context of the member, whose' discriminator is only known if
+    /// it is declared. Prefer the members of the [NetworkEnum] context.
+    fn to_context(&self) -> Value {
+        member(self, self.discriminator())
+    }
+}
+
+impl ToContext for NetworkEnum {
+    fn to_context(&self) -> Value {
+        // the discriminators end before a member, whose' implicit
+        // discriminator overflows, so the remaining members have none
+        let discriminators = self.discriminators();
+        let members: Vec<Value> = self
+            .members()
+            .enumerate()
+            .map(|(index, value)| {
+                member(
+                    value,
+                    discriminators
+                        .get(index)
+                        .map(|(_, discriminator)| *discriminator),
+                )
+            })
+            .collect();
+
+        let value = json!({
+            "name": name(self.name()),
+            "discriminator_type": optional(self.discriminator_type()),
+            "unit": self.members().all(|member| member.payload().next().is_none()),
+            "members": members,
+        });
+
+        annotated(value, self.documentation(), self.tags())
+    }
+}
+
+impl ToContext for FlagsMember {
+    fn to_context(&self) -> Value {
+        let value = json!({
+            "name": name(self.name()),
+            "position": self.position(),
+            "bit": self.bit(),
+        });
+
+        annotated(value, self.documentation(), self.tags())
+    }
+}
+
+impl ToContext for NetworkFlags {
+    fn to_context(&self) -> Value {
+        let value = json!({
+            "name": name(self.name()),
+            "bits": self.width().bits(),
+            "primitive": self.width().primitive(),
+            "members": list(self.members()),
+        });
+
+        annotated(value, self.documentation(), self.tags())
+    }
+}
+
+impl ToContext for NetworkType {
+    fn to_context(&self) -> Value {
+        let value = json!({
+            "name": name(self.name()),
+            "aliased": self.aliased().to_context(),
+        });
+
+        annotated(value, self.documentation(), self.tags())
+    }
+}
+
+impl ToContext for NetworkConst {
+    /// The context of the constant without its' value, which requires the
+    /// file to be evaluated.
+    fn to_context(&self) -> Value {
+        let value = json!({
+            "name": name(self.name()),
+            "type": self.const_type().to_context(),
+            "value": null,
+        });
+
+        annotated(value, self.documentation(), self.tags())
+    }
+}
+
+impl ToContext for NetworkImport {
+    fn to_context(&self) -> Value {
+        annotated(
+            json!({ "path": self.path() }),
+            self.documentation(),
+            self.tags(),
+        )
+    }
+}
+
+impl ToContext for ProtocolMessage {
+    fn to_context(&self) -> Value {
+        json!({
+            "name": self.name().map_or(Value::Null, name),
+            "type": self.message_type().to_context(),
+        })
+    }
+}
+
+/// The context of a protocol entry with its' resolved discriminator.
+fn entry(entry: &ProtocolEntry, discriminator: Option<u64>) -> Value {
+    let value = json!({
+        "discriminator": discriminator,
+        "client": optional(entry.client()),
+        "server": optional(entry.server()),
+    });
+
+    annotated(value, entry.documentation(), entry.tags())
+}
+
+impl ToContext for NetworkProtocol {
+    fn to_context(&self) -> Value {
+        // the discriminators end before an entry, whose' implicit
+        // discriminator overflows, so the remaining entries have none
+        let discriminators = self.discriminators();
+        let entries: Vec<Value> = self
+            .entries()
+            .enumerate()
+            .map(|(index, value)| {
+                entry(
+                    value,
+                    discriminators
+                        .get(index)
+                        .map(|(discriminator, _)| *discriminator),
+                )
+            })
+            .collect();
+
+        let value = json!({
+            "name": name(self.name()),
+            "discriminator_type": optional(self.discriminator_type()),
+            "entries": entries,
+        });
+
+        annotated(value, self.documentation(), self.tags())
+    }
+}
+
+impl ToContext for ConstValue {
+    fn to_context(&self) -> Value {
+        match self {
+            ConstValue::Integer(value) => integer(*value),
+            ConstValue::Float(value) => Number::from_f64(*value).map_or(Value::Null, Value::Number),
+            ConstValue::String(value) => json!(value),
+        }
+    }
+}
+
+/// The context of a whole network file. The constants are evaluated
+/// without the `NETZ_SCHEMA` variable, so constants, which fail to
+/// evaluate or refer to the schema, have the value `null`.
+pub fn file(file: &NetworkFileReader) -> Value {
+    let evaluator = ConstEvaluator::new(file);
+    let consts: Vec<Value> = file
+        .consts()
+        .map(|constant| {
+            let mut value = constant.to_context();
+
+            if let Some(Ok(evaluated)) = evaluator.evaluate_name(constant.name()) {
+                value["value"] = evaluated.to_context();
+            }
+
+            value
+        })
+        .collect();
+
+    let value = json!({
+        "version": SCHEMA_VERSION,
+        "imports": list(file.imports()),
+        "types": list(file.aliases()),
+        "flags": list(file.all_flags()),
+        "enums": list(file.enumerations()),
+        "structs": list(file.structures()),
+        "consts": consts,
+        "protocols": list(file.protocols()),
+    });
+
+    annotated(value, file.documentation(), file.tags())
+}
+
+/// The file context as JSON string, which is the value of `NETZ_SCHEMA`.
+pub fn schema(file: &NetworkFileReader) -> String {
+    self::file(file).to_string()
+}
+
+/// A constant evaluator over the file, which provides the `NETZ_SCHEMA`
+/// variable.
+pub fn evaluator(file: &NetworkFileReader) -> ConstEvaluator<'_> {
+    ConstEvaluator::new(file).with_variable("NETZ_SCHEMA", ConstValue::String(schema(file)))
+}
+
+/// The contexts of the templates, which are generated for every single
+/// declaration of the file. Every context is the declaration extended by
+/// the key `file_is_<kind>` and `<kind>_name`, where the kind is `struct`,
+/// `enum`, `flags` or `type`. For structs, `fields` are the fields.
+pub fn declarations(file: &NetworkFileReader) -> Vec<Value> {
+    fn declaration(kind: &str, mut value: Value) -> Value {
+        value[format!("file_is_{kind}")] = Value::Bool(true);
+        value[format!("{kind}_name")] = value["name"].clone();
+        value
+    }
+
+    let types = file
+        .aliases()
+        .map(|alias| declaration("type", alias.to_context()));
+    let flags = file
+        .all_flags()
+        .map(|flags| declaration("flags", flags.to_context()));
+    let enums = file
+        .enumerations()
+        .map(|value| declaration("enum", value.to_context()));
+    let structs = file
+        .structures()
+        .map(|value| declaration("struct", value.to_context()));
+
+    types.chain(flags).chain(enums).chain(structs).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source(source: &str) -> NetworkFileReader {
+        NetworkFileReader::from_source(source).unwrap()
+    }
+
+    #[test]
+    fn names() {
+        let value = name("UserData");
+        assert_eq!(value["value"], "UserData");
+        assert_eq!(value["lower_flat_case"], "userdata");
+        assert_eq!(value["lower_dash_case"], "user-data");
+        assert_eq!(value["lower_camel_case"], "userData");
+        assert_eq!(value["upper_camel_case"], "UserData");
+        assert_eq!(value["lower_snake_case"], "user_data");
+        assert_eq!(value["upper_snake_case"], "USER_DATA");
+    }
+
+    #[test]
+    fn types() {
+        let file = source("struct A {\n    a: map(string -> option(u8)[]);\n    b: i(24);\n}");
+        let value = file.structures().next().unwrap().to_context();
+        let a = &value["fields"][0]["type"];
+
+        assert_eq!(a["kind"], "map");
+        assert_eq!(a["key"]["upper_camel_case"], "String");
+        assert_eq!(a["item"]["kind"], "array");
+        assert_eq!(a["item"]["element"]["kind"], "option");
+        assert_eq!(a["item"]["element"]["inner"]["value"], "u8");
+
+        let b = &value["fields"][1]["type"];
+        assert_eq!(
+            b,
+            &json!({ "kind": "integer", "signed": true, "bits": 24, "value": b["value"] })
+        );
+    }
+
+    #[test]
+    fn annotations() {
+        let file = source(
+            "/**\n * A user.\n * @note Stored.\n */\n@!limit(20B)\noption struct User {\n    @fixed id: u32 = 1;\n}",
+        );
+        let value = file.structures().next().unwrap().to_context();
+
+        assert_eq!(value["documentation"]["description"], "A user.");
+        assert_eq!(
+            value["documentation"]["tags"][0],
+            json!({ "name": "note", "content": "Stored." })
+        );
+        assert_eq!(
+            value["tags"][0],
+            json!({
+                "name": "limit",
+                "required": true,
+                "arguments": [{ "kind": "byte_size", "value": 20 }],
+            })
+        );
+        assert_eq!(value["tagged"]["limit"], true);
+        assert_eq!(value["fields"][0]["tagged"]["fixed"], true);
+        assert_eq!(value["fields"][0]["documentation"], Value::Null);
+    }
+
+    #[test]
+    fn enums() {
+        let file = source("enum A: u16 {\n    B;\n    C(string, u8) = 5;\n    D;\n}");
+        let value = file.enumerations().next().unwrap().to_context();
+
+        assert_eq!(value["discriminator_type"]["value"], "u16");
+        assert_eq!(value["unit"], false);
+        assert_eq!(value["members"][1]["payload"][1]["value"], "u8");
+
+        let discriminators: Vec<_> = value["members"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|member| member["discriminator"].as_u64().unwrap())
+            .collect();
+        assert_eq!(discriminators, [0, 5, 6]);
+    }
+
+    #[test]
+    fn files() {
+        let file = source(
+            "type Id = u64;\nflags F {\n    A = 1;\n    B = 9;\n}\nconst MAX: u32 = u32.MAX;\nconst SCHEMA: string = NETZ_SCHEMA;\nstruct S {}",
+        );
+        let value = self::file(&file);
+
+        assert_eq!(value["types"][0]["aliased"]["value"], "u64");
+        assert_eq!(value["flags"][0]["primitive"], "u16");
+        assert_eq!(value["flags"][0]["members"][1]["bit"], 8);
+        assert_eq!(value["consts"][0]["value"], u32::MAX);
+        assert_eq!(value["consts"][1]["value"], Value::Null);
+
+        let schema = evaluator(&file).evaluate_name("SCHEMA").unwrap().unwrap();
+        assert_eq!(schema, ConstValue::String(self::schema(&file)));
+
+        let declarations = declarations(&file);
+        assert_eq!(declarations.len(), 3);
+        assert_eq!(declarations[2]["file_is_struct"], true);
+        assert_eq!(declarations[2]["struct_name"]["value"], "S");
+        assert_eq!(declarations[2]["fields"], json!([]));
+    }
+}
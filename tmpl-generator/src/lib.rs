@@ -1,26 +1,73 @@
-use std::collections::{HashMap, HashSet};
+use std::{collections::HashMap, rc::Rc};
 
-use parser::components::TemplateElement;
+use parser::components::{Condition, TemplateElement};
 use serde_json::{json, Map, Value};
 
+pub mod context;
 pub mod parser;
 
+/// The indentation of a single `#indent` level.
+const INDENT: &str = "    ";
+
 /// The Template state structure keeps track of the template file
 /// and possible executions.
 #[derive(Clone, Debug)]
 pub struct Template {
     element: TemplateElement,
-    arguments: Value,
+    arguments: Rc<Value>,
     redirects: HashMap<String, Vec<String>>,
 }
 
+/// A file generated from a template.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TemplateFile {
+    /// The name written by `#file_name`.
+    pub name: Option<String>,
+    /// The extension written by `#file_extension`.
+    pub extension: Option<String>,
+    pub content: String,
+    indent: usize,
+}
+
+impl TemplateFile {
+    /// The name of the file with its' extension, if the template wrote a name.
+    pub fn file_name(&self) -> Option<String> {
+        let name = self.name.as_ref()?;
+
+        match &self.extension {
+            Some(extension) => Some(format!("{name}.{extension}")),
+            None => Some(name.clone()),
+        }
+    }
+
+    /// Writes text to the content. Lines are indented when their' first
+    /// character is written, empty lines stay empty.
+    fn write(&mut self, text: &str) {
+        for line in text.split_inclusive('\n') {
+            if line != "\n" && (self.content.is_empty() || self.content.ends_with('\n')) {
+                self.content += &INDENT.repeat(self.indent);
+            }
+
+            self.content += line;
+        }
+    }
+
+    /// Starts a new line, unless the content is empty or a line was just
+    /// started.
+    fn require_newline(&mut self) {
+        if !self.content.is_empty() && !self.content.ends_with('\n') {
+            self.content.push('\n');
+        }
+    }
+}
+
 impl Template {
     /// Create a new empty and undeclared template file. This function
     /// is mostly useless.
     pub fn new() -> Self {
         Self {
             element: TemplateElement::Ignored,
-            arguments: Value::Null,
+            arguments: Rc::new(Value::Null),
             redirects: HashMap::new(),
         }
     }
@@ -29,7 +76,7 @@ impl Template {
     pub fn from(template: &str) -> Option<Self> {
         Some(Self {
             element: TemplateElement::from_str(template)?,
-            arguments: Value::Null,
+            arguments: Rc::new(Value::Null),
             redirects: HashMap::new(),
         })
     }
@@ -39,141 +86,370 @@ impl Template {
         self.element = value;
     }
 
-    /// Set the argument context.
+    /// Set the argument context, see [context] for the context of network
+    /// files.
     pub fn set_context(&mut self, value: Value) {
-        self.arguments = value;
+        self.arguments = Rc::new(value);
     }
 
     /// Creates a new variable and adds it to the argument context, or if None,
-    /// deletes a variable and all sub-variables from the tree.
+    /// deletes a variable and all sub-variables from the tree. Returns whether
+    /// the context changed.
     pub fn set_argument(&mut self, key: Vec<String>, value: Option<String>) -> bool {
-        todo!()
-    }
+        let Some((last, parents)) = key.split_last() else {
+            return false;
+        };
 
-    /// Index the argument context and search for the variable.
-    pub fn get_argument(&self, mut key: Vec<String>) -> Option<String> {
-        if key.len() == 0 {
-            return None;
-        }
+        let arguments = Rc::make_mut(&mut self.arguments);
 
-        let mut proof_cycles = HashSet::new();
+        if !arguments.is_object() {
+            *arguments = Value::Object(Map::new());
+        }
 
-        loop {
-            let root = key.get(0).unwrap();
+        let mut map = arguments.as_object_mut().unwrap();
 
-            if proof_cycles.contains(root) {
-                return None;
+        for parent in parents {
+            if value.is_none() && !map.contains_key(parent) {
+                return false;
             }
 
-            if let Some(append) = self.redirects.get(key.get(0).unwrap()) {
-                proof_cycles.insert(root.to_owned());
+            let child = map
+                .entry(parent.clone())
+                .or_insert_with(|| Value::Object(Map::new()));
+
+            if !child.is_object() {
+                *child = Value::Object(Map::new());
+            }
 
-                let mut tmp = append.clone();
-                tmp.append(&mut key);
-                key = tmp;
+            map = child.as_object_mut().unwrap();
+        }
 
-                continue;
+        match value {
+            Some(value) => {
+                map.insert(last.clone(), Value::String(value));
+                true
             }
+            None => map.remove(last).is_some(),
+        }
+    }
+
+    /// Replaces the first identifier of the key, if it is the value of a
+    /// `#foreach` directive, by the path of the current item.
+    fn resolve(&self, key: &[String]) -> Vec<String> {
+        match key.split_first() {
+            Some((root, rest)) => match self.redirects.get(root) {
+                Some(path) => path.iter().chain(rest).cloned().collect(),
+                None => key.to_vec(),
+            },
+            None => vec![],
+        }
+    }
+
+    /// Index the argument context and search for the value of the variable.
+    fn lookup(&self, key: &[String]) -> Option<&Value> {
+        let key = self.resolve(key);
+
+        if key.is_empty() {
+            return None;
+        }
+
+        let mut value = self.arguments.as_ref();
 
-            break;
+        for index in key.iter() {
+            value = match value {
+                Value::Object(map) => map.get(index.as_str())?,
+                Value::Array(vec) => vec.get(index.parse::<usize>().ok()?)?,
+                _ => return None,
+            };
         }
 
-        let mut value = &self.arguments;
+        Some(value)
+    }
 
-        for index in key.into_iter() {
+    /// Index the argument context and search for the variable. Objects are
+    /// read by their' `value`, arrays and `null` have no text.
+    pub fn get_argument(&self, key: Vec<String>) -> Option<String> {
+        fn text(value: &Value) -> Option<String> {
             match value {
-                Value::Null => return Some("".to_owned()),
-                Value::String(s) => return Some(s.clone()),
-                Value::Bool(b) => return if *b { Some("true".to_owned()) } else { None },
-                Value::Number(i) => return Some(i.to_string()),
-                Value::Object(map) => {
-                    value = map.get(index.as_str())?;
-                }
-                Value::Array(vec) => {
-                    let idx = index.parse::<usize>().ok()?;
-                    value = vec.get(idx)?;
-                }
+                Value::String(s) => Some(s.clone()),
+                Value::Number(i) => Some(i.to_string()),
+                Value::Bool(b) => b.then(|| "true".to_owned()),
+                Value::Object(map) => text(map.get("value")?),
+                Value::Null | Value::Array(_) => None,
             }
         }
 
-        return None;
+        text(self.lookup(&key)?)
+    }
+
+    /// Whether the condition of a `#requires` directive holds.
+    fn holds(&self, condition: &Condition) -> bool {
+        match condition {
+            Condition::Defined(key) => match self.lookup(key) {
+                None | Some(Value::Null) | Some(Value::Bool(false)) => false,
+                Some(Value::Array(vec)) => !vec.is_empty(),
+                Some(_) => true,
+            },
+            Condition::Not(condition) => !self.holds(condition),
+        }
     }
 
     /// Overrides some variables
     pub fn override_arguments(&mut self) {
-        if !self.arguments.is_object() {
-            self.arguments = Value::Object(Map::new());
+        let arguments = Rc::make_mut(&mut self.arguments);
+
+        if !arguments.is_object() {
+            *arguments = Value::Object(Map::new());
         }
 
-        let arguments = self.arguments.as_object_mut().unwrap();
-        
+        let arguments = arguments.as_object_mut().unwrap();
+
         // Set true and false as default boolean arguments.
         arguments.insert("true".to_owned(), Value::String("true".to_owned()));
         arguments.remove("false");
 
         // Sets the meta arguments: Version and version support flag.
-        arguments.insert("meta".to_owned(), json!({
-            "version": "v1",
-            "v1": "true",
-        }));
+        arguments.insert(
+            "meta".to_owned(),
+            json!({
+                "version": context::SCHEMA_VERSION,
+                context::SCHEMA_VERSION: "true",
+            }),
+        );
+    }
+
+    /// Generate a file from the current template. Returns `None`, if the
+    /// template was rejected by a `#requires` directive of the top level.
+    pub fn render(&self) -> Result<Option<TemplateFile>, String> {
+        let mut file = TemplateFile::default();
+
+        let accepted = match &self.element {
+            TemplateElement::Scope(scope) => self.render_scope(scope, &mut file)?,
+            element => self.render_element(element, &mut file)?,
+        };
+
+        Ok(accepted.then_some(file))
     }
 
-    /// Create a string from the current template.
+    /// Create a string from the current template. A rejected template
+    /// generates the empty string.
     pub fn generate(&self) -> Result<String, String> {
-        match &self.element {
-            TemplateElement::Ignored => Ok(String::from("")),
+        Ok(self.render()?.map(|file| file.content).unwrap_or_default())
+    }
+
+    /// Writes the elements of a scope until a `#requires` directive rejects
+    /// the scope. Returns whether the scope was accepted.
+    fn render_scope(
+        &self,
+        scope: &[TemplateElement],
+        file: &mut TemplateFile,
+    ) -> Result<bool, String> {
+        for element in scope {
+            if !self.render_element(element, file)? {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Writes an element to the file. Returns `false`, if the element
+    /// rejects the scope it is in.
+    fn render_element(
+        &self,
+        element: &TemplateElement,
+        file: &mut TemplateFile,
+    ) -> Result<bool, String> {
+        match element {
+            TemplateElement::Ignored => {}
             TemplateElement::Variable(value) => {
-                if let Some(v) = self.get_argument(value.clone()) {
-                    Ok(v)
-                } else {
-                    Ok("".to_owned())
+                file.write(&self.get_argument(value.clone()).unwrap_or_default());
+            }
+            TemplateElement::Requires(condition) => return Ok(self.holds(condition)),
+            TemplateElement::StringLiteral(string) => file.write(string),
+            TemplateElement::Scope(scope) => {
+                // a rejected scope leaves the file as it was
+                let mut scoped = file.clone();
+
+                if self.render_scope(scope, &mut scoped)? {
+                    *file = scoped;
                 }
             }
-            TemplateElement::Requires(value) => {
-                if self.get_argument(value.clone()).is_some() {
-                    Ok("".to_owned())
-                } else {
-                    Err(format!(
-                        "The (requiring) variable {} killed the scope.",
-                        value.join(".")
-                    ))
+            TemplateElement::FileName(element) => {
+                let mut name = TemplateFile::default();
+
+                if self.render_element(element, &mut name)? {
+                    file.name = Some(name.content);
                 }
             }
-            TemplateElement::StringLiteral(string) => Ok(string.clone()),
-            TemplateElement::Scope(scope) => {
-                let mut output = String::from("");
+            TemplateElement::FileExtension(element) => {
+                let mut extension = TemplateFile::default();
+
+                if self.render_element(element, &mut extension)? {
+                    file.extension = Some(extension.content);
+                }
+            }
+            TemplateElement::RequireNewline => file.require_newline(),
+            TemplateElement::Indent => file.indent += 1,
+            TemplateElement::Outdent => {
+                file.indent = file
+                    .indent
+                    .checked_sub(1)
+                    .ok_or("`#outdent` has no matching `#indent`")?;
+            }
+            TemplateElement::Foreach(foreach) => {
+                let length = match self.lookup(&foreach.variable) {
+                    None | Some(Value::Null) => 0,
+                    Some(Value::Array(vec)) => vec.len(),
+                    Some(_) => {
+                        return Err(format!(
+                            "The variable {} is not an array.",
+                            foreach.variable.join(".")
+                        ))
+                    }
+                };
 
-                for element in scope {
+                let variable = self.resolve(&foreach.variable);
+
+                for index in 0..length {
                     let mut context = self.clone();
-                    context.set_template(element.clone());
+                    let mut item = variable.clone();
+                    item.push(index.to_string());
+                    context.redirects.insert(foreach.value.clone(), item);
+
+                    // every iteration is a scope of its' own
+                    let mut scoped = file.clone();
 
-                    if let Ok(s) = context.generate() {
-                        output += s.as_str();
-                    } else {
-                        break;
+                    if context.render_scope(&foreach.scope, &mut scoped)? {
+                        *file = scoped;
                     }
                 }
-
-                Ok(output)
-            }
-            TemplateElement::Foreach(foreach) => {
-                let mut context = self.clone();
-                context.redirects.insert(foreach.value.clone(), foreach.variable.clone());
-                context.set_template(TemplateElement::Scope(foreach.scope.clone()));
-                Ok(context.generate()?)
             }
         }
+
+        Ok(true)
     }
 }
 
+impl Default for Template {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use network_parser::parser::network::NetworkFileReader;
 
     #[test]
     fn string_template() {
         let template = Template::from("\"Hello!\"").unwrap();
         assert_eq!("Hello!", template.generate().unwrap().as_str());
     }
+
+    fn render(template: &str, context: Value) -> Option<TemplateFile> {
+        let mut template = Template::from(template).unwrap();
+        template.set_context(context);
+        template.render().unwrap()
+    }
+
+    #[test]
+    fn requires() {
+        let context = json!({ "a": "A", "empty": [], "no": false, "null": null });
+
+        assert_eq!(
+            render("#requires a a", context.clone()).unwrap().content,
+            "A"
+        );
+        assert_eq!(render("#requires missing a", context.clone()), None);
+        assert_eq!(
+            render("#requires (#not empty) a", context.clone())
+                .unwrap()
+                .content,
+            "A"
+        );
+
+        let file = render(
+            "\"<\" (#requires no \"no\") (#requires null \"null\") (#requires #not #not a a) \">\"",
+            context,
+        );
+        assert_eq!(file.unwrap().content, "<A>");
+    }
+
+    #[test]
+    fn foreach() {
+        let context = json!({ "rows": [{ "cells": [1, 2] }, { "cells": [] }, { "cells": [3] }] });
+        let template = r#"
+            // the older form is read the same way
+            foreach row : rows (
+                (#requires row.cells "[" #foreach cell #of row.cells (cell) "]")
+            )
+            #foreach row #of rows (#requires (#not row.cells) "empty")
+        "#;
+
+        assert_eq!(render(template, context).unwrap().content, "[12][3]empty");
+    }
+
+    #[test]
+    fn indentation() {
+        let template = r#""a {" #indent #require_newline "b\n\nc" #outdent #require_newline #require_newline "}""#;
+        assert_eq!(
+            render(template, Value::Null).unwrap().content,
+            "a {\n    b\n\n    c\n}"
+        );
+
+        let mut template = Template::from("#outdent").unwrap();
+        template.set_context(Value::Null);
+        assert!(template.render().is_err());
+    }
+
+    #[test]
+    fn arguments() {
+        let mut template = Template::from("a.b \" \" a.c.value").unwrap();
+        assert!(template.set_argument(vec!["a".to_owned(), "b".to_owned()], Some("B".to_owned())));
+        template.override_arguments();
+
+        assert_eq!(
+            template.get_argument(vec!["true".to_owned()]),
+            Some("true".to_owned())
+        );
+        assert_eq!(
+            template.get_argument(vec!["meta".to_owned(), "v1".to_owned()]),
+            Some("true".to_owned())
+        );
+        assert_eq!(template.generate().unwrap(), "B ");
+
+        assert!(template.set_argument(vec!["a".to_owned()], None));
+        assert!(!template.set_argument(vec!["a".to_owned(), "b".to_owned()], None));
+        assert_eq!(template.generate().unwrap(), " ");
+    }
+
+    #[test]
+    fn typescript_struct() {
+        let file = NetworkFileReader::from_source(
+            "type Name = string;\nstruct Image {}\nstruct UserData {\n    user_name: Name;\n    avatar: Image;\n}",
+        )
+        .unwrap();
+        let mut template =
+            Template::from(include_str!("../../templates/typescript/both.struct.tmpl")).unwrap();
+
+        let files: Vec<_> = context::declarations(&file)
+            .into_iter()
+            .filter_map(|context| {
+                template.set_context(context);
+                template.override_arguments();
+                template.render().unwrap()
+            })
+            .collect();
+
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].file_name().unwrap(), "image.ts");
+        assert_eq!(files[0].content, "export default interface IImage {}\n");
+        assert_eq!(files[1].file_name().unwrap(), "user-data.ts");
+        assert_eq!(
+            files[1].content,
+            "export default interface IUserData {\n    userName: Name;\n    avatar: Image;\n}\n"
+        );
+    }
 }
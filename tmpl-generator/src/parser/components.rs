@@ -11,6 +11,21 @@ pub struct ForeachScope {
     pub scope: Vec<TemplateElement>,
 }
 
+/// A condition of the `#requires` directive. A variable is defined, if it
+/// exists and is neither `null`, `false` nor an empty array.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Condition {
+    /// The variable is defined.
+    Defined(Vec<String>),
+
+    /// The negation of a condition, written as `#not`.
+    ///
+    /// ```tmpl
+    /// #requires (#not fields) "{}"
+    /// ```
+    Not(Box<Condition>),
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum TemplateElement {
     /// Any ignored template element like comments and whitespace that have
@@ -20,17 +35,37 @@ pub enum TemplateElement {
     /// An (environment) variable is a simple concatenation of identifiers
     /// with dots (e.g. `root.child.attribute`) and has two definition states:
     /// When undefined, it can be used with `requires` to reject a scope, and
-    /// when defined, it will write the variables' string contents. All
-    /// variables are of type string.
-    ///
-    /// The variable enum will also hold any internal function, like `indent`,
-    /// `outdent` and `require_newline`
+    /// when defined, it will write the variables' string contents. Objects
+    /// write their' `value`, like the names of the template context.
     Variable(Vec<String>),
 
-    /// A variable with the `requires` keyword will act as a boolean value and
-    /// read its' defined state. If undefined, the entire scope (marked by the
-    /// round parentheses `(` and `)`) will be rejected and not printed.
-    Requires(Vec<String>),
+    /// The `#requires` directive reads a condition. If it does not hold, the
+    /// entire scope (marked by the round parentheses `(` and `)`) will be
+    /// rejected and not printed. A rejected top level scope rejects the file.
+    ///
+    /// ```tmpl
+    /// #requires file_is_struct
+    /// (#requires fields "{" ... "}")
+    /// ```
+    Requires(Condition),
+
+    /// The `#file_name` directive writes the element into the name of the
+    /// generated file instead of its' content.
+    FileName(Box<TemplateElement>),
+
+    /// The `#file_extension` directive writes the element into the extension
+    /// of the generated file instead of its' content.
+    FileExtension(Box<TemplateElement>),
+
+    /// The `#require_newline` directive writes a line break, unless the
+    /// content is empty or already ends with one.
+    RequireNewline,
+
+    /// The `#indent` directive indents all following lines by one more level.
+    Indent,
+
+    /// The `#outdent` directive reverts one `#indent`.
+    Outdent,
 
     /// A string literal is the most simple element of a template file
     /// and in execution it simply writes its' content.
@@ -56,11 +91,14 @@ pub enum TemplateElement {
     /// // root.2 = "1"
     /// // root.2.Hello = "C"
     ///
-    /// foreach v : root {
+    /// #foreach v #of root (
     ///     v.Hello " "
-    /// }
+    /// )
     /// ```
     ///
+    /// The older form `foreach v : root ( ... )` is read the same way. Every
+    /// iteration is a scope of its' own, which `#requires` can reject.
+    ///
     /// will produce the following text
     ///
     /// ```txt
@@ -70,8 +108,9 @@ pub enum TemplateElement {
 }
 
 impl TemplateElement {
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(input: &str) -> Option<TemplateElement> {
-        let (input, v) = Self::scope_from_str.parse(input).ok()?;
+        let (_, v) = Self::scope_from_str.parse(input).ok()?;
         Some(TemplateElement::Scope(v))
     }
 
@@ -2,10 +2,16 @@
 //! template files, such as whitespace, comments and string related methods.
 
 use nom::{
-    branch::alt, bytes::complete::{is_a, is_not, tag, take_until}, character::complete::{alphanumeric1, anychar, multispace1}, combinator::{eof, map, opt, recognize, value}, multi::{many0, many0_count}, sequence::{delimited, pair}, IResult, Parser
+    branch::alt,
+    bytes::complete::{is_a, is_not, tag, take_until},
+    character::complete::{alphanumeric1, multispace1},
+    combinator::{eof, map, opt, recognize, value, verify},
+    multi::{many0, many0_count},
+    sequence::{delimited, pair, preceded},
+    IResult, Parser,
 };
 
-use super::components::{ForeachScope, TemplateElement};
+use super::components::{Condition, ForeachScope, TemplateElement};
 
 /// A combinator that takes a parser `inner` and produces a parser that also consumes both leading and
 /// trailing whitespace, returning the output of `inner`.
@@ -33,13 +39,14 @@ pub fn whitespace(input: &str) -> IResult<&str, ()> {
 /// use tmpl_generator::parser::util::c_comment;
 ///
 /// assert!(c_comment("// Hello World!\n").is_ok());
+/// assert_eq!(c_comment("// Hello\n\"World!\"").unwrap().0, "\"World!\"");
 /// ```
 ///
 /// https://github.com/rust-bakery/nom/blob/main/doc/nom_recipes.md#comments
 pub fn c_comment(input: &str) -> IResult<&str, ()> {
     value(
         (),
-        delimited(tag("//"), many0(anychar), alt((is_a("\n\r"), eof))),
+        delimited(tag("//"), opt(is_not("\n\r")), alt((is_a("\n\r"), eof))),
     )
     .parse(input)
 }
@@ -89,12 +96,13 @@ pub fn read_ignored(mut input: &str) -> IResult<&str, ()> {
 ///
 /// assert_eq!(identifier("FooBar").unwrap(), ("", "FooBar"));
 /// assert_eq!(identifier("fooBar123").unwrap(), ("", "fooBar123"));
+/// assert_eq!(identifier("field2_name").unwrap(), ("", "field2_name"));
 /// assert!(identifier("123fooBar").is_err());
 /// ```
 pub fn identifier(input: &str) -> IResult<&str, &str> {
     recognize(pair(
         is_a("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ_"),
-        many0_count(alphanumeric1),
+        many0_count(alt((alphanumeric1, is_a("_")))),
     ))
     .parse(input)
 }
@@ -104,7 +112,7 @@ pub fn identifier(input: &str) -> IResult<&str, &str> {
 /// // ```
 /// // use tmpl_generator::parser::util::variable;
 /// // use tmpl_generator::parser::components::TemplateElement;
-/// // 
+/// //
 /// // assert_eq!(variable("hello.world").unwrap(), ("", TemplateElement::Variable(vec!["hello".to_owned(), "world".to_owned()])));
 /// // assert_eq!(variable("root.child.child").unwrap(), ("", TemplateElement::Variable(vec!["root".to_owned(), "child".to_owned(), "child".to_owned()])));
 /// // assert_eq!(variable("simple").unwrap(), ("", TemplateElement::Variable(vec!["simple".to_owned()])));
@@ -135,13 +143,14 @@ pub fn escape_string_literal(input: &str) -> IResult<&str, &str> {
         value("\t", nom::character::complete::char('t')),
         value("\\", nom::character::complete::char('\\')),
         value("\"", nom::character::complete::char('\"')),
-    )).parse(input)
+    ))
+    .parse(input)
 }
 
 /// A string literal is a literal starting and closing with quotation marks. The
 /// closing quotation mark can be escaped with a backslash, which is used as a
 /// general escape character.
-/// 
+///
 /// // ```
 /// // use tmpl_generator::parser::util::string_literal;
 /// // use tmpl_generator::parser::components::TemplateElement;
@@ -199,11 +208,129 @@ pub fn foreach_scope(input: &str) -> IResult<&str, ForeachScope> {
     let (input, _) = read_ignored.parse(input)?;
     let (input, scope) = surrounded_scope.parse(input)?;
 
-    IResult::Ok((input, ForeachScope {
-        value,
-        variable,
-        scope,
-    }))
+    IResult::Ok((
+        input,
+        ForeachScope {
+            value,
+            variable,
+            scope,
+        },
+    ))
+}
+
+/// Reads the name of a directive, an identifier after the `#` symbol.
+///
+/// ```
+/// use tmpl_generator::parser::util::directive_name;
+///
+/// assert_eq!(directive_name("#requires x").unwrap(), (" x", "requires"));
+/// ```
+pub fn directive_name(input: &str) -> IResult<&str, &str> {
+    preceded(tag("#"), identifier).parse(input)
+}
+
+/// Reads a directive, which is only accepted if its' name is `name`.
+fn keyword<'a>(name: &'static str) -> impl FnMut(&'a str) -> IResult<&'a str, &'a str> {
+    verify(directive_name, move |found: &str| found == name)
+}
+
+/// Reads the condition of a `#requires` directive, which is a variable,
+/// a negated condition or a condition in round parentheses.
+///
+/// ```
+/// use tmpl_generator::parser::util::condition;
+/// use tmpl_generator::parser::components::Condition;
+///
+/// assert_eq!(
+///     condition("(#not fields)").unwrap(),
+///     ("", Condition::Not(Box::new(Condition::Defined(vec!["fields".to_owned()]))))
+/// );
+/// ```
+pub fn condition(input: &str) -> IResult<&str, Condition> {
+    alt((
+        map(
+            preceded(pair(keyword("not"), read_ignored), condition),
+            |condition| Condition::Not(Box::new(condition)),
+        ),
+        delimited(
+            pair(tag("("), read_ignored),
+            condition,
+            pair(read_ignored, tag(")")),
+        ),
+        map(variable, Condition::Defined),
+    ))
+    .parse(input)
+}
+
+/// Reads the `#foreach value #of variable ( ... )` directive.
+pub fn foreach_directive(input: &str) -> IResult<&str, ForeachScope> {
+    let (input, _) = keyword("foreach").parse(input)?;
+    let (input, _) = read_ignored.parse(input)?;
+    let (input, value) = identifier.map(|s| s.to_owned()).parse(input)?;
+    let (input, _) = read_ignored.parse(input)?;
+    let (input, _) = keyword("of").parse(input)?;
+    let (input, _) = read_ignored.parse(input)?;
+    let (input, variable) = variable.parse(input)?;
+    let (input, _) = read_ignored.parse(input)?;
+    let (input, scope) = surrounded_scope.parse(input)?;
+
+    IResult::Ok((
+        input,
+        ForeachScope {
+            value,
+            variable,
+            scope,
+        },
+    ))
+}
+
+/// Reads the argument of the `#file_name` and `#file_extension`
+/// directives, which is a single element.
+fn argument(input: &str) -> IResult<&str, Box<TemplateElement>> {
+    map(
+        alt((
+            surrounded_scope.map(TemplateElement::Scope),
+            string_literal.map(TemplateElement::StringLiteral),
+            variable.map(TemplateElement::Variable),
+        )),
+        Box::new,
+    )
+    .parse(input)
+}
+
+/// Reads any directive, which starts with the `#` symbol.
+///
+/// ```
+/// use tmpl_generator::parser::util::directive;
+/// use tmpl_generator::parser::components::TemplateElement;
+///
+/// assert_eq!(directive("#require_newline").unwrap(), ("", TemplateElement::RequireNewline));
+/// assert_eq!(
+///     directive("#file_extension \"ts\"").unwrap(),
+///     ("", TemplateElement::FileExtension(Box::new(TemplateElement::StringLiteral("ts".to_owned()))))
+/// );
+/// assert!(directive("#unknown").is_err());
+/// ```
+pub fn directive(input: &str) -> IResult<&str, TemplateElement> {
+    alt((
+        map(
+            preceded(pair(keyword("requires"), read_ignored), condition),
+            TemplateElement::Requires,
+        ),
+        map(
+            preceded(pair(keyword("file_name"), read_ignored), argument),
+            TemplateElement::FileName,
+        ),
+        map(
+            preceded(pair(keyword("file_extension"), read_ignored), argument),
+            TemplateElement::FileExtension,
+        ),
+        value(TemplateElement::RequireNewline, keyword("require_newline")),
+        value(TemplateElement::Indent, keyword("indent")),
+        value(TemplateElement::Outdent, keyword("outdent")),
+        foreach_directive.map(TemplateElement::Foreach),
+    ))
+    .parse(input)
 }
 
 pub fn surrounded_scope(input: &str) -> IResult<&str, Vec<TemplateElement>> {
@@ -214,13 +341,23 @@ pub fn surrounded_scope(input: &str) -> IResult<&str, Vec<TemplateElement>> {
 /// but will provide them in a recursive call to self.
 pub fn scope(input: &str) -> IResult<&str, Vec<TemplateElement>> {
     let (input, structures) = many0(alt((
-        surrounded_scope.map(|s| TemplateElement::Scope(s)),
-        foreach_scope.map(|foreach| TemplateElement::Foreach(foreach)),
-        value(TemplateElement::Ignored, read_ignored),
-        string_literal.map(|s| TemplateElement::StringLiteral(s)),
-        variable.map(|v| TemplateElement::Variable(v)),
+        surrounded_scope.map(TemplateElement::Scope),
+        foreach_scope.map(TemplateElement::Foreach),
+        directive,
+        value(
+            TemplateElement::Ignored,
+            alt((whitespace, c_comment, c_multiline_comment)),
+        ),
+        string_literal.map(TemplateElement::StringLiteral),
+        variable.map(TemplateElement::Variable),
     )))
     .parse(input)?;
 
-    IResult::Ok((input, structures.into_iter().filter(|e| TemplateElement::Ignored != *e).collect()))
+    IResult::Ok((
+        input,
+        structures
+            .into_iter()
+            .filter(|e| TemplateElement::Ignored != *e)
+            .collect(),
+    ))
 }
@@ -9,7 +9,7 @@
 use paste::paste;
 
 /// Contains the characters that case insensitive separate identifier words.
-const SEPARATION_CHARACTERS: &str = &"-_~,. ";
+const SEPARATION_CHARACTERS: &str = "-_~,. ";
 
 /// The case generator macro expects a documented trait method
 /// and generates case conversions and assertion methods.
@@ -72,8 +72,8 @@ macro_rules! add_case {
             $(#[$outer])*
             ///
             /// The method `is_strict_case` will return true for every
-            /// `case`, if the conversion to split case and generation
-            /// matches case sensitive.
+            /// `case`, if the identifier matches its' conversion case
+            /// sensitive.
             /// 
             /// ```rs
             /// use util_cases::CaseStyles;
@@ -82,7 +82,7 @@ macro_rules! add_case {
             /// assert!("HttpRequest".is_strict_pascal_case());
             /// ```
             fn [<is_strict_ $case_name>] (&$s) -> bool {
-                &$s.to_split_case().join("") == &$s.[<to_ $case_name>]()
+                $s.to_identifier() == $s.[<to_ $case_name>]()
             }
         }
     };
@@ -96,6 +96,12 @@ pub trait CaseStyles {
     /// work with case insensitive characters other than a few separation markers.
     fn to_split_case(&self) -> Vec<String>;
 
+    /// The identifier as it is written. Types which only store the words
+    /// of an identifier join them without separators.
+    fn to_identifier(&self) -> String {
+        self.to_split_case().join("")
+    }
+
     add_case! {
         /// The flat case (`flatcase`) conversion concatenates the
         /// words of an identifier into lowercase letters without
@@ -148,8 +154,8 @@ pub trait CaseStyles {
                 out
             };
 
-            (&self.to_split_case())
-                .into_iter()
+            self.to_split_case()
+                .iter()
                 .map(capitalize)
                 .collect::<Vec<String>>()
                 .join("")
@@ -184,6 +190,10 @@ pub trait CaseStyles {
 /// Implements for `CaseStyles` for the [common trait](https://www.reddit.com/r/rust/comments/zfgo1f/common_trait_for_str_string_string_arcstring/)
 /// which all string types share.
 impl<T: AsRef<str>> CaseStyles for T {
+    fn to_identifier(&self) -> String {
+        self.as_ref().to_string()
+    }
+
     fn to_split_case(&self) -> Vec<String> {
         let identifier = self.as_ref().to_string();
         let separation = identifier
@@ -217,7 +227,7 @@ impl<T: AsRef<str>> CaseStyles for T {
                 const DEFAULT: char = '?';
 
                 let previous_letter = buffer.chars().last().unwrap_or(DEFAULT);
-                let next_letter = element.chars().skip(idx + 1).next().unwrap_or(DEFAULT);
+                let next_letter = element.chars().nth(idx + 1).unwrap_or(DEFAULT);
 
                 match c {
                     // If we're an uppercase letter and the next letter is lowercase, we start
@@ -239,6 +249,11 @@ impl<T: AsRef<str>> CaseStyles for T {
                     c if c.is_ascii_lowercase() => {
                         buffer += c.to_string().as_str();
                     }
+                    // Digits have no case and belong to the word they follow.
+                    // This covers `...a1` and `...A1`
+                    c if c.is_ascii_digit() => {
+                        buffer += c.to_string().as_str();
+                    }
                     // NOTE: The case `...aA` does not need coverage.
                     // If the letter has no case, panic.
                     _ => panic!(
@@ -248,12 +263,12 @@ impl<T: AsRef<str>> CaseStyles for T {
                 }
             }
 
-            if buffer.len() != 0 {
+            if !buffer.is_empty() {
                 vec.push(buffer);
             }
         }
 
-        vec.into_iter().filter(|s| s.len() != 0).collect()
+        vec.into_iter().filter(|s| !s.is_empty()).collect()
     }
 }
 
@@ -320,5 +335,26 @@ mod tests {
     #[test]
     fn snake_case() {
         assert_eq!("HelloWorld".to_snake_case(), "hello_world");
+        assert!("author_id".is_strict_snake_case());
+        assert!(!"authorId".is_strict_snake_case());
+    }
+
+    #[test]
+    fn strict_cases() {
+        assert!("HelloWorld".is_strict_pascal_case());
+        assert!(!"HTTPRequest".is_strict_pascal_case());
+        assert!("MAX_LENGTH".is_strict_constant_case());
+        assert!(!"MaxLength".is_strict_constant_case());
+    }
+
+    #[test]
+    fn digits() {
+        assert_eq!("u8".to_split_case(), vec!["u8".to_string()]);
+        assert_eq!(
+            "Sha256Hash".to_split_case(),
+            vec!["Sha256".to_string(), "Hash".to_string()]
+        );
+        assert_eq!("ipv4Address".to_snake_case(), "ipv4_address");
+        assert!("u16".is_strict_flat_case());
     }
 }
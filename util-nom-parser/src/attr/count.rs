@@ -6,6 +6,7 @@ use std::collections::HashMap;
 use util_cases::CaseStyles;
 
 impl Rule {
+    #[allow(dead_code)]
     fn qualify_type() {}
 
     /// Returns a map of all declared fields and their respective
@@ -39,7 +40,7 @@ impl Rule {
             }
 
             // TODO add proper hashmap scanner
-            Rule::Branch(vec) => todo!(),
+            Rule::Branch(_vec) => todo!(),
 
             // Nested rules just pass the hashmap and other ignore.
             Rule::Option(rule) | Rule::Repetition(rule) => rule.get_fields(map),
@@ -6,17 +6,20 @@ use std::str::FromStr;
 use crate::regexp::{Rule, RegexpRange};
 use proc_macro::{Delimiter, TokenStream, TokenTree};
 
+#[allow(dead_code)] // reserved for the error messages of the TODOs below
 fn error(span: proc_macro::Span, msg: impl std::fmt::Display) -> TokenStream {
     syn::Error::new(span.into(), msg).to_compile_error().into()
 }
 
 impl From<TokenStream> for Rule {
+    // the empty `match` arms are placeholders for error messages
+    #[allow(clippy::single_match)]
     fn from(attrs: TokenStream) -> Self {
         let mut vec = Vec::new();
         let mut iter = attrs.clone().into_iter();
 
         while let Some(attr) = iter.next() {
-            let span = attr.span();
+            let _span = attr.span();
 
             match attr {
                 TokenTree::Literal(literal) => {
@@ -53,7 +56,7 @@ impl From<TokenStream> for Rule {
                         None => {} // TODO error: `*` was used at the beginning of a scope
                     },
                     '-' => match (vec.pop(), iter.next()) {
-                        (Some(id), Some(tp)) => {
+                        (Some(_id), Some(_tp)) => {
                             // TODO
                         }
                         _ => {} // TODO error: `-` was used at the beginning or end of a scope
@@ -18,9 +18,9 @@ impl Rule {
     }
 }
 
-impl Into<TokenStream> for Rule {
-    fn into(self) -> TokenStream {
-        match self {
+impl From<Rule> for TokenStream {
+    fn from(val: Rule) -> Self {
+        match val {
             // Whitespace is an atomic lambda. It will expect one whitespace
             // character and trim the start.
             Rule::Whitespace => {
@@ -62,7 +62,7 @@ impl Into<TokenStream> for Rule {
                 let stream: proc_macro2::TokenStream = range.into();
                 quote! { nom::character::complete::satisfy ( #stream ) }
             },
-            Rule::Branch(vec) => todo!("branch not implemented"),
+            Rule::Branch(_vec) => todo!("branch not implemented"),
         }
         .into()
     }
@@ -5,8 +5,8 @@ impl Rule {
     /// rule.
     fn dimension(&self) -> Dimension {
         match self {
-            Rule::Option(rule) => Dimension::Option,
-            Rule::Repetition(rule) => Dimension::Many,
+            Rule::Option(_rule) => Dimension::Option,
+            Rule::Repetition(_rule) => Dimension::Many,
             _ => Dimension::Scalar,
         }
     }
@@ -1,21 +1,20 @@
 use crate::regexp::Rule;
 use proc_macro::TokenStream;
-use quote::{format_ident, quote, ToTokens};
+use quote::{format_ident, quote};
 use std::collections::HashMap;
-use syn::{Fields, FieldsNamed, ItemStruct, Type};
+use syn::{ItemStruct, Type};
 
 fn list_fields(input: &ItemStruct) -> HashMap<String, Type> {
     input
         .fields
         .iter()
-        .enumerate()
-        .map(|(_idx, field)| {
+        .map(|field| {
             if let Some(field_name) = &field.ident {
                 return (field_name.to_string(), field.ty.clone());
             }
             panic!(
                 "The macro [grammar] does not support tuple structs: `{}`",
-                input.ident.to_string()
+                input.ident
             );
         })
         .collect()
@@ -27,25 +26,25 @@ pub fn parse_struct(context: Rule, input: ItemStruct) -> TokenStream {
 
     // TODO use where clauses?
     // TODO instead of panic, use: https://stackoverflow.com/questions/57025894/issuing-a-warning-at-compile-time
-    if let Some(wher) = input.generics.where_clause {
+    if let Some(_wher) = input.generics.where_clause {
         panic!("The macro [grammar] does not support `where` clauses, but `{name}` had it implemented.")
     }
 
     if let Some(c) = input.generics.const_params().next() {
-        panic!("The macro [grammar] does not support const generics, but `{name}` had `{}` implemented.", c.ident.to_string())
+        panic!("The macro [grammar] does not support const generics, but `{name}` had `{}` implemented.", c.ident)
     }
 
     if let Some(c) = input.generics.lifetimes().next() {
         panic!(
             "The macro [grammar] does not support lifetimes, but `{name}` had `{}` implemented.",
-            c.lifetime.ident.to_string()
+            c.lifetime.ident
         )
     }
 
     if let Some(c) = input.generics.type_params().next() {
         panic!(
             "The macro [grammar] does not support generics, but `{name}` had `{}` implemented.",
-            c.ident.to_string()
+            c.ident
         )
     }
 
@@ -5,7 +5,6 @@ pub mod range;
 pub mod rule;
 
 pub use dimension::Dimension;
-pub use parse_type::ParseType;
 pub use prod_type::ProductionType;
 pub use range::RegexpRange;
 pub use rule::Rule;
@@ -1,26 +1,30 @@
 //! This module contains the production type definition and implementations.
 //! A production type is the interpreted type of a field in a 
 
+use std::fmt::Display;
+
 use super::{ProductionType, Dimension};
 
+#[allow(dead_code)] // not used by the grammar macro yet
 #[derive(Debug, PartialEq)]
 pub struct ParseType {
     prod_type: ProductionType,
     dimension: Dimension,
 }
 
+#[allow(dead_code)]
 impl ParseType {
     pub fn new(dimension: Dimension, prod_type: ProductionType) -> Self {
         Self { prod_type, dimension }
     }
 }
 
-impl ToString for ParseType {
-    fn to_string(&self) -> String {
+impl Display for ParseType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self.dimension {
-            Dimension::Scalar => self.prod_type.to_string(),
-            Dimension::Option => ["Option<", self.prod_type.to_string().as_str(), ">"].concat(),
-            Dimension::Many => ["Vec<", self.prod_type.to_string().as_str(), ">"].concat(),
+            Dimension::Scalar => write!(f, "{}", self.prod_type),
+            Dimension::Option => write!(f, "Option<{}>", self.prod_type),
+            Dimension::Many => write!(f, "Vec<{}>", self.prod_type),
         }
     }
 }
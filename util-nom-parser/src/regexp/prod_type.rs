@@ -1,6 +1,9 @@
 //! This module contains the production type definition and implementations.
 //! A production type is the interpreted type of a field in a 
 
+use std::fmt::Display;
+
+#[allow(dead_code)] // not used by the grammar macro yet
 #[derive(Debug, PartialEq)]
 pub enum ProductionType {
     /// The production type `Bool` is a binary value with `true` indicating
@@ -37,19 +40,17 @@ pub enum ProductionType {
     Float(u8),
 }
 
-impl ToString for ProductionType {
+impl Display for ProductionType {
     /// Convert the production type into a Rust-syntax type. This method should
     /// be used to convert the the production type.
-    fn to_string(&self) -> String {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Self::Bool => "bool".into(),
-            Self::String => "String".into(),
-            Self::Int(signed, bytes) => [
-                if *signed { "i" } else { "u" },
-                (bytes * 8).to_string().as_str(),
-            ]
-            .concat(),
-            Self::Float(bytes) => ["f", (bytes * 8).to_string().as_str()].concat(),
+            Self::Bool => write!(f, "bool"),
+            Self::String => write!(f, "String"),
+            Self::Int(signed, bytes) => {
+                write!(f, "{}{}", if *signed { "i" } else { "u" }, *bytes as u32 * 8)
+            }
+            Self::Float(bytes) => write!(f, "f{}", *bytes as u32 * 8),
         }
     }
 }
@@ -60,6 +60,8 @@ fn literal_to_string(literal: Literal) -> String {
 }
 
 impl From<TokenStream> for RegexpRange {
+    // the empty `match` arms are placeholders for error messages
+    #[allow(clippy::single_match)]
     fn from(attrs: TokenStream) -> Self {
         let mut range = Self::new();
         let mut iter = attrs.clone().into_iter();
@@ -123,19 +125,19 @@ impl From<TokenStream> for RegexpRange {
     }
 }
 
-impl Into<TokenStream> for RegexpRange {
-    fn into(mut self) -> TokenStream {
+impl From<RegexpRange> for TokenStream {
+    fn from(val: RegexpRange) -> Self {
         // TODO self = self.optimize();
 
         let select = {
-            let mut crs = self.select.chars();
+            let mut crs = val.select.chars();
             crs.next()
                 .map(|first| crs.fold(quote! { #first }, |a, b| quote! { #a | #b }))
         }
         .unwrap_or(TokenStream::from_str("_ if false").unwrap());
 
         let ranges = {
-            let mut crs = self.ranges.into_iter();
+            let mut crs = val.ranges.into_iter();
             crs.next().map(|(from, to)| {
                 // let from = from as usize;
                 // let to = to as usize;
@@ -1,6 +1,7 @@
 //! This module implements stdlib traits for StringBuilder.
 //! 
 //! - `From<T>` for strings and string slices
+//! - `Default` for the empty StringBuilder
 //! - `Into<String>` conversion of a StringBuilder into a String
 //! - `ops::Add<&str>` and the assign variant. Concatenation with
 //!   string slices
@@ -22,15 +23,22 @@ impl<T: AsRef<str>> From<T> for StringBuilder {
     }
 }
 
+// The default string builder is empty.
+impl Default for StringBuilder {
+    fn default() -> Self {
+        Self::from("")
+    }
+}
+
 // A string builder converted to string is simply
 // the buffer of the string builder.
 //
 // ```
 // TODO add example
 // ```
-impl Into<String> for StringBuilder {
-    fn into(self) -> String {
-        self.buffer
+impl From<StringBuilder> for String {
+    fn from(val: StringBuilder) -> Self {
+        val.buffer
     }
 }
 
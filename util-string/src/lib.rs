@@ -8,7 +8,7 @@ mod test;
 
 /// The StringBuilder is a String management structure
 /// that
-struct StringBuilder {
+pub struct StringBuilder {
     buffer: String,
     indent: usize,
 }
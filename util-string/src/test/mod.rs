@@ -5,7 +5,7 @@ use util_cases::CaseStyles;
 fn typescript_interface() {
     let mut builder = StringBuilder::from("");
     let interface_name = "Example Interface";
-    let fields = vec![
+    let _fields = [
         ("fieldA", "FieldType"),
         ("fieldB", "FieldType"),
         ("fieldArray", "Fields[]"),